@@ -0,0 +1,54 @@
+/// How literals compare to the text they are matched against.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CaseMode {
+	/// Characters must be exactly equal.
+	#[default]
+	Sensitive,
+	/// ASCII letters match regardless of case, all other characters must be exactly equal.
+	IgnoreAsciiCase,
+	/// All characters match regardless of case, comparing their lowercase forms. As exceptions 'ß' and 'ẞ' match "ss", 'ς' matches 'σ' and 'ſ' matches 's', so a literal may match text of a different byte length, like "STRASSE" matching "straße". Other full case foldings, like 'ﬃ' matching "FFI", are not applied.
+	IgnoreCase
+}
+impl CaseMode {
+
+	/* USAGE METHODS */
+
+	/// Check if the text starts with the literal in this mode. Returns the byte length of the matching part of the text.
+	pub fn match_prefix(&self, text:&str, literal:&str) -> Option<usize> {
+		match self {
+			CaseMode::Sensitive => text.starts_with(literal).then_some(literal.len()),
+			CaseMode::IgnoreAsciiCase => text.as_bytes().get(..literal.len()).filter(|prefix| prefix.eq_ignore_ascii_case(literal.as_bytes())).map(|_| literal.len()),
+			CaseMode::IgnoreCase => CaseMode::match_folded_prefix(text, literal)
+		}
+	}
+
+	/// Check if the text starts with the literal after folding the case of both. Returns the byte length of the matching part of the text.
+	fn match_folded_prefix(text:&str, literal:&str) -> Option<usize> {
+		let folded_literal:String = literal.chars().map(CaseMode::fold_char).collect();
+		if folded_literal.is_empty() {
+			return Some(0);
+		}
+		let mut folded_text:String = String::new();
+		for (index, char) in text.char_indices() {
+			folded_text += &CaseMode::fold_char(char);
+			if folded_text == folded_literal {
+				return Some(index + char.len_utf8());
+			}
+			if !folded_literal.starts_with(&folded_text) {
+				return None;
+			}
+		}
+		None
+	}
+
+	/// Map a character to its lowercase form, or to the form of its exception like "ss" for 'ß'.
+	fn fold_char(char:char) -> String {
+		match char {
+			'ß' | 'ẞ' => "ss".to_string(),
+			'ς' => "σ".to_string(),
+			'ſ' => "s".to_string(),
+			'K' => "k".to_string(),
+			_ => char.to_lowercase().collect()
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+	use crate::CaseMode;
+
+
+
+	/* PREFIX TESTS */
+
+	#[test]
+	fn test_case_mode_sensitive() {
+		assert_eq!(CaseMode::Sensitive.match_prefix("TRUE", "TRUE"), Some(4));
+		assert_eq!(CaseMode::Sensitive.match_prefix("True", "TRUE"), None);
+		assert_eq!(CaseMode::Sensitive.match_prefix("TR", "TRUE"), None);
+	}
+
+	#[test]
+	fn test_case_mode_ignore_ascii_case() {
+		assert_eq!(CaseMode::IgnoreAsciiCase.match_prefix("True or false", "TRUE"), Some(4));
+		assert_eq!(CaseMode::IgnoreAsciiCase.match_prefix("tr", "TRUE"), None);
+		assert_eq!(CaseMode::IgnoreAsciiCase.match_prefix("ÉTÉ", "été"), None);
+		assert_eq!(CaseMode::IgnoreAsciiCase.match_prefix("été", "été"), Some(5));
+	}
+
+	#[test]
+	fn test_case_mode_ignore_case() {
+		assert_eq!(CaseMode::IgnoreCase.match_prefix("ÉTÉ!", "été"), Some(5));
+		assert_eq!(CaseMode::IgnoreCase.match_prefix("ΣΟΦΟΣ", "σοφος"), Some(10));
+		assert_eq!(CaseMode::IgnoreCase.match_prefix("ΣΟΦΟΣ", "σοφος"), CaseMode::IgnoreCase.match_prefix("ΣΟΦΟΣ", "σοφοσ"));
+		assert_eq!(CaseMode::IgnoreCase.match_prefix("straße", "STRASSE"), Some(7));
+		assert_eq!(CaseMode::IgnoreCase.match_prefix("STRASSE", "straße"), Some(7));
+		assert_eq!(CaseMode::IgnoreCase.match_prefix("straß", "STRASSE"), None);
+		assert_eq!(CaseMode::IgnoreCase.match_prefix("anything", ""), Some(0));
+		assert_eq!(CaseMode::IgnoreCase.match_prefix("ﬃ", "FFI"), None);
+	}
+}
\ No newline at end of file
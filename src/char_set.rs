@@ -0,0 +1,88 @@
+use std::{ ops::RangeInclusive, fmt::{ Display, Formatter, Result } };
+
+
+
+/// A set of characters, described by single characters and ranges like "a-zA-Z0-9_".
+#[derive(Clone, PartialEq, Debug)]
+pub struct CharSet {
+	ranges:Vec<RangeInclusive<char>>,
+	negated:bool
+}
+impl CharSet {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a set from a description of characters and ranges like "a-zA-Z0-9_". A '-' at the start or end of the description is a literal, '\' escapes the character after it.
+	/// Panics when a range is reversed or the description ends in an unfinished escape.
+	pub fn new(description:&str) -> CharSet {
+		CharSet::try_new(description).unwrap_or_else(|message| panic!("{message}"))
+	}
+
+	/// Create a set from a description of characters and ranges like "a-zA-Z0-9_". Returns a message describing the problem when a range is reversed or the description ends in an unfinished escape.
+	pub fn try_new(description:&str) -> std::result::Result<CharSet, String> {
+		let mut chars:Vec<(char, bool)> = Vec::new(); // Character and whether it was escaped.
+		let mut description_chars = description.chars();
+		while let Some(char) = description_chars.next() {
+			if char == '\\' {
+				match description_chars.next() {
+					Some(escaped_char) => chars.push((escaped_char, true)),
+					None => return Err(format!("Character set \"{description}\" ends in an unfinished escape."))
+				}
+			} else {
+				chars.push((char, false));
+			}
+		}
+
+		let mut ranges:Vec<RangeInclusive<char>> = Vec::new();
+		let mut index:usize = 0;
+		while index < chars.len() {
+			let start:char = chars[index].0;
+			if index + 2 < chars.len() && chars[index + 1] == ('-', false) {
+				let end:char = chars[index + 2].0;
+				if end < start {
+					return Err(format!("Character set \"{description}\" contains reversed range {start}-{end}."));
+				}
+				ranges.push(start..=end);
+				index += 3;
+			} else {
+				ranges.push(start..=start);
+				index += 1;
+			}
+		}
+		Ok(CharSet {
+			ranges,
+			negated: false
+		})
+	}
+
+	/// Create a set from a list of ranges.
+	pub fn from_ranges(ranges:Vec<RangeInclusive<char>>) -> CharSet {
+		CharSet {
+			ranges,
+			negated: false
+		}
+	}
+
+	/// Return self matching exactly the characters it did not match before.
+	pub fn negated(mut self) -> Self {
+		self.negated = !self.negated;
+		self
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Check if the set contains the given character.
+	pub fn contains(&self, char:char) -> bool {
+		self.ranges.iter().any(|range| range.contains(&char)) != self.negated
+	}
+}
+impl Display for CharSet {
+	fn fmt(&self, f:&mut Formatter<'_>) -> Result {
+		const SPECIAL_CHARS:&[char] = &['\\', '-', ']', '^'];
+		let escape = |char:char| if SPECIAL_CHARS.contains(&char) { format!("\\{char}") } else { char.to_string() };
+		let ranges:String = self.ranges.iter().map(|range| if range.start() == range.end() { escape(*range.start()) } else { format!("{}-{}", escape(*range.start()), escape(*range.end())) }).collect();
+		write!(f, "[{}{ranges}]", if self.negated { "^" } else { "" })
+	}
+}
\ No newline at end of file
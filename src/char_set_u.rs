@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+	use crate::CharSet;
+
+
+
+	/* PARSING TESTS */
+
+	#[test]
+	fn test_ranges() {
+		let set:CharSet = CharSet::new("a-zA-Z0-9_");
+		assert!(set.contains('q'));
+		assert!(set.contains('Q'));
+		assert!(set.contains('7'));
+		assert!(set.contains('_'));
+		assert!(!set.contains('-'));
+		assert!(!set.contains('['));
+		assert!(!set.contains('é'));
+	}
+
+	#[test]
+	fn test_literal_dashes_and_escapes() {
+		let set:CharSet = CharSet::new("-+\\-\\\\x-");
+		assert!(set.contains('-'));
+		assert!(set.contains('+'));
+		assert!(set.contains('\\'));
+		assert!(set.contains('x'));
+		assert!(!set.contains(','));
+	}
+
+	#[test]
+	fn test_non_ascii_ranges() {
+		let set:CharSet = CharSet::new("ぁ-ゖ");
+		assert!(set.contains('ひ'));
+		assert!(!set.contains('キ'));
+	}
+
+	#[test]
+	fn test_negated() {
+		let set:CharSet = CharSet::new("0-9").negated();
+		assert!(!set.contains('4'));
+		assert!(set.contains('a'));
+		assert_eq!(set.to_string(), "[^0-9]");
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_reversed_range() {
+		CharSet::new("z-a");
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_unfinished_escape() {
+		CharSet::new("a\\");
+	}
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use std::{ ops::Range, sync::Arc };
+
+
+
+type BoundaryFunction = Arc<dyn Fn(&str, usize) -> bool + Send + Sync>;
+
+
+
+/// Where a text may be split into chunks that are scanned independently.
+#[derive(Clone)]
+pub enum ChunkBoundary {
+	/// Split directly after a line feed.
+	LineBreak,
+	/// Split at byte positions for which the function returns true. The function receives the full text and a byte position on a character boundary.
+	Custom(BoundaryFunction)
+}
+impl ChunkBoundary {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a boundary from a function receiving the full text and a byte position.
+	pub fn custom<T:Fn(&str, usize) -> bool + Send + Sync + 'static>(boundary_function:T) -> ChunkBoundary {
+		ChunkBoundary::Custom(Arc::new(boundary_function))
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Check if the text may be split at the given byte position.
+	pub fn is_boundary(&self, text:&str, position:usize) -> bool {
+		match self {
+			ChunkBoundary::LineBreak => position > 0 && text.as_bytes().get(position - 1) == Some(&b'\n'),
+			ChunkBoundary::Custom(boundary_function) => text.is_char_boundary(position) && boundary_function(text, position)
+		}
+	}
+
+	/// Split the text into at most the given amount of chunks of roughly equal size. Each chunk but the last ends at a boundary.
+	pub fn split_chunks(&self, text:&str, chunk_count:usize) -> Vec<Range<usize>> {
+		let mut chunks:Vec<Range<usize>> = Vec::new();
+		let mut chunk_start:usize = 0;
+		for chunk_index in 1..chunk_count.max(1) {
+			let target:usize = (text.len() * chunk_index / chunk_count).max(chunk_start + 1);
+			match (target..text.len()).find(|position| self.is_boundary(text, *position)) {
+				Some(chunk_end) => {
+					chunks.push(chunk_start..chunk_end);
+					chunk_start = chunk_end;
+				},
+				None => break
+			}
+		}
+		chunks.push(chunk_start..text.len());
+		chunks
+	}
+}
\ No newline at end of file
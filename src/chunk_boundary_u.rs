@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+	use crate::ChunkBoundary;
+
+
+
+	#[test]
+	fn test_chunk_boundary_line_break() {
+		let text:&str = "aa\nbb\ncc\ndd";
+		assert!(ChunkBoundary::LineBreak.is_boundary(text, 3));
+		assert!(!ChunkBoundary::LineBreak.is_boundary(text, 2));
+		assert!(!ChunkBoundary::LineBreak.is_boundary(text, 0));
+		assert_eq!(ChunkBoundary::LineBreak.split_chunks(text, 2), vec![0..6, 6..11]);
+		assert_eq!(ChunkBoundary::LineBreak.split_chunks(text, 1), vec![0..11]);
+		assert_eq!(ChunkBoundary::LineBreak.split_chunks(text, 100), vec![0..3, 3..6, 6..9, 9..11]);
+		assert_eq!(ChunkBoundary::LineBreak.split_chunks("no breaks", 4), vec![0..9]);
+	}
+
+	#[test]
+	fn test_chunk_boundary_custom() {
+		let boundary:ChunkBoundary = ChunkBoundary::custom(|text, position| text[position..].starts_with("--"));
+		assert_eq!(boundary.split_chunks("ab--cd--ef", 3), vec![0..6, 6..10]);
+
+		// Positions inside multi-byte characters are never boundaries.
+		let boundary:ChunkBoundary = ChunkBoundary::custom(|_, _| true);
+		assert_eq!(boundary.split_chunks("日本語", 3), vec![0..3, 3..6, 6..9]);
+	}
+}
\ No newline at end of file
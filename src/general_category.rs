@@ -0,0 +1,107 @@
+use crate::unicode_tables;
+
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GeneralCategory {
+	UppercaseLetter,
+	LowercaseLetter,
+	TitlecaseLetter,
+	ModifierLetter,
+	OtherLetter,
+	NonspacingMark,
+	SpacingMark,
+	EnclosingMark,
+	DecimalNumber,
+	LetterNumber,
+	OtherNumber,
+	ConnectorPunctuation,
+	DashPunctuation,
+	OpenPunctuation,
+	ClosePunctuation,
+	InitialPunctuation,
+	FinalPunctuation,
+	OtherPunctuation,
+	MathSymbol,
+	CurrencySymbol,
+	ModifierSymbol,
+	OtherSymbol,
+	SpaceSeparator,
+	LineSeparator,
+	ParagraphSeparator,
+	Control,
+	Format,
+	Surrogate,
+	PrivateUse,
+	Unassigned
+}
+impl GeneralCategory {
+
+	/* CATEGORY GROUPS */
+
+	/// All letter categories (L).
+	pub const LETTER:&[GeneralCategory] = &[GeneralCategory::UppercaseLetter, GeneralCategory::LowercaseLetter, GeneralCategory::TitlecaseLetter, GeneralCategory::ModifierLetter, GeneralCategory::OtherLetter];
+
+	/// All mark categories (M).
+	pub const MARK:&[GeneralCategory] = &[GeneralCategory::NonspacingMark, GeneralCategory::SpacingMark, GeneralCategory::EnclosingMark];
+
+	/// All number categories (N).
+	pub const NUMBER:&[GeneralCategory] = &[GeneralCategory::DecimalNumber, GeneralCategory::LetterNumber, GeneralCategory::OtherNumber];
+
+	/// All punctuation categories (P).
+	pub const PUNCTUATION:&[GeneralCategory] = &[GeneralCategory::ConnectorPunctuation, GeneralCategory::DashPunctuation, GeneralCategory::OpenPunctuation, GeneralCategory::ClosePunctuation, GeneralCategory::InitialPunctuation, GeneralCategory::FinalPunctuation, GeneralCategory::OtherPunctuation];
+
+	/// All symbol categories (S).
+	pub const SYMBOL:&[GeneralCategory] = &[GeneralCategory::MathSymbol, GeneralCategory::CurrencySymbol, GeneralCategory::ModifierSymbol, GeneralCategory::OtherSymbol];
+
+	/// All separator categories (Z).
+	pub const SEPARATOR:&[GeneralCategory] = &[GeneralCategory::SpaceSeparator, GeneralCategory::LineSeparator, GeneralCategory::ParagraphSeparator];
+
+	/// All other categories (C).
+	pub const OTHER:&[GeneralCategory] = &[GeneralCategory::Control, GeneralCategory::Format, GeneralCategory::Surrogate, GeneralCategory::PrivateUse, GeneralCategory::Unassigned];
+
+
+
+	/* USAGE METHODS */
+
+	/// Get the general category of the given character.
+	pub fn of(char:char) -> GeneralCategory {
+		unicode_tables::general_category(char)
+	}
+
+	/// Get the two-letter abbreviation of the category, like "Lu" for uppercase letters.
+	pub fn abbreviation(&self) -> &'static str {
+		match self {
+			GeneralCategory::UppercaseLetter => "Lu",
+			GeneralCategory::LowercaseLetter => "Ll",
+			GeneralCategory::TitlecaseLetter => "Lt",
+			GeneralCategory::ModifierLetter => "Lm",
+			GeneralCategory::OtherLetter => "Lo",
+			GeneralCategory::NonspacingMark => "Mn",
+			GeneralCategory::SpacingMark => "Mc",
+			GeneralCategory::EnclosingMark => "Me",
+			GeneralCategory::DecimalNumber => "Nd",
+			GeneralCategory::LetterNumber => "Nl",
+			GeneralCategory::OtherNumber => "No",
+			GeneralCategory::ConnectorPunctuation => "Pc",
+			GeneralCategory::DashPunctuation => "Pd",
+			GeneralCategory::OpenPunctuation => "Ps",
+			GeneralCategory::ClosePunctuation => "Pe",
+			GeneralCategory::InitialPunctuation => "Pi",
+			GeneralCategory::FinalPunctuation => "Pf",
+			GeneralCategory::OtherPunctuation => "Po",
+			GeneralCategory::MathSymbol => "Sm",
+			GeneralCategory::CurrencySymbol => "Sc",
+			GeneralCategory::ModifierSymbol => "Sk",
+			GeneralCategory::OtherSymbol => "So",
+			GeneralCategory::SpaceSeparator => "Zs",
+			GeneralCategory::LineSeparator => "Zl",
+			GeneralCategory::ParagraphSeparator => "Zp",
+			GeneralCategory::Control => "Cc",
+			GeneralCategory::Format => "Cf",
+			GeneralCategory::Surrogate => "Cs",
+			GeneralCategory::PrivateUse => "Co",
+			GeneralCategory::Unassigned => "Cn"
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+	use crate::GeneralCategory;
+
+
+
+	/* CATEGORY TESTS */
+
+	#[test]
+	fn test_letter_categories() {
+		assert_eq!(GeneralCategory::of('A'), GeneralCategory::UppercaseLetter);
+		assert_eq!(GeneralCategory::of('ß'), GeneralCategory::LowercaseLetter);
+		assert_eq!(GeneralCategory::of('ǅ'), GeneralCategory::TitlecaseLetter);
+		assert_eq!(GeneralCategory::of('ー'), GeneralCategory::ModifierLetter);
+		assert_eq!(GeneralCategory::of('キ'), GeneralCategory::OtherLetter);
+	}
+
+	#[test]
+	fn test_other_categories() {
+		assert_eq!(GeneralCategory::of('\u{301}'), GeneralCategory::NonspacingMark);
+		assert_eq!(GeneralCategory::of('٣'), GeneralCategory::DecimalNumber);
+		assert_eq!(GeneralCategory::of('Ⅻ'), GeneralCategory::LetterNumber);
+		assert_eq!(GeneralCategory::of('_'), GeneralCategory::ConnectorPunctuation);
+		assert_eq!(GeneralCategory::of('('), GeneralCategory::OpenPunctuation);
+		assert_eq!(GeneralCategory::of('€'), GeneralCategory::CurrencySymbol);
+		assert_eq!(GeneralCategory::of('😀'), GeneralCategory::OtherSymbol);
+		assert_eq!(GeneralCategory::of(' '), GeneralCategory::SpaceSeparator);
+		assert_eq!(GeneralCategory::of('\u{2028}'), GeneralCategory::LineSeparator);
+		assert_eq!(GeneralCategory::of('\n'), GeneralCategory::Control);
+		assert_eq!(GeneralCategory::of('\u{E000}'), GeneralCategory::PrivateUse);
+		assert_eq!(GeneralCategory::of('\u{378}'), GeneralCategory::Unassigned);
+	}
+
+	#[test]
+	fn test_category_groups() {
+		assert!(GeneralCategory::LETTER.contains(&GeneralCategory::of('é')));
+		assert!(GeneralCategory::NUMBER.contains(&GeneralCategory::of('5')));
+		assert!(!GeneralCategory::LETTER.contains(&GeneralCategory::of('5')));
+		assert_eq!(GeneralCategory::LowercaseLetter.abbreviation(), "Ll");
+	}
+}
\ No newline at end of file
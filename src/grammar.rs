@@ -0,0 +1,335 @@
+use std::{ error::Error, fmt::{ Display, Formatter } };
+use crate::{ CharSet, MatchExpr, MatcherRegistry, SourceIndex, SourcePosition };
+
+
+
+/// The deepest parenthesized expressions may be nested, as parsing recurses into each of them.
+const MAX_NESTING_DEPTH:usize = 250;
+
+
+
+/// An error in the text of a grammar.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GrammarError {
+	pub position:SourcePosition,
+	pub message:String
+}
+impl Display for GrammarError {
+	fn fmt(&self, f:&mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} at {}", self.message, self.position)
+	}
+}
+impl Error for GrammarError {}
+
+
+
+/// Compile a grammar written as text into a registry. See `MatcherRegistry::from_grammar` for the syntax.
+pub(crate) fn compile_grammar(source:&str) -> Result<MatcherRegistry, GrammarError> {
+	let mut parser:GrammarParser = GrammarParser { source, cursor: 0, references: Vec::new(), depth: 0 };
+	let mut rules:Vec<(String, usize, MatchExpr)> = Vec::new();
+	parser.skip_whitespace();
+	while parser.cursor < source.len() {
+		let name_offset:usize = parser.cursor;
+		let name:String = parser.parse_identifier().ok_or_else(|| parser.error("expected rule name"))?;
+		if rules.iter().any(|(rule_name, _, _)| *rule_name == name) {
+			return Err(parser.error_at(name_offset, &format!("rule \"{name}\" is defined twice")));
+		}
+		parser.skip_whitespace();
+		if !parser.eat("=") {
+			return Err(parser.error("expected '=' after rule name"));
+		}
+		let definition:MatchExpr = parser.parse_choice()?;
+		parser.eat(";");
+		parser.skip_whitespace();
+		rules.push((name, name_offset, definition));
+	}
+
+	// Validate all references now, so mistakes do not surface as panics while matching.
+	for (name, offset) in &parser.references {
+		if !rules.iter().any(|(rule_name, _, _)| rule_name == name) {
+			return Err(parser.error_at(*offset, &format!("undefined rule \"{name}\"")));
+		}
+	}
+
+	let mut registry:MatcherRegistry = MatcherRegistry::new();
+	for (name, _, definition) in rules {
+		registry = if name.starts_with('_') { registry.with_hidden_rule(&name, definition) } else { registry.with_matcher(&name, definition) };
+	}
+	Ok(registry)
+}
+
+
+
+/// Replace the escapes `\n`, `\r` and `\t` by the characters they represent, keeping all other escapes.
+fn unescape_control_chars(text:&str) -> String {
+	let mut result:String = String::new();
+	let mut chars = text.chars();
+	while let Some(char) = chars.next() {
+		if char != '\\' {
+			result.push(char);
+			continue;
+		}
+		match chars.next() {
+			Some('n') => result.push('\n'),
+			Some('r') => result.push('\r'),
+			Some('t') => result.push('\t'),
+			Some(escaped_char) => {
+				result.push('\\');
+				result.push(escaped_char);
+			},
+			None => result.push('\\')
+		}
+	}
+	result
+}
+
+
+
+struct GrammarParser<'a> {
+	source:&'a str,
+	cursor:usize,
+	references:Vec<(String, usize)>,
+	depth:usize
+}
+impl<'a> GrammarParser<'a> {
+
+	/* EXPRESSION METHODS */
+
+	/// Parse alternatives separated by '|'.
+	fn parse_choice(&mut self) -> Result<MatchExpr, GrammarError> {
+		let mut alternatives:Vec<MatchExpr> = vec![self.parse_sequence()?];
+		while self.eat("|") {
+			alternatives.push(self.parse_sequence()?);
+		}
+		Ok(if alternatives.len() == 1 { alternatives.remove(0) } else { MatchExpr::choice(alternatives) })
+	}
+
+	/// Parse a sequence of elements, ending at a '|', ')', ';', the end of the grammar or the start of the next rule.
+	fn parse_sequence(&mut self) -> Result<MatchExpr, GrammarError> {
+		let mut sequence:Option<MatchExpr> = None;
+		loop {
+			self.skip_whitespace();
+			let remaining:&str = &self.source[self.cursor..];
+			if remaining.is_empty() || remaining.starts_with(['|', ')', ';']) || self.at_rule_start() {
+				break;
+			}
+			let element:MatchExpr = self.parse_element()?;
+			sequence = Some(match sequence {
+				Some(sequence) => sequence + element,
+				None => element
+			});
+		}
+		Ok(sequence.unwrap_or_else(|| MatchExpr::new("")))
+	}
+
+	/// Parse an element with an optional label, prefix and suffixes.
+	fn parse_element(&mut self) -> Result<MatchExpr, GrammarError> {
+		let label_start:usize = self.cursor;
+		let label:Option<String> = match self.parse_identifier() {
+			Some(label) if self.eat(":") => Some(label),
+			_ => {
+				self.cursor = label_start;
+				None
+			}
+		};
+
+		self.skip_whitespace();
+		let element:MatchExpr = if self.eat("!") {
+			!self.parse_suffixed()?
+		} else if self.eat("&") {
+			MatchExpr::followed_by(self.parse_suffixed()?)
+		} else {
+			self.parse_suffixed()?
+		};
+		Ok(match label {
+			Some(label) => MatchExpr::named(&label, element),
+			None => element
+		})
+	}
+
+	/// Parse a primary expression followed by any quantifiers.
+	fn parse_suffixed(&mut self) -> Result<MatchExpr, GrammarError> {
+		let mut expression:MatchExpr = self.parse_primary()?;
+		loop {
+			expression = if self.eat("*") {
+				MatchExpr::optional_repeat_max(expression)
+			} else if self.eat("+") {
+				MatchExpr::repeat_max(expression)
+			} else if self.eat("?") {
+				MatchExpr::optional(expression)
+			} else if self.source[self.cursor..].starts_with('{') {
+				let (min_count, max_count) = self.parse_count_range()?;
+				MatchExpr::repeat(expression, min_count..=max_count)
+			} else {
+				return Ok(expression);
+			};
+		}
+	}
+
+	/// Parse a literal, character class, any-character dot, rule reference or parenthesized expression.
+	fn parse_primary(&mut self) -> Result<MatchExpr, GrammarError> {
+		self.skip_whitespace();
+		let start:usize = self.cursor;
+		match self.source[self.cursor..].chars().next() {
+			Some(quote @ ('"' | '\'')) => {
+				self.cursor += 1;
+				let literal:String = self.parse_string(quote)?;
+				Ok(if self.eat_directly("i") { MatchExpr::ignore_case(&literal) } else { MatchExpr::new(literal) })
+			},
+			Some('[') => {
+				self.cursor += 1;
+				let negated:bool = self.eat_directly("^");
+				let description:String = unescape_control_chars(self.parse_until_unescaped(']').ok_or_else(|| self.error_at(start, "unclosed character class"))?);
+				let set:CharSet = CharSet::try_new(&description).map_err(|message| self.error_at(start, &message))?;
+				Ok(MatchExpr::char_set(if negated { set.negated() } else { set }))
+			},
+			Some('.') => {
+				self.cursor += 1;
+				Ok(MatchExpr::char_matching("any character", |_| true))
+			},
+			Some('(') => {
+				if self.depth == MAX_NESTING_DEPTH {
+					return Err(self.error("expression nested too deeply"));
+				}
+				self.cursor += 1;
+				self.depth += 1;
+				let expression:MatchExpr = self.parse_choice()?;
+				self.depth -= 1;
+				if !self.eat(")") {
+					return Err(self.error("expected ')'"));
+				}
+				Ok(expression)
+			},
+			_ => match self.parse_identifier() {
+				Some(name) => {
+					self.references.push((name.clone(), start));
+					Ok(MatchExpr::reference(&name))
+				},
+				None => Err(self.error("expected expression"))
+			}
+		}
+	}
+
+	/// Parse the contents of a string literal after its opening quote.
+	fn parse_string(&mut self, quote:char) -> Result<String, GrammarError> {
+		let start:usize = self.cursor - 1;
+		let mut literal:String = String::new();
+		let mut chars = self.source[self.cursor..].char_indices();
+		while let Some((offset, char)) = chars.next() {
+			match char {
+				_ if char == quote => {
+					self.cursor += offset + 1;
+					return Ok(literal);
+				},
+				'\\' => match chars.next() {
+					Some((_, 'n')) => literal.push('\n'),
+					Some((_, 'r')) => literal.push('\r'),
+					Some((_, 't')) => literal.push('\t'),
+					Some((_, escaped_char)) => literal.push(escaped_char),
+					None => break
+				},
+				_ => literal.push(char)
+			}
+		}
+		Err(self.error_at(start, "unclosed string literal"))
+	}
+
+	/// Parse a count range like `{2}`, `{2,}` or `{2,5}`.
+	fn parse_count_range(&mut self) -> Result<(usize, usize), GrammarError> {
+		let start:usize = self.cursor;
+		let contents:&str = self.parse_until_unescaped('}').map(|contents| &contents[1..]).ok_or_else(|| self.error_at(start, "unclosed count range"))?;
+		let parse_count = |count:&str| count.trim().parse::<usize>().ok();
+		let counts:Option<(usize, usize)> = match contents.split_once(',') {
+			None => parse_count(contents).map(|count| (count, count)),
+			Some((min_count, max_count)) if max_count.trim().is_empty() => parse_count(min_count).map(|min_count| (min_count, usize::MAX)),
+			Some((min_count, max_count)) => parse_count(min_count).zip(parse_count(max_count))
+		};
+		match counts {
+			Some((min_count, max_count)) if min_count <= max_count => Ok((min_count, max_count)),
+			_ => Err(self.error_at(start, "invalid count range"))
+		}
+	}
+
+
+
+	/* TOKEN METHODS */
+
+	/// Parse an identifier of XID characters and underscores.
+	fn parse_identifier(&mut self) -> Option<String> {
+		let remaining:&str = &self.source[self.cursor..];
+		let length:usize = remaining.char_indices().find(|(index, char)| !(*char == '_' || char.is_alphanumeric()) || (*index == 0 && char.is_numeric())).map(|(index, _)| index).unwrap_or(remaining.len());
+		if length == 0 {
+			return None;
+		}
+		self.cursor += length;
+		Some(remaining[..length].to_string())
+	}
+
+	/// Check if the cursor is at the start of a new rule definition, an identifier followed by '='.
+	fn at_rule_start(&mut self) -> bool {
+		let start:usize = self.cursor;
+		let is_rule_start:bool = self.parse_identifier().is_some() && { self.skip_whitespace(); self.source[self.cursor..].starts_with('=') };
+		self.cursor = start;
+		is_rule_start
+	}
+
+	/// Consume everything up to the given unescaped character, returning the consumed text without the character.
+	fn parse_until_unescaped(&mut self, end_char:char) -> Option<&'a str> {
+		let remaining:&'a str = &self.source[self.cursor..];
+		let mut escaped:bool = false;
+		for (offset, char) in remaining.char_indices() {
+			if char == end_char && !escaped {
+				self.cursor += offset + 1;
+				return Some(&remaining[..offset]);
+			}
+			escaped = char == '\\' && !escaped;
+		}
+		None
+	}
+
+	/// Skip whitespace and comments starting with '#'.
+	fn skip_whitespace(&mut self) {
+		loop {
+			let remaining:&str = &self.source[self.cursor..];
+			let trimmed:&str = remaining.trim_start();
+			self.cursor += remaining.len() - trimmed.len();
+			if !trimmed.starts_with('#') {
+				return;
+			}
+			self.cursor += trimmed.find('\n').unwrap_or(trimmed.len());
+		}
+	}
+
+	/// Skip whitespace, then consume the given token if it is next.
+	fn eat(&mut self, token:&str) -> bool {
+		self.skip_whitespace();
+		self.eat_directly(token)
+	}
+
+	/// Consume the given token if it is directly next, without skipping whitespace.
+	fn eat_directly(&mut self, token:&str) -> bool {
+		if self.source[self.cursor..].starts_with(token) {
+			self.cursor += token.len();
+			true
+		} else {
+			false
+		}
+	}
+
+
+
+	/* ERROR METHODS */
+
+	/// Create an error at the cursor.
+	fn error(&self, message:&str) -> GrammarError {
+		self.error_at(self.cursor, message)
+	}
+
+	/// Create an error at the given byte offset.
+	fn error_at(&self, offset:usize, message:&str) -> GrammarError {
+		GrammarError {
+			position: SourceIndex::new(self.source).position(offset),
+			message: message.to_string()
+		}
+	}
+}
\ No newline at end of file
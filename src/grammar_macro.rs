@@ -0,0 +1,126 @@
+/// Declare a set of recursive rules from a PEG-like grammar at compile time. Every rule `name = expression;` becomes a local `Rule` variable with the same name, so rules can reference themselves and each other in any order. Referencing a rule that is not defined is a compile error.
+/// Expressions are sequences of elements, with alternatives separated by `|`. Elements are string or character literals, rule names, `.` for any character, parenthesized expressions and `{ rust_expression }` for any other predicate.
+/// Elements can be labeled as `label: element`, prefixed with `!` to match one character the element does not start with or `&` to require the element without consuming it, and followed by `*`, `+` or `?`.
+/// The grammar is parsed token by token, so large grammars may need a higher `#![recursion_limit]`.
+#[macro_export]
+macro_rules! grammar {
+
+	/* RULES */
+
+	(@rules [$($rules:tt)*] $name:ident = $($rest:tt)*) => {
+		$crate::grammar!(@rule_body [$($rules)*] $name [] $($rest)*)
+	};
+	(@rules [$(($name:ident [$($body:tt)*]))*]) => {
+		$(let $name:$crate::Rule = $crate::Rule::new(stringify!($name));)*
+		$($name.define($crate::grammar!(@choice [] [] $($body)*));)*
+	};
+	(@rule_body [$($rules:tt)*] $name:ident [$($body:tt)*] ; $($rest:tt)*) => {
+		$crate::grammar!(@rules [$($rules)* ($name [$($body)*])] $($rest)*)
+	};
+	(@rule_body [$($rules:tt)*] $name:ident [$($body:tt)*] $next:tt $($rest:tt)*) => {
+		$crate::grammar!(@rule_body [$($rules)*] $name [$($body)* $next] $($rest)*)
+	};
+
+
+
+	/* CHOICES */
+
+	(@choice [$($alternatives:tt)*] [$($current:tt)*] | $($rest:tt)*) => {
+		$crate::grammar!(@choice [$($alternatives)* [$($current)*]] [] $($rest)*)
+	};
+	(@choice [$($alternatives:tt)*] [$($current:tt)*] $next:tt $($rest:tt)*) => {
+		$crate::grammar!(@choice [$($alternatives)*] [$($current)* $next] $($rest)*)
+	};
+	(@choice [] [$($current:tt)*]) => {
+		$crate::grammar!(@sequence [] $($current)*)
+	};
+	(@choice [$([$($alternative:tt)*])*] [$($current:tt)*]) => {
+		$crate::MatchExpr::choice(vec![$($crate::grammar!(@sequence [] $($alternative)*),)* $crate::grammar!(@sequence [] $($current)*)])
+	};
+
+
+
+	/* SEQUENCES */
+
+	(@sequence []) => {
+		$crate::MatchExpr::new("")
+	};
+	(@sequence [$first:tt $($elements:tt)*]) => {
+		$first $(+ $elements)*
+	};
+	(@sequence [$($elements:tt)*] $label:ident : $($rest:tt)*) => {
+		$crate::grammar!(@element [$($elements)*] ($label) () $($rest)*)
+	};
+	(@sequence [$($elements:tt)*] $($rest:tt)+) => {
+		$crate::grammar!(@element [$($elements)*] () () $($rest)*)
+	};
+
+
+
+	/* ELEMENTS */
+
+	(@element [$($elements:tt)*] ($($label:ident)?) () ! $($rest:tt)*) => {
+		$crate::grammar!(@element [$($elements)*] ($($label)?) (not) $($rest)*)
+	};
+	(@element [$($elements:tt)*] ($($label:ident)?) () & $($rest:tt)*) => {
+		$crate::grammar!(@element [$($elements)*] ($($label)?) (followed_by) $($rest)*)
+	};
+	(@element [$($elements:tt)*] ($($label:ident)?) ($($prefix:ident)?) $atom:tt $($rest:tt)*) => {
+		$crate::grammar!(@suffix [$($elements)*] ($($label)?) ($($prefix)?) ($crate::grammar!(@atom $atom)) $($rest)*)
+	};
+	(@suffix [$($elements:tt)*] ($($label:ident)?) ($($prefix:ident)?) ($element:expr) * $($rest:tt)*) => {
+		$crate::grammar!(@suffix [$($elements)*] ($($label)?) ($($prefix)?) ($crate::MatchExpr::optional_repeat_max($element)) $($rest)*)
+	};
+	(@suffix [$($elements:tt)*] ($($label:ident)?) ($($prefix:ident)?) ($element:expr) + $($rest:tt)*) => {
+		$crate::grammar!(@suffix [$($elements)*] ($($label)?) ($($prefix)?) ($crate::MatchExpr::repeat_max($element)) $($rest)*)
+	};
+	(@suffix [$($elements:tt)*] ($($label:ident)?) ($($prefix:ident)?) ($element:expr) ? $($rest:tt)*) => {
+		$crate::grammar!(@suffix [$($elements)*] ($($label)?) ($($prefix)?) ($crate::MatchExpr::optional($element)) $($rest)*)
+	};
+	(@suffix [$($elements:tt)*] ($($label:ident)?) ($($prefix:ident)?) ($element:expr) $($rest:tt)*) => {
+		$crate::grammar!(@sequence [$($elements)* ($crate::grammar!(@label ($($label)?) ($crate::grammar!(@prefix ($($prefix)?) $element))))] $($rest)*)
+	};
+	(@prefix () $element:expr) => {
+		$element
+	};
+	(@prefix (not) $element:expr) => {
+		!$element
+	};
+	(@prefix (followed_by) $element:expr) => {
+		$crate::MatchExpr::followed_by($element)
+	};
+	(@label () $element:expr) => {
+		$element
+	};
+	(@label ($label:ident) $element:expr) => {
+		$crate::MatchExpr::named(stringify!($label), $element)
+	};
+
+
+
+	/* ATOMS */
+
+	(@atom ($($inner:tt)*)) => {
+		$crate::grammar!(@choice [] [] $($inner)*)
+	};
+	(@atom {$predicate:expr}) => {
+		$crate::MatchExpr::new($predicate)
+	};
+	(@atom .) => {
+		$crate::MatchExpr::char_matching("any character", |_| true)
+	};
+	(@atom $literal:literal) => {
+		$crate::MatchExpr::new($literal)
+	};
+	(@atom $rule:ident) => {
+		$crate::MatchExpr::new($rule.clone())
+	};
+
+
+
+	/* ENTRY */
+
+	($($grammar:tt)+) => {
+		$crate::grammar!(@rules [] $($grammar)+)
+	};
+}
\ No newline at end of file
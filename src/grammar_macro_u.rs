@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ MatchExpr, MatchHit, TextPredicate };
+
+
+
+	/* GRAMMAR MACRO TESTS */
+
+	#[test]
+	fn test_grammar_macro_recursion() {
+		crate::grammar! {
+			sum = sum "+" product | product;
+			product = product "*" atom | atom;
+			atom = number | "(" sum ")";
+			number = {MatchExpr::unsigned_integer()};
+		}
+		let result:MatchHit = sum.parse_complete("1+2*(3+4)").unwrap();
+		assert_eq!(result.type_name, "sum");
+		assert_eq!(result.find_child_by_type_path(&["product", "atom", "sum"]).unwrap().contents, "3+4");
+		assert_eq!(number.match_text("42").unwrap().type_name, "number");
+		assert!(sum.parse_complete("1+").is_err());
+	}
+
+	#[test]
+	fn test_grammar_macro_operators() {
+		crate::grammar! {
+			pair = key: (!'=')+ '=' value: (!"\n")* ;
+			call = name: {MatchExpr::word()} &'(';
+			list = "[" items? "]";
+			items = {MatchExpr::digit()} ("," {MatchExpr::digit()})*;
+			anything = . .;
+			empty = ;
+		}
+		let result:MatchHit = pair.match_text("名前=太郎\nx").unwrap();
+		assert_eq!(&result["key"], "名前");
+		assert_eq!(&result["value"], "太郎");
+		assert_eq!(call.match_text("print(x)").unwrap().length, 5);
+		assert_eq!(call.match_text("print x"), None);
+		assert_eq!(list.match_text("[]").unwrap().length, 2);
+		assert_eq!(list.match_text("[1,2,3]").unwrap().length, 7);
+		assert_eq!(list.match_text("[1,]"), None);
+		assert_eq!(anything.match_text("日本").unwrap().length, 6);
+		assert_eq!(empty.match_text("x").unwrap().length, 0);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ GrammarError, MatchHit, MatcherRegistry, SourcePosition, TextPredicate };
+
+
+
+	/* COMPILATION TESTS */
+
+	#[test]
+	fn test_grammar_ini() {
+		const GRAMMAR:&str = "
+			# Groups of variables.
+			group = \"[\" category_name:(!\"]\")* \"]\" _row*
+			_row = [ \\t\\r\\n]* variable_row:(name:(![=\\n])+ \"=\" value:(!\"\\n\")*)
+			whitespace = [ \\t\\r\\n]+
+		";
+		let registry:MatcherRegistry = MatcherRegistry::from_grammar(GRAMMAR).unwrap();
+		let result:MatchHit = registry.parse_complete("[user]\nname=bob\nage=32\n\n[other]\n").unwrap();
+		assert_eq!(result.find_children(|child| child.type_name == "category_name").iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["user", "other"]);
+		assert_eq!(result.find_children(|child| child.type_name == "value").iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["bob", "32"]);
+		assert_eq!(result.find_children(|child| child.type_name == "value")[1].span(), 20..22);
+		assert!(registry.matcher_by_name("_row").is_some());
+		assert_eq!(registry.match_text("name=bob"), None);
+	}
+
+	#[test]
+	fn test_grammar_recursion() {
+		const GRAMMAR:&str = "
+			sum = sum \"+\" product | product
+			product = product \"*\" _atom | _atom
+			_atom = number | \"(\" sum \")\"
+			number = [0-9]+
+		";
+		let registry:MatcherRegistry = MatcherRegistry::from_grammar(GRAMMAR).unwrap();
+		let result:MatchHit = registry.parse_complete("1+2*(3+4)").unwrap();
+		assert_eq!(result.type_name, "sum");
+		assert_eq!(result.type_name_tree(), "sum\n| sum\n| product\n| | product\n| | sum\n| | | sum\n| | | product");
+		assert_eq!(result.find_child_by_type_path(&["product", "sum"]).unwrap().contents, "3+4");
+	}
+
+	#[test]
+	fn test_grammar_operators() {
+		let registry:MatcherRegistry = MatcherRegistry::from_grammar("
+			keyword = (\"if\"i | \"else\"i) ![a-z]
+			hex = '0x' [0-9a-fA-F]{2,4}
+			call = name:[a-z]+ &\"(\" ; maybe = \"a\"? \"b\"
+			anything = .
+		").unwrap();
+		assert_eq!(registry["keyword"].1.match_text("IF (").unwrap().length, 3);
+		assert_eq!(registry["hex"].1.match_text("0xfffff").unwrap().length, 6);
+		assert_eq!(registry["hex"].1.match_text("0xf"), None);
+		assert_eq!(registry["call"].1.match_text("print(").unwrap().length, 5);
+		assert_eq!(registry["call"].1.match_text("print "), None);
+		assert_eq!(registry["maybe"].1.match_text("b").unwrap().length, 1);
+		assert_eq!(registry["anything"].1.match_text("日").unwrap().length, 3);
+	}
+
+
+
+	/* ERROR TESTS */
+
+	#[test]
+	fn test_grammar_undefined_rule() {
+		let error:GrammarError = MatcherRegistry::from_grammar("a = \"x\" b\nb = c").err().unwrap();
+		assert_eq!(error, GrammarError { position: SourcePosition { line: 2, column: 5 }, message: "undefined rule \"c\"".to_string() });
+		assert_eq!(error.to_string(), "undefined rule \"c\" at line 2, col 5");
+	}
+
+	#[test]
+	fn test_grammar_syntax_errors() {
+		let error = |grammar:&str| MatcherRegistry::from_grammar(grammar).err().unwrap();
+		assert_eq!(error("a = (\"x\"").message, "expected ')'");
+		assert_eq!(error("a = \"x").position, SourcePosition { line: 1, column: 5 });
+		assert_eq!(error("a = \"x").message, "unclosed string literal");
+		assert_eq!(error("a = [a-").message, "unclosed character class");
+		assert_eq!(error("a = [z-a]").message, "Character set \"z-a\" contains reversed range z-a.");
+		assert_eq!(error("a = \"x\"{3,1}").message, "invalid count range");
+		assert_eq!(error("a \"x\"").message, "expected '=' after rule name");
+		assert_eq!(error("a = \"x\"\na = \"y\"").position, SourcePosition { line: 2, column: 1 });
+		assert_eq!(error("a = \"x\" )").message, "expected rule name");
+		assert_eq!(error("a = *").message, "expected expression");
+	}
+
+	#[test]
+	fn test_grammar_nesting_limit() {
+		let nested = |depth:usize| format!("a = {}\"x\"{}", "(".repeat(depth), ")".repeat(depth));
+		assert_eq!(MatcherRegistry::from_grammar(&nested(250)).unwrap().match_text("x").unwrap().length, 1);
+		let error:GrammarError = MatcherRegistry::from_grammar(&nested(251)).err().unwrap();
+		assert_eq!(error, GrammarError { position: SourcePosition { line: 1, column: 255 }, message: "expression nested too deeply".to_string() });
+		assert_eq!(MatcherRegistry::from_grammar(&format!("a = {}", "(".repeat(100_000))).err().unwrap().message, "expression nested too deeply");
+	}
+}
\ No newline at end of file
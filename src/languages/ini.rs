@@ -1,4 +1,4 @@
-use crate::{ MatchHit, MatchExpr, MatcherRegistry };
+use crate::{ MatchFailure, MatchHit, MatchExpr, MatcherRegistry };
 
 
 
@@ -8,7 +8,7 @@ const VARIABLE_VALUE_ID:&str = "value";
 
 
 
-type StringFormatter = &'static dyn Fn(&str) -> String;
+type StringFormatter = &'static (dyn Fn(&str) -> String + Send + Sync);
 pub struct IniParser {
 	matcher_set:MatcherRegistry,
 	formatter:Option<StringFormatter>
@@ -16,6 +16,7 @@ pub struct IniParser {
 impl IniParser {
 
 	/// Create a new ini parser.
+	#[allow(clippy::new_without_default)]
 	pub fn new() -> IniParser {
 		IniParser {
 			matcher_set: MatcherRegistry::new().with_matchers(vec![
@@ -52,7 +53,21 @@ impl IniParser {
 
 	/// Parse some text.
 	pub fn parse(&self, text:&str) -> MatchHit {
-		let mut results = self.matcher_set.multi_match_text(text);
+		self.format_values(self.matcher_set.multi_match_text(text))
+	}
+
+	/// Parse some text, failing when any of the text is not valid ini.
+	pub fn parse_complete(&self, text:&str) -> Result<MatchHit, MatchFailure> {
+		self.matcher_set.parse_complete(text).map(|results| self.format_values(results))
+	}
+
+	/// Check that all of the text is valid ini.
+	pub fn validate(&self, text:&str) -> Result<(), MatchFailure> {
+		self.matcher_set.parse_complete(text).map(|_| ())
+	}
+
+	/// Apply the value formatter to all values in the results.
+	fn format_values(&self, mut results:MatchHit) -> MatchHit {
 		if let Some(formatter) = self.formatter {
 			results.execute_recursive_mut(|text_match| {
 				if text_match.type_name == VARIABLE_VALUE_ID {
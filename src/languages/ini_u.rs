@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-	use crate::{ MatchHit, languages::IniParser };
+	use crate::{ Expectation, MatchFailure, MatchHit, SourceIndex, SourcePosition, languages::IniParser };
 
 
 
@@ -47,4 +47,57 @@ mod tests {
 		let age:&str = &age_obj.find_child(|child| child.type_name == "value").unwrap().contents;
 		assert_eq!(age, "32");
 	}
+
+	#[test]
+	fn test_ini_positions() {
+		const INI_CODE:&str = "[user]\nname=bob\n\n[test results]\ntest1=full failure";
+		let parse_result:MatchHit = IniParser::new().parse(INI_CODE);
+		let index:SourceIndex = SourceIndex::new(INI_CODE);
+
+		let category_positions:Vec<SourcePosition> = parse_result.find_children(|child| child.type_name == "category_name").iter().map(|child| child.start_position(&index)).collect();
+		assert_eq!(category_positions, vec![SourcePosition { line: 1, column: 2 }, SourcePosition { line: 4, column: 2 }]);
+
+		let value:&MatchHit = parse_result.find_children(|child| child.type_name == "value")[1];
+		assert_eq!(value.contents, "full failure");
+		assert_eq!(value.start_position(&index).to_string(), "line 5, col 7");
+	}
+
+	#[test]
+	fn test_ini_multi_byte() {
+		const INI_CODE:&str = "[ユーザー]\n名前=太郎\nmood=😀 \n\n[émoji]\nキー=🔒";
+		let parse_result:MatchHit = IniParser::new().parse(INI_CODE);
+
+		assert_eq!(parse_result.find_children(|child| child.type_name == "category_name").iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["ユーザー", "émoji"]);
+		assert_eq!(parse_result.find_children(|child| child.type_name == "name").iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["名前", "mood", "キー"]);
+		assert_eq!(parse_result.find_children(|child| child.type_name == "value").iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["太郎", "😀 ", "🔒"]);
+	}
+
+	#[test]
+	fn test_ini_parse_complete() {
+		let parser:IniParser = IniParser::new().with_value_formatter(&|value| value.trim().to_string());
+		let parse_result:MatchHit = parser.parse_complete("[user]\nname= bob \n").unwrap();
+		assert_eq!(parse_result.find_child(|child| child.type_name == "value").unwrap().contents, "bob");
+		assert_eq!(parser.validate("[user]\nname=bob\n\n[other]\nage=32"), Ok(()));
+
+		const INI_CODE:&str = "[user]\nname=bob\n]garbage";
+		assert_eq!(parser.parse(INI_CODE).length, 16);
+		let failure:MatchFailure = parser.validate(INI_CODE).unwrap_err();
+
+		// The furthest attempt reads "]garbage" as a variable name and misses its "=".
+		assert_eq!(failure.position, 24);
+		assert_eq!(failure.found, None);
+		assert!(failure.expected.contains(&Expectation::Literal("=".to_string())));
+		assert_eq!(SourceIndex::new(INI_CODE).position(failure.position).to_string(), "line 3, col 9");
+	}
+
+	#[test]
+	fn test_ini_parser_shared_between_threads() {
+		let parser:IniParser = IniParser::new().with_value_formatter(&|value| value.trim().to_string());
+		let results:Vec<MatchHit> = std::thread::scope(|scope| {
+			let handles = ["[a]\nx=1", "[b]\ny=2"].map(|text| scope.spawn(|| parser.parse(text)));
+			handles.map(|handle| handle.join().unwrap()).to_vec()
+		});
+		assert_eq!(results[0], parser.parse("[a]\nx=1"));
+		assert_eq!(results[1], parser.parse("[b]\ny=2"));
+	}
 }
\ No newline at end of file
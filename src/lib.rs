@@ -1,15 +1,66 @@
 pub mod languages;
 
+mod case_mode;
+mod case_mode_u;
+mod char_set;
+mod char_set_u;
+mod chunk_boundary;
+mod chunk_boundary_u;
+mod general_category;
+mod general_category_u;
+mod grammar;
+mod grammar_u;
+mod grammar_macro;
+mod grammar_macro_u;
 mod matcher_registry;
 mod matcher_registry_u;
+mod match_context;
+mod match_context_u;
+mod match_failure;
+mod match_failure_u;
 mod match_hit;
 mod match_hit_u;
+mod match_iter;
+mod match_iter_u;
+mod match_span;
+mod match_span_u;
+mod match_stream;
+mod match_stream_u;
 mod match_expression;
 mod match_expression_u;
+mod regex;
+mod regex_u;
+mod replacer;
+mod replacer_u;
+mod rule;
+mod rule_u;
+mod source_index;
+mod source_index_u;
+mod split;
+mod split_u;
 mod text_predicate;
 mod text_predicate_u;
+mod typed_expr;
+mod typed_expr_u;
+mod unicode_tables;
 
+pub use case_mode::*;
+pub use char_set::*;
+pub use chunk_boundary::*;
+pub use general_category::*;
+pub use grammar::GrammarError;
 pub use matcher_registry::*;
+pub use match_context::*;
+pub use match_failure::*;
 pub use match_hit::*;
+pub use match_iter::*;
+pub use match_span::*;
+pub use match_stream::*;
 pub use match_expression::*;
-pub use text_predicate::*;
\ No newline at end of file
+pub use regex::*;
+pub use replacer::Replacer;
+pub use rule::*;
+pub use source_index::*;
+pub use split::*;
+pub use text_predicate::*;
+pub use typed_expr::*;
\ No newline at end of file
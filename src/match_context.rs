@@ -0,0 +1,257 @@
+use std::{ cell::{ Cell, RefCell }, collections::HashMap };
+use crate::{ CaseMode, Expectation, MatchFailure, MatchHit, MatcherRegistry, TextPredicate };
+
+
+
+type FailureState = (usize, Vec<Expectation>);
+type MemoTable = HashMap<(usize, usize), (Option<MatchHit>, FailureState)>;
+
+/// The state of a rule that is being matched.
+struct RuleFrame {
+	seed:Option<MatchHit>,
+	left_recursive:bool,
+	depends_on_seed:bool
+}
+
+/// The full text a match is performed on. Every predicate taking part in a match receives the same context, so results can be positioned absolutely in the original text.
+pub struct MatchContext<'a> {
+	text:&'a str,
+	registry:Option<&'a MatcherRegistry>,
+	memo:Option<RefCell<MemoTable>>,
+	rule_stack:RefCell<Vec<RuleFrame>>,
+	active_rules:RefCell<HashMap<(usize, usize), usize>>,
+	furthest_failure:RefCell<FailureState>,
+	suppressed_depth:Cell<usize>,
+	case_mode:CaseMode,
+	span_only:bool
+}
+impl<'a> MatchContext<'a> {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new context for the given text.
+	pub fn new(text:&'a str) -> MatchContext<'a> {
+		MatchContext {
+			text,
+			registry: None,
+			memo: None,
+			rule_stack: RefCell::new(Vec::new()),
+			active_rules: RefCell::new(HashMap::new()),
+			furthest_failure: RefCell::new((0, Vec::new())),
+			suppressed_depth: Cell::new(0),
+			case_mode: CaseMode::Sensitive,
+			span_only: false
+		}
+	}
+
+
+
+	/// Return self with a registry in which rules referenced by name are looked up.
+	pub fn with_registry(mut self, registry:&'a MatcherRegistry) -> Self {
+		self.registry = Some(registry);
+		self
+	}
+
+	/// Return self with memoization enabled. The result of every named rule is stored per position, so each rule is matched at most once at each position of the text. This makes matching grammars with shared prefixes linear in time, at the cost of memory.
+	pub fn with_memoization(mut self) -> Self {
+		self.memo = Some(RefCell::new(HashMap::new()));
+		self
+	}
+
+
+
+	/// Return self with the given case mode, in which all plain string and character literals are matched.
+	pub fn with_case_mode(mut self, case_mode:CaseMode) -> Self {
+		self.case_mode = case_mode;
+		self
+	}
+
+
+
+	/// Return self creating results that only record their spans, leaving their contents empty. This avoids copying the text into every result of a large parse tree. See `MatchSpan` for reading such results.
+	pub fn with_span_only(mut self) -> Self {
+		self.span_only = true;
+		self
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Get the full text being matched.
+	pub fn text(&self) -> &'a str {
+		self.text
+	}
+
+	/// Get the registry in which rules referenced by name are looked up.
+	pub fn registry(&self) -> Option<&'a MatcherRegistry> {
+		self.registry
+	}
+
+	/// Get the text from the given cursor onwards. Returns an empty string when the cursor is at or past the end of the text.
+	pub fn remaining(&self, cursor:usize) -> &'a str {
+		if self.text.len() > cursor { &self.text[cursor..] } else { "" }
+	}
+
+	/// Get the character starting at the given cursor. Returns None at or past the end of the text.
+	pub fn char_at(&self, cursor:usize) -> Option<char> {
+		self.remaining(cursor).chars().next()
+	}
+
+	/// Get the case mode plain string and character literals are matched in.
+	pub fn case_mode(&self) -> CaseMode {
+		self.case_mode
+	}
+
+	/// Get the text the contents of results are taken from. Empty when only spans are recorded.
+	pub(crate) fn contents_source(&self) -> &'a str {
+		if self.span_only { "" } else { self.text }
+	}
+
+	/// Check if memoization is enabled.
+	pub fn is_memoized(&self) -> bool {
+		self.memo.is_some()
+	}
+
+
+
+	/* RULE METHODS */
+
+	/// Match the given matcher as the rule with the given identity and name. The result is named after the rule unless the rule is hidden, a failure is reported as the rule and results are memoized when memoization is enabled.
+	/// A rule that is matched again at the same cursor while it is still being matched is left-recursive. Such a rule first matches without the recursion as a seed, after which the seed is grown by matching again until the result no longer gets longer.
+	pub(crate) fn match_rule<T:TextPredicate + ?Sized>(&self, rule_id:usize, name:&str, hidden:bool, matcher:&T, cursor:usize) -> Option<MatchHit> {
+		let failure_checkpoint:(usize, usize) = self.failure_checkpoint();
+		let result:Option<MatchHit> = self.match_rule_growing(rule_id, name, hidden, matcher, cursor);
+		if result.is_none() {
+			self.fail_as_rule(failure_checkpoint, cursor, name);
+		}
+		result
+	}
+
+	/// Match the given rule, handling memoization and left-recursion.
+	fn match_rule_growing<T:TextPredicate + ?Sized>(&self, rule_id:usize, name:&str, hidden:bool, matcher:&T, cursor:usize) -> Option<MatchHit> {
+		let key:(usize, usize) = (rule_id, cursor);
+
+		// When the rule is already being matched at this cursor, it is left-recursive. Return its current seed and mark all rules in between as depending on it.
+		if let Some(frame_index) = self.active_rules.borrow().get(&key) {
+			let mut rule_stack = self.rule_stack.borrow_mut();
+			rule_stack[*frame_index].left_recursive = true;
+			for frame in &mut rule_stack[*frame_index + 1..] {
+				frame.depends_on_seed = true;
+			}
+			return rule_stack[*frame_index].seed.clone();
+		}
+
+		// Reuse a memoized result, replaying the expectations its matching registered.
+		let memoized:Option<(Option<MatchHit>, FailureState)> = self.memo.as_ref().and_then(|memo| memo.borrow().get(&key).cloned());
+		if let Some((result, rule_failure)) = memoized {
+			self.merge_failure(&rule_failure);
+			return result;
+		}
+
+		// Register the expectations of this rule separately, so they can be memoized with the result.
+		let outer_failure:Option<FailureState> = self.memo.as_ref().map(|_| self.furthest_failure.replace((0, Vec::new())));
+
+		// Match the rule, growing the seed while the rule is left-recursive.
+		let match_once = || matcher.match_text_at(self, cursor).map(|mut result| {
+			if !hidden {
+				result.type_name = name.to_string();
+			}
+			result
+		});
+		let frame_index:usize = self.rule_stack.borrow().len();
+		self.rule_stack.borrow_mut().push(RuleFrame { seed: None, left_recursive: false, depends_on_seed: false });
+		self.active_rules.borrow_mut().insert(key, frame_index);
+		let mut result:Option<MatchHit> = match_once();
+		if self.rule_stack.borrow()[frame_index].left_recursive {
+			while let Some(seed) = result {
+				self.rule_stack.borrow_mut()[frame_index].seed = Some(seed.clone());
+				match match_once() {
+					Some(grown) if grown.end() > seed.end() => result = Some(grown),
+					_ => {
+						result = Some(seed);
+						break;
+					}
+				}
+			}
+		}
+		self.active_rules.borrow_mut().remove(&key);
+		let frame:RuleFrame = self.rule_stack.borrow_mut().pop().unwrap();
+
+		// Results that depend on the seed of a rule that is still growing may not be reused, nor may results matched while expectations were suppressed, as their expectations are missing.
+		if let Some(memo) = &self.memo && let Some(outer_failure) = outer_failure {
+			let rule_failure:FailureState = self.furthest_failure.replace(outer_failure);
+			self.merge_failure(&rule_failure);
+			if !frame.depends_on_seed && self.suppressed_depth.get() == 0 {
+				memo.borrow_mut().insert(key, (result.clone(), rule_failure));
+			}
+		}
+		result
+	}
+
+
+
+	/* FAILURE METHODS */
+
+	/// Register that the given expectation was not met at the given cursor. Only the expectations at the furthest cursor are kept.
+	pub fn expect(&self, cursor:usize, expectation:Expectation) {
+		if self.suppressed_depth.get() > 0 {
+			return;
+		}
+		let mut furthest_failure = self.furthest_failure.borrow_mut();
+		if cursor > furthest_failure.0 || furthest_failure.1.is_empty() {
+			*furthest_failure = (cursor, vec![expectation]);
+		} else if cursor == furthest_failure.0 && !furthest_failure.1.contains(&expectation) {
+			furthest_failure.1.push(expectation);
+		}
+	}
+
+	/// Get the failure describing the furthest position any predicate failed at.
+	pub fn failure(&self) -> MatchFailure {
+		let furthest_failure = self.furthest_failure.borrow();
+		MatchFailure {
+			position: furthest_failure.0,
+			expected: furthest_failure.1.clone(),
+			found: self.remaining(furthest_failure.0).chars().next()
+		}
+	}
+
+	/// Run the given action without registering any failed expectations, for example for negated predicates.
+	pub(crate) fn without_expectations<T, U:FnOnce() -> T>(&self, action:U) -> T {
+		self.suppressed_depth.set(self.suppressed_depth.get() + 1);
+		let result:T = action();
+		self.suppressed_depth.set(self.suppressed_depth.get() - 1);
+		result
+	}
+
+	/// Register all expectations of a failure state, as if they were registered directly.
+	fn merge_failure(&self, failure:&FailureState) {
+		for expectation in &failure.1 {
+			self.expect(failure.0, expectation.clone());
+		}
+	}
+
+	/// Get a checkpoint of the currently registered failure, to be used with `fail_as_rule`.
+	pub(crate) fn failure_checkpoint(&self) -> (usize, usize) {
+		let furthest_failure = self.furthest_failure.borrow();
+		(furthest_failure.0, furthest_failure.1.len())
+	}
+
+	/// Register that the rule with the given name failed at the given cursor. If no predicate within the rule got further than the cursor, the expectations registered since the checkpoint are replaced by the rule itself.
+	pub(crate) fn fail_as_rule(&self, checkpoint:(usize, usize), cursor:usize, name:&str) {
+		if self.suppressed_depth.get() > 0 {
+			return;
+		}
+		{
+			let mut furthest_failure = self.furthest_failure.borrow_mut();
+			if furthest_failure.0 > cursor {
+				return;
+			}
+			if furthest_failure.0 == cursor {
+				let kept_expectations:usize = if checkpoint.0 == cursor { checkpoint.1 } else { 0 };
+				furthest_failure.1.truncate(kept_expectations);
+			}
+		}
+		self.expect(cursor, Expectation::Rule(name.to_string()));
+	}
+}
\ No newline at end of file
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+	use std::sync::{ Arc, atomic::{ AtomicUsize, Ordering } };
+	use crate::{ Expectation, MatchContext, MatchExpr, MatchFailure, MatchHit, MatcherRegistry, TextPredicate };
+
+
+
+	#[test]
+	fn test_context_text() {
+		assert_eq!(MatchContext::new("xaba").text(), "xaba");
+		assert_eq!(MatchContext::new("").text(), "");
+	}
+
+	#[test]
+	fn test_context_remaining() {
+		let context:MatchContext = MatchContext::new("xaba");
+		assert_eq!(context.remaining(0), "xaba");
+		assert_eq!(context.remaining(2), "ba");
+		assert_eq!(context.remaining(4), "");
+		assert_eq!(context.remaining(10), "");
+	}
+
+	#[test]
+	fn test_context_expect() {
+		let context:MatchContext = MatchContext::new("xaba");
+		context.expect(1, Expectation::Literal("a".to_string()));
+		context.expect(1, Expectation::Literal("a".to_string()));
+		context.expect(0, Expectation::Literal("x".to_string()));
+		assert_eq!(context.failure(), MatchFailure { position: 1, expected: vec![Expectation::Literal("a".to_string())], found: Some('a') });
+
+		context.expect(2, Expectation::Literal("c".to_string()));
+		context.expect(2, Expectation::CharClass("digit".to_string()));
+		assert_eq!(context.failure(), MatchFailure { position: 2, expected: vec![Expectation::Literal("c".to_string()), Expectation::CharClass("digit".to_string())], found: Some('b') });
+	}
+
+
+
+	/* MEMOIZATION TESTS */
+
+	#[test]
+	fn test_context_memoization() {
+		let evaluations:Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+		let evaluation_counter:Arc<AtomicUsize> = evaluations.clone();
+		let term:MatchExpr = MatchExpr::named("term", move |text:&str| {
+			evaluation_counter.fetch_add(1, Ordering::Relaxed);
+			MatchExpr::unsigned_integer().match_text(text)
+		});
+		let expression:MatchExpr = (term.clone() + "+" + term.clone()) | (term.clone() + "-" + term.clone()) | term.clone();
+
+		let result:MatchHit = expression.match_text_at(&MatchContext::new("12-3"), 0).unwrap();
+		assert_eq!(result.length, 4);
+		assert_eq!(evaluations.load(Ordering::Relaxed), 3);
+
+		evaluations.store(0, Ordering::Relaxed);
+		let context:MatchContext = MatchContext::new("12-3").with_memoization();
+		assert!(context.is_memoized());
+		assert_eq!(expression.match_text_at(&context, 0), Some(result));
+		assert_eq!(evaluations.load(Ordering::Relaxed), 2); // Once at each position.
+	}
+
+	#[test]
+	fn test_context_memoization_failures() {
+		let evaluations:Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+		let evaluation_counter:Arc<AtomicUsize> = evaluations.clone();
+		let keyword:MatchExpr = MatchExpr::named("keyword", move |text:&str| {
+			evaluation_counter.fetch_add(1, Ordering::Relaxed);
+			"if".match_text(text)
+		});
+		let expression:MatchExpr = (keyword.clone() + "x") | (keyword.clone() + "y") | "else";
+
+		let context:MatchContext = MatchContext::new("then").with_memoization();
+		assert_eq!(expression.match_text_at(&context, 0), None);
+		assert_eq!(evaluations.load(Ordering::Relaxed), 1);
+		assert_eq!(context.failure().expected, vec![Expectation::Rule("keyword".to_string()), Expectation::Literal("else".to_string())]);
+	}
+
+	#[test]
+	fn test_registry_memoization() {
+		let evaluations:Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+		let evaluation_counter:Arc<AtomicUsize> = evaluations.clone();
+		let set:MatcherRegistry = MatcherRegistry::new()
+			.with_matchers(vec![
+				("call", MatchExpr::reference("name") + "()"),
+				("index", MatchExpr::reference("name") + "[]"),
+				("word", MatchExpr::reference("name"))
+			])
+			.with_rule("name", move |text:&str| {
+				evaluation_counter.fetch_add(1, Ordering::Relaxed);
+				MatchExpr::word().match_text(text)
+			});
+
+		assert_eq!(set.match_text("abc").unwrap().type_name, "word");
+		assert_eq!(evaluations.load(Ordering::Relaxed), 3);
+
+		evaluations.store(0, Ordering::Relaxed);
+		let set:MatcherRegistry = set.with_memoization();
+		assert_eq!(set.match_text("abc").unwrap().type_name, "word");
+		assert_eq!(evaluations.load(Ordering::Relaxed), 1);
+	}
+
+	#[test]
+	fn test_memoization_keeps_failures() {
+		let matchers = vec![
+			("negation", MatchExpr::not_followed_by(MatchExpr::reference("number") + "!") + MatchExpr::reference("number") + "x"),
+			("sum", MatchExpr::reference("number") + "+" + MatchExpr::reference("number"))
+		];
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(matchers.clone()).with_rule("number", MatchExpr::unsigned_integer());
+		let memoized_set:MatcherRegistry = MatcherRegistry::new().with_matchers(matchers).with_rule("number", MatchExpr::unsigned_integer()).with_memoization();
+		// The number rule is first matched while expectations are suppressed, then reused at the same position.
+		for text in ["12z", "12+", "12+a", "x"] {
+			assert_eq!(memoized_set.try_match_text(text), set.try_match_text(text));
+		}
+		assert_eq!(memoized_set.try_match_text("12z").unwrap_err().position, 2);
+	}
+}
\ No newline at end of file
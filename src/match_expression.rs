@@ -1,58 +1,98 @@
-use std::{ rc::Rc, ops::{ Add, BitAnd, BitOr, Mul, Not } };
-use crate::{ MatchHit, TextPredicate };
+use std::{ sync::Arc, ops::{ Add, BitAnd, BitOr, Bound, Mul, Not, Range, RangeBounds, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive } };
+use crate::{ replacer::replace_matches, unicode_tables, CaseMode, CharSet, Expectation, GeneralCategory, MatchContext, MatchHit, Regex, Replacer, Split, TextPredicate, TypedExpr };
 
 
 
 const LINE_BREAK_CHARS:&[char] = &['\n', '\r'];
 
+/// The most characters before the cursor look-behind predicates try as start, which keeps them from rescanning the whole text at every position.
+const MAX_LOOKBEHIND:usize = 256;
+
 
 
 #[derive(Clone)]
-pub struct MatchExpr(Rc<dyn TextPredicate>);
+pub struct MatchExpr(Arc<dyn TextPredicate>);
 impl MatchExpr {
 
 	/// Create a new match-expression from a source.
 	pub fn new<T:TextPredicate + 'static>(source:T) -> MatchExpr {
-		MatchExpr(Rc::new(source))
+		MatchExpr(Arc::new(source))
+	}
+
+	/// Create a new match-expression from a function that receives the full context and the byte cursor to match at.
+	pub fn positional<T:Fn(&MatchContext, usize) -> Option<MatchHit> + Send + Sync + 'static>(match_function:T) -> MatchExpr {
+		MatchExpr::new(PositionalPredicate(match_function))
+	}
+
+
+
+	/// Get a number uniquely identifying the predicate of this match-expression while it exists.
+	pub(crate) fn id(&self) -> usize {
+		Arc::as_ptr(&self.0).cast::<()>().addr()
 	}
 
 
 
 	/* NAME MODIFICATION MATCHER METHODS */
 
-	/// Wrap any result of the sub-matcher in the given name.
+	/// Wrap any result of the sub-matcher in the given name, also when the name starts with an underscore. Only grammar rules starting with an underscore are hidden, see `MatcherRegistry::from_grammar`.
 	pub fn named<T:TextPredicate + 'static>(name:&str, sub_matcher:T) -> MatchExpr {
 		let name:String = name.to_string();
-		MatchExpr::new(move |text:&str| {
-			match sub_matcher.match_text(text) {
-				Some(mut result) => {
-					result.type_name = name.to_string();
-					Some(result)
-				},
-				None => None
+		let sub_matcher:Arc<T> = Arc::new(sub_matcher);
+		let rule_id:usize = Arc::as_ptr(&sub_matcher).addr();
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			context.match_rule(rule_id, &name, false, sub_matcher.as_ref(), cursor)
+		})
+	}
+
+
+
+	/// Match the rule with the given name from the registry the match was started from. This allows registry rules to reference each other, and themselves, by name.
+	/// When matched outside of a registry, or when the registry has no rule with the given name, the reference does not match and the rule is reported as expectation.
+	pub fn reference(name:&str) -> MatchExpr {
+		let name:String = name.to_string();
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			match context.registry().and_then(|registry| registry.matcher_by_name(&name).map(|(_, matcher)| (registry, matcher))) {
+				Some((registry, matcher)) => context.match_rule(matcher.id(), &name, registry.is_hidden_rule(&name), matcher, cursor),
+				None => {
+					context.expect(cursor, Expectation::Rule(name.clone()));
+					None
+				}
 			}
 		})
 	}
 
 
 
+	/* TYPED MATCHER METHODS */
+
+	/// Create a typed match-expression that converts every result of the sub-matcher to a value.
+	pub fn map<T:'static, U:TextPredicate + 'static, V:Fn(&MatchHit) -> T + Send + Sync + 'static>(sub_matcher:U, action:V) -> TypedExpr<T> {
+		TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			sub_matcher.match_text_at(context, cursor).map(|result| {
+				let value:T = action(&result);
+				(result, value)
+			})
+		})
+	}
+
+
+
 	/* REPETITION MATCHER METHODS */
 
 	/// Repeat the given match-expression as many times as possible. Will return None when not matched once.
 	pub fn repeat_max<T:TextPredicate + 'static>(sub_matcher:T) -> MatchExpr {
-		MatchExpr::new(move |text:&str| {
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
 			let mut matched_any:bool = false;
-			let mut cursor:usize = 0;
+			let mut end:usize = cursor;
 			let mut sub_matches:Vec<MatchHit> = Vec::new();
-			let mut remaining_text:&str = text;
-			while let Some(match_result) = sub_matcher.match_text(remaining_text) {
+			while let Some(match_result) = sub_matcher.match_text_at(context, end) {
 				matched_any = true;
-				cursor += match_result.length;
+				end += match_result.length;
 				sub_matches.push(match_result);
-				remaining_text = if text.len() > cursor { &text[cursor..] } else { "" };
 			}
 			if matched_any {
-				Some(MatchHit::new_with_sub_matches(cursor, text, sub_matches))
+				Some(MatchHit::new_at_with_sub_matches(cursor, end - cursor, context.contents_source(), sub_matches))
 			} else {
 				None
 			}
@@ -61,23 +101,252 @@ impl MatchExpr {
 
 	/// Repeat the given match-expression as many times as possible. Will return Some(0) when not matched once.
 	pub fn optional_repeat_max<T:TextPredicate + 'static>(sub_matcher:T) -> MatchExpr {
-		MatchExpr::new(move |text:&str| {
-			let mut cursor:usize = 0;
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let mut end:usize = cursor;
 			let mut sub_matches:Vec<MatchHit> = Vec::new();
-			let mut remaining_text:&str = text;
-			while let Some(match_result) = sub_matcher.match_text(remaining_text) {
-				cursor += match_result.length;
+			while let Some(match_result) = sub_matcher.match_text_at(context, end) {
+				end += match_result.length;
 				sub_matches.push(match_result);
-				remaining_text = if text.len() > cursor { &text[cursor..] } else { "" };
 			}
-			Some(MatchHit::new_with_sub_matches(cursor, text, sub_matches))
+			Some(MatchHit::new_at_with_sub_matches(cursor, end - cursor, context.contents_source(), sub_matches))
+		})
+	}
+
+	/// Repeat the given match-expression as many times as possible within the given range of counts, like `2..=5` or `1..`. Will return None when matched fewer times than the range allows.
+	pub fn repeat<T:TextPredicate + 'static, U:RangeBounds<usize>>(sub_matcher:T, count_range:U) -> MatchExpr {
+		let (min_count, max_count) = MatchExpr::count_bounds(&count_range);
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let mut end:usize = cursor;
+			let mut sub_matches:Vec<MatchHit> = Vec::new();
+			while sub_matches.len() < max_count {
+				match sub_matcher.match_text_at(context, end) {
+					Some(match_result) => {
+						let length:usize = match_result.length;
+						end += length;
+						sub_matches.push(match_result);
+						if length == 0 {
+							break;
+						}
+					},
+					None => break
+				}
+			}
+			// A repetition of length 0 would repeat identically, so it satisfies any minimum count.
+			if sub_matches.len() >= min_count || sub_matches.last().is_some_and(|last| last.length == 0) {
+				Some(MatchHit::new_at_with_sub_matches(cursor, end - cursor, context.contents_source(), sub_matches))
+			} else {
+				None
+			}
+		})
+	}
+
+	/// Repeat the given match-expression as few times as possible within the given range of counts, stopping at the first count after which the following match-expression matches. The result contains the repetitions followed by the match of the following match-expression.
+	pub fn repeat_lazy<T:TextPredicate + 'static, U:RangeBounds<usize>, V:TextPredicate + 'static>(sub_matcher:T, count_range:U, following:V) -> MatchExpr {
+		let (min_count, max_count) = MatchExpr::count_bounds(&count_range);
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let mut end:usize = cursor;
+			let mut sub_matches:Vec<MatchHit> = Vec::new();
+			loop {
+				if sub_matches.len() >= min_count && let Some(following_match) = following.match_text_at(context, end) {
+					end += following_match.length;
+					sub_matches.push(following_match);
+					return Some(MatchHit::new_at_with_sub_matches(cursor, end - cursor, context.contents_source(), sub_matches));
+				}
+				if sub_matches.len() >= max_count {
+					return None;
+				}
+				match sub_matcher.match_text_at(context, end) {
+					Some(match_result) if match_result.length > 0 || sub_matches.len() < min_count => {
+						end += match_result.length;
+						sub_matches.push(match_result);
+					},
+					_ => return None
+				}
+			}
 		})
 	}
 
 	/// Create a match-expression that tries to match the given sub-matcher, but still returns Some(0) on mismatch.
 	pub fn optional<T:TextPredicate + 'static>(sub_matcher:T) -> MatchExpr {
-		MatchExpr::new(move |text:&str| {
-			Some(sub_matcher.match_text(text).unwrap_or(MatchHit::new(0, text)))
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			Some(sub_matcher.match_text_at(context, cursor).unwrap_or_else(|| MatchHit::new_at(cursor, 0, context.contents_source())))
+		})
+	}
+
+
+
+	/* LITERAL MATCHER METHODS */
+
+	/// Create a match-expression that matches the literal in the given case mode, regardless of the case mode of the match.
+	pub fn literal(literal:&str, case_mode:CaseMode) -> MatchExpr {
+		let literal:String = literal.to_string();
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			if let Some(length) = case_mode.match_prefix(context.remaining(cursor), &literal) {
+				return Some(MatchHit::new_at(cursor, length, context.contents_source()));
+			}
+			context.expect(cursor, Expectation::Literal(literal.clone()));
+			None
+		})
+	}
+
+	/// Create a match-expression that matches the literal regardless of case, comparing lowercase forms as described for `CaseMode::IgnoreCase`.
+	pub fn ignore_case(literal:&str) -> MatchExpr {
+		MatchExpr::literal(literal, CaseMode::IgnoreCase)
+	}
+
+	/// Create a match-expression that matches the literal regardless of the case of ASCII letters.
+	pub fn ignore_ascii_case(literal:&str) -> MatchExpr {
+		MatchExpr::literal(literal, CaseMode::IgnoreAsciiCase)
+	}
+
+	/// Create a match-expression that matches the character regardless of case, comparing lowercase forms as described for `CaseMode::IgnoreCase`.
+	pub fn char_ignore_case(char:char) -> MatchExpr {
+		MatchExpr::literal(&char.to_string(), CaseMode::IgnoreCase)
+	}
+
+	/// Create a match-expression that matches the character regardless of case when it is an ASCII letter.
+	pub fn char_ignore_ascii_case(char:char) -> MatchExpr {
+		MatchExpr::literal(&char.to_string(), CaseMode::IgnoreAsciiCase)
+	}
+
+
+
+	/// Create a match-expression from a regex pattern, matched anchored at the cursor. Named capture groups become named sub-matches. See `Regex`.
+	/// Panics when the pattern is invalid. Use `Regex::new` to handle invalid patterns.
+	pub fn regex(pattern:&str) -> MatchExpr {
+		MatchExpr::new(Regex::new(pattern).unwrap_or_else(|error| panic!("Invalid regex pattern \"{pattern}\": {error}.")))
+	}
+
+
+
+	/* CHOICE MATCHER METHODS */
+
+	/// Try the alternatives in order and return the match of the first one that matches.
+	pub fn choice<T:TextPredicate + 'static, U:IntoIterator<Item = T>>(alternatives:U) -> MatchExpr {
+		let alternatives:Vec<T> = alternatives.into_iter().collect();
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			alternatives.iter().find_map(|alternative| alternative.match_text_at(context, cursor))
+		})
+	}
+
+	/// Try all alternatives and return the longest match. When multiple alternatives match the same length, the first of them wins.
+	pub fn longest_of<T:TextPredicate + 'static, U:IntoIterator<Item = T>>(alternatives:U) -> MatchExpr {
+		let alternatives:Vec<T> = alternatives.into_iter().collect();
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			MatchExpr::longest(alternatives.iter().filter_map(|alternative| alternative.match_text_at(context, cursor)))
+		})
+	}
+
+
+
+	/* LIST MATCHER METHODS */
+
+	/// Match one or more items separated by the separator. The children of the result are the items, separators are only kept when they are named.
+	pub fn separated_by<T:TextPredicate + 'static, U:TextPredicate + 'static>(item:T, separator:U) -> MatchExpr {
+		MatchExpr::separated_list(item, separator, false)
+	}
+
+	/// Match one or more items separated by the separator, optionally followed by one trailing separator. The children of the result are the items, separators are only kept when they are named.
+	pub fn separated_by_trailing<T:TextPredicate + 'static, U:TextPredicate + 'static>(item:T, separator:U) -> MatchExpr {
+		MatchExpr::separated_list(item, separator, true)
+	}
+
+	/// Match the inner match-expression between the open and close match-expressions. The only child of the result is the inner match, the open and close matches are only kept when they are named.
+	pub fn delimited<T:TextPredicate + 'static, U:TextPredicate + 'static, V:TextPredicate + 'static>(open:T, inner:U, close:V) -> MatchExpr {
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let open_match:MatchHit = open.match_text_at(context, cursor)?;
+			let inner_match:MatchHit = inner.match_text_at(context, open_match.end())?;
+			let close_match:MatchHit = close.match_text_at(context, inner_match.end())?;
+			let end:usize = close_match.end();
+			let sub_matches:Vec<MatchHit> = [open_match, inner_match, close_match].into_iter().enumerate().filter(|(index, sub_match)| *index == 1 || !sub_match.type_name.is_empty()).map(|(_, sub_match)| sub_match).collect();
+			Some(MatchHit::new_at_with_list(cursor, end - cursor, context.contents_source(), sub_matches))
+		})
+	}
+
+	/// Match the inner match-expression with the surrounding match-expression on both sides, like quotes around a string. See `delimited`.
+	pub fn surrounded_by<T:TextPredicate + 'static, U:TextPredicate + 'static>(inner:T, surrounding:U) -> MatchExpr {
+		let surrounding:MatchExpr = MatchExpr::new(surrounding);
+		MatchExpr::delimited(surrounding.clone(), inner, surrounding)
+	}
+
+
+
+	/* ASSERTION MATCHER METHODS */
+
+	/// Create a match-expression that matches with length 0 when the given sub-matcher matches at the cursor. Never consumes any text.
+	pub fn followed_by<T:TextPredicate + 'static>(sub_matcher:T) -> MatchExpr {
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			sub_matcher.match_text_at(context, cursor).map(|_| MatchHit::new_at(cursor, 0, context.contents_source()))
+		})
+	}
+
+	/// Create a match-expression that matches with length 0 when the given sub-matcher does not match at the cursor. Never consumes any text.
+	pub fn not_followed_by<T:TextPredicate + 'static>(sub_matcher:T) -> MatchExpr {
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			match context.without_expectations(|| sub_matcher.match_text_at(context, cursor)) {
+				Some(_) => None,
+				None => Some(MatchHit::new_at(cursor, 0, context.contents_source()))
+			}
+		})
+	}
+
+	/// Create a match-expression that matches with length 0 when the given sub-matcher matches a piece of text ending exactly at the cursor. Never consumes any text.
+	/// Only starts up to 256 characters before the cursor are tried, so longer pieces of text before the cursor are never found.
+	pub fn preceded_by<T:TextPredicate + 'static>(sub_matcher:T) -> MatchExpr {
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			if MatchExpr::matches_behind(&sub_matcher, context, cursor) { Some(MatchHit::new_at(cursor, 0, context.contents_source())) } else { None }
+		})
+	}
+
+	/// Create a match-expression that matches with length 0 when the given sub-matcher does not match any piece of text ending exactly at the cursor. Never consumes any text.
+	/// Like `preceded_by`, only pieces of text of up to 256 characters are tried.
+	pub fn not_preceded_by<T:TextPredicate + 'static>(sub_matcher:T) -> MatchExpr {
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			if MatchExpr::matches_behind(&sub_matcher, context, cursor) { None } else { Some(MatchHit::new_at(cursor, 0, context.contents_source())) }
+		})
+	}
+
+
+
+	/* ANCHOR MATCHER METHODS */
+
+	/// Create a match-expression that matches with length 0 at the start of the text.
+	pub fn bof() -> MatchExpr {
+		MatchExpr::at_position("start of input", |_, cursor| cursor == 0)
+	}
+
+	/// Create a match-expression that matches with length 0 at the end of the text.
+	pub fn eof() -> MatchExpr {
+		MatchExpr::positional(|context:&MatchContext, cursor:usize| {
+			if cursor >= context.text().len() {
+				return Some(MatchHit::new_at(context.text().len(), 0, context.contents_source()));
+			}
+			context.expect(cursor, Expectation::EndOfInput);
+			None
+		})
+	}
+
+	/// Create a match-expression that matches with length 0 at the start of a line.
+	pub fn bol() -> MatchExpr {
+		MatchExpr::at_position("start of line", |text, cursor| {
+			let previous_char:Option<char> = text[..cursor].chars().next_back();
+			previous_char.is_none() || previous_char == Some('\n') || (previous_char == Some('\r') && !text[cursor..].starts_with('\n'))
+		})
+	}
+
+	/// Create a match-expression that matches with length 0 at the end of a line, right before the linebreak or the end of the text.
+	pub fn eol() -> MatchExpr {
+		MatchExpr::at_position("end of line", |text, cursor| {
+			let next_char:Option<char> = text[cursor..].chars().next();
+			next_char.is_none() || next_char == Some('\r') || (next_char == Some('\n') && !text[..cursor].ends_with('\r'))
+		})
+	}
+
+	/// Create a match-expression that matches with length 0 between a word character and a non-word character, or a word character and the start or end of the text. Word characters are XID_Continue characters.
+	pub fn word_boundary() -> MatchExpr {
+		MatchExpr::at_position("word boundary", |text, cursor| {
+			let is_word_char = |char:Option<char>| char.is_some_and(|char| unicode_tables::in_ranges(char, unicode_tables::XID_CONTINUE));
+			is_word_char(text[..cursor].chars().next_back()) != is_word_char(text[cursor..].chars().next())
 		})
 	}
 
@@ -87,17 +356,17 @@ impl MatchExpr {
 
 	/// Create a match-expression that matches only white-space. Matches maximum one character.
 	pub fn whitespace() -> MatchExpr {
-		MatchExpr::on_first_char(|char| char.is_whitespace())
+		MatchExpr::on_first_char("whitespace", |char| char.is_whitespace())
 	}
 
 	/// Create a match-expression that matches only linebreaks. Matches maximum one character.
 	pub fn linebreak() ->  MatchExpr {
-		MatchExpr::on_first_char(|char| LINE_BREAK_CHARS.contains(&char))
+		MatchExpr::on_first_char("linebreak", |char| LINE_BREAK_CHARS.contains(&char))
 	}
 
 	/// Create a match-expression that matches only non-linebreak whitespace. Matches maximum one character.
 	pub fn inline_whitespace() ->  MatchExpr {
-		MatchExpr::on_first_char(|char| char.is_whitespace() && !LINE_BREAK_CHARS.contains(&char))
+		MatchExpr::on_first_char("inline whitespace", |char| char.is_whitespace() && !LINE_BREAK_CHARS.contains(&char))
 	}
 
 
@@ -106,7 +375,7 @@ impl MatchExpr {
 
 	/// Create a match-expression that matches only digits. Matches maximum one character.
 	pub fn digit() -> MatchExpr {
-		MatchExpr::on_first_char(|char| char >= '0' && char <= '9')
+		MatchExpr::on_first_char("digit", |char| char.is_ascii_digit())
 	}
 
 	/// Create a match-expression that matches unsigned integers. Matches as long as possible.
@@ -128,61 +397,286 @@ impl MatchExpr {
 
 	/* WORD-LIKE MATCH-EXPRESSION METHODS */
 
-	/// Create a match expression that only matches A-z. Matches maximum one character.
+	/// Create a match expression that only matches A-Z and a-z. Matches maximum one character.
 	pub fn alphabetic() -> MatchExpr {
-		MatchExpr::on_first_char(|char| (char >= 'A' && char <= 'z'))
+		MatchExpr::on_first_char("alphabetic character", |char| char.is_ascii_alphabetic())
 	}
 
 	/// Create a match expression that only matches a-z. Matches maximum one character.
 	pub fn lowercase_alphabetic() -> MatchExpr {
-		MatchExpr::on_first_char(|char| (char >= 'a' && char <= 'z'))
+		MatchExpr::on_first_char("lowercase character", |char| char.is_ascii_lowercase())
 	}
 
 	/// Create a match expression that only matches A-Z. Matches maximum one character.
 	pub fn uppercase_alphabetic() -> MatchExpr {
-		MatchExpr::on_first_char(|char| (char >= 'A' && char <= 'Z'))
+		MatchExpr::on_first_char("uppercase character", |char| char.is_ascii_uppercase())
 	}
 
-	/// Create a match expression that matches one word that exists of only A-z. Matches as much as possible.
+	/// Create a match expression that matches one word that exists of only A-Z and a-z. Matches as much as possible.
 	pub fn word() -> MatchExpr {
 		MatchExpr::repeat_max(MatchExpr::alphabetic())
 	}
 
-	
+	/// Create a match expression that matches one identifier: an XID_Start character or underscore, followed by any XID_Continue characters. Matches as much as possible.
+	pub fn identifier() -> MatchExpr {
+		(MatchExpr::xid_start() | '_') + MatchExpr::optional_repeat_max(MatchExpr::xid_continue())
+	}
+
+
+
+	/* ASCII CHARACTER CLASS MATCH-EXPRESSION METHODS */
+
+	/// Create a match expression that only matches A-Z, a-z and 0-9. Matches maximum one character.
+	pub fn ascii_alphanumeric() -> MatchExpr {
+		MatchExpr::on_first_char("alphanumeric character", |char| char.is_ascii_alphanumeric())
+	}
+
+	/// Create a match expression that only matches 0-9, A-F and a-f. Matches maximum one character.
+	pub fn ascii_hexdigit() -> MatchExpr {
+		MatchExpr::on_first_char("hexadecimal digit", |char| char.is_ascii_hexdigit())
+	}
+
+	/// Create a match expression that only matches ASCII punctuation and symbols. Matches maximum one character.
+	pub fn ascii_punctuation() -> MatchExpr {
+		MatchExpr::on_first_char("punctuation", |char| char.is_ascii_punctuation())
+	}
+
+
+
+	/* UNICODE CHARACTER CLASS MATCH-EXPRESSION METHODS */
+
+	/// Create a match expression that matches any character with the Unicode Alphabetic property. Matches maximum one character.
+	pub fn unicode_alphabetic() -> MatchExpr {
+		MatchExpr::on_first_char("alphabetic character", char::is_alphabetic)
+	}
+
+	/// Create a match expression that matches any character with the Unicode Lowercase property. Matches maximum one character.
+	pub fn unicode_lowercase() -> MatchExpr {
+		MatchExpr::on_first_char("lowercase character", char::is_lowercase)
+	}
+
+	/// Create a match expression that matches any character with the Unicode Uppercase property. Matches maximum one character.
+	pub fn unicode_uppercase() -> MatchExpr {
+		MatchExpr::on_first_char("uppercase character", char::is_uppercase)
+	}
+
+	/// Create a match expression that matches any character in a Unicode number category. Matches maximum one character.
+	pub fn unicode_numeric() -> MatchExpr {
+		MatchExpr::on_first_char("numeric character", char::is_numeric)
+	}
+
+	/// Create a match expression that matches any Unicode alphabetic or numeric character. Matches maximum one character.
+	pub fn unicode_alphanumeric() -> MatchExpr {
+		MatchExpr::on_first_char("alphanumeric character", char::is_alphanumeric)
+	}
+
+	/// Create a match expression that matches any character in the given Unicode general category. Matches maximum one character.
+	pub fn general_category(category:GeneralCategory) -> MatchExpr {
+		MatchExpr::on_first_char(category.abbreviation(), move |char| GeneralCategory::of(char) == category)
+	}
+
+	/// Create a match expression that matches any character in one of the given Unicode general categories, like `GeneralCategory::LETTER`. Matches maximum one character.
+	pub fn general_categories(categories:&[GeneralCategory]) -> MatchExpr {
+		let description:String = categories.iter().map(GeneralCategory::abbreviation).collect::<Vec<&str>>().join("|");
+		let categories:Vec<GeneralCategory> = categories.to_vec();
+		MatchExpr::on_first_char(&description, move |char| categories.contains(&GeneralCategory::of(char)))
+	}
+
+	/// Create a match expression that matches any character that can start an identifier according to Unicode XID_Start. Matches maximum one character.
+	pub fn xid_start() -> MatchExpr {
+		MatchExpr::on_first_char("XID_Start", |char| unicode_tables::in_ranges(char, unicode_tables::XID_START))
+	}
+
+	/// Create a match expression that matches any character that can continue an identifier according to Unicode XID_Continue. Matches maximum one character.
+	pub fn xid_continue() -> MatchExpr {
+		MatchExpr::on_first_char("XID_Continue", |char| unicode_tables::in_ranges(char, unicode_tables::XID_CONTINUE))
+	}
+
+
+
+	/* USER-DEFINED CHARACTER CLASS MATCH-EXPRESSION METHODS */
+
+	/// Create a match expression that matches any character in the given set description, like "a-zA-Z0-9_". See `CharSet::new`. Matches maximum one character.
+	pub fn char_in(set_description:&str) -> MatchExpr {
+		MatchExpr::char_set(CharSet::new(set_description))
+	}
+
+	/// Create a match expression that matches any character not in the given set description. Matches maximum one character.
+	pub fn char_not_in(set_description:&str) -> MatchExpr {
+		MatchExpr::char_set(CharSet::new(set_description).negated())
+	}
+
+	/// Create a match expression that matches any character in the given set. Matches maximum one character.
+	pub fn char_set(set:CharSet) -> MatchExpr {
+		MatchExpr::on_first_char(&set.to_string(), move |char| set.contains(char))
+	}
+
+	/// Create a match expression that matches any character in the given range. Matches maximum one character.
+	pub fn char_range(range:RangeInclusive<char>) -> MatchExpr {
+		MatchExpr::char_set(CharSet::from_ranges(vec![range]))
+	}
+
+	/// Create a match expression that matches any character the given function accepts. The description is reported as expectation when the character is not accepted. Matches maximum one character.
+	pub fn char_matching<T:Fn(char) -> bool + Send + Sync + 'static>(description:&str, compare_function:T) -> MatchExpr {
+		MatchExpr::on_first_char(description, compare_function)
+	}
+
+
+
+	/* REPLACE METHODS */
+
+	/// Create a copy of the text with every non-overlapping match replaced. See `Replacer` for templates and closures.
+	pub fn replace_all<T:Replacer>(&self, text:&str, replacer:T) -> String {
+		self.replace_n(text, usize::MAX, replacer)
+	}
+
+	/// Create a copy of the text with the first `limit` non-overlapping matches replaced. See `Replacer` for templates and closures.
+	pub fn replace_n<T:Replacer>(&self, text:&str, limit:usize, replacer:T) -> String {
+		let context:MatchContext = MatchContext::new(text);
+		let mut cursor:usize = 0;
+		let matches = std::iter::from_fn(|| {
+
+			// The end of the text is tried as well, like `split` does, so empty matches there are replaced.
+			while cursor <= text.len() {
+				let match_result:Option<MatchHit> = self.match_text_at(&context, cursor);
+				cursor += match &match_result {
+					Some(match_result) if match_result.length > 0 => match_result.length,
+					_ => context.char_at(cursor).map(char::len_utf8).unwrap_or(1)
+				};
+				if let Some(match_result) = match_result {
+					return Some((match_result.start, match_result));
+				}
+			}
+			None
+		});
+		replace_matches(text, matches, limit, replacer)
+	}
+
+
+
+	/* SPLIT METHODS */
+
+	/// Split the text at every non-overlapping match of this match-expression, like `str::split`. Yields the start index and text of each piece between the matches.
+	pub fn split<'a>(&'a self, text:&'a str) -> Split<'a> {
+		Split::new(self, text, usize::MAX, false)
+	}
+
+	/// Split the text at the first matches of this match-expression into at most `max_pieces` pieces, like `str::splitn`. The last piece contains the rest of the text.
+	pub fn splitn<'a>(&'a self, text:&'a str, max_pieces:usize) -> Split<'a> {
+		Split::new(self, text, max_pieces, false)
+	}
+
+	/// Split the text after every non-overlapping match of this match-expression, like `str::split_inclusive`. Each piece ends with the match that terminated it.
+	pub fn split_inclusive<'a>(&'a self, text:&'a str) -> Split<'a> {
+		Split::new(self, text, usize::MAX, true)
+	}
 
 
 
 	/* HELPER METHODS */
 	
-	/// Create a match-expression that checks something on the first character.
-	fn on_first_char<T:Fn(char) -> bool + 'static>(compare_function:T) -> MatchExpr {
-		MatchExpr::new(move |text:&str| {
-			if !text.is_empty() {
-				if let Some(first_char) = text[..1].chars().next() {
-					if compare_function(first_char) {
-						return Some(MatchHit::new(1, text));
+	/// Get the longest of the given results. When multiple results have the same length, the first of them wins.
+	pub(crate) fn longest<T:Iterator<Item = MatchHit>>(results:T) -> Option<MatchHit> {
+		results.fold(None, |longest:Option<MatchHit>, result| match longest {
+			Some(longest) if longest.length >= result.length => Some(longest),
+			_ => Some(result)
+		})
+	}
+
+	/// Match one or more items separated by the separator, optionally allowing a trailing separator.
+	fn separated_list<T:TextPredicate + 'static, U:TextPredicate + 'static>(item:T, separator:U, allow_trailing:bool) -> MatchExpr {
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let first_item:MatchHit = item.match_text_at(context, cursor)?;
+			let mut end:usize = first_item.end();
+			let mut sub_matches:Vec<MatchHit> = vec![first_item];
+			while let Some(separator_match) = separator.match_text_at(context, end) {
+				match item.match_text_at(context, separator_match.end()) {
+					Some(item_match) if item_match.end() > end => {
+						end = item_match.end();
+						if !separator_match.type_name.is_empty() {
+							sub_matches.push(separator_match);
+						}
+						sub_matches.push(item_match);
+					},
+					_ => {
+						if allow_trailing && separator_match.length > 0 {
+							end = separator_match.end();
+							if !separator_match.type_name.is_empty() {
+								sub_matches.push(separator_match);
+							}
+						}
+						break;
 					}
 				}
 			}
+			Some(MatchHit::new_at_with_list(cursor, end - cursor, context.contents_source(), sub_matches))
+		})
+	}
+
+	/// Get the minimum and maximum count of a range of repetition counts.
+	pub(crate) fn count_bounds<T:RangeBounds<usize>>(count_range:&T) -> (usize, usize) {
+		let min_count:usize = match count_range.start_bound() {
+			Bound::Included(count) => *count,
+			Bound::Excluded(count) => count + 1,
+			Bound::Unbounded => 0
+		};
+		let max_count:usize = match count_range.end_bound() {
+			Bound::Included(count) => *count,
+			Bound::Excluded(count) => count.saturating_sub(1),
+			Bound::Unbounded => usize::MAX
+		};
+		(min_count, max_count)
+	}
+
+	/// Check if the given matcher matches any piece of text of up to `MAX_LOOKBEHIND` characters ending exactly at the given cursor. Starts are tried from the cursor backwards.
+	fn matches_behind<T:TextPredicate>(matcher:&T, context:&MatchContext, cursor:usize) -> bool {
+		let text:&str = context.text();
+		let cursor:usize = cursor.min(text.len());
+		if !text.is_char_boundary(cursor) {
+			return false;
+		}
+		context.without_expectations(|| {
+			let mut starts = std::iter::once(cursor).chain(text[..cursor].char_indices().rev().map(|(start, _)| start)).take(MAX_LOOKBEHIND + 1);
+			starts.any(|start| matcher.match_text_at(context, start).is_some_and(|result| result.end() == cursor))
+		})
+	}
+
+	/// Create a match-expression that matches with length 0 when the given check on the full text and cursor succeeds. The description is reported as expectation when the check fails.
+	fn at_position<T:Fn(&str, usize) -> bool + Send + Sync + 'static>(description:&str, check_function:T) -> MatchExpr {
+		let description:String = description.to_string();
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			if context.text().is_char_boundary(cursor) && check_function(context.text(), cursor) {
+				return Some(MatchHit::new_at(cursor, 0, context.contents_source()));
+			}
+			context.expect(cursor, Expectation::CharClass(description.clone()));
+			None
+		})
+	}
+
+	/// Create a match-expression that checks something on the first character. The description is reported as expectation when the check fails.
+	fn on_first_char<T:Fn(char) -> bool + Send + Sync + 'static>(description:&str, compare_function:T) -> MatchExpr {
+		let description:String = description.to_string();
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			if let Some(first_char) = context.char_at(cursor) && compare_function(first_char) {
+				return Some(MatchHit::new_at(cursor, first_char.len_utf8(), context.contents_source()));
+			}
+			context.expect(cursor, Expectation::CharClass(description.clone()));
 			None
 		})
 	}
 }
 impl TextPredicate for MatchExpr {
-	fn match_text(&self, text:&str) -> Option<MatchHit> {
-		self.0.match_text(text)
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		self.0.match_text_at(context, cursor)
 	}
 }
 impl<T:TextPredicate + 'static> Add<T> for MatchExpr {
 	type Output = MatchExpr;
 
 	fn add(self, rhs:T) -> Self::Output {
-		MatchExpr::new(move |text:&str| {
-			if let Some(left_match) = self.match_text(text) {
-				let remaining_text:&str = if text.len() > left_match.length { &text[left_match.length..] } else { "" };
-				if let Some(right_match) = rhs.match_text(remaining_text) {
-					return Some(MatchHit::new_with_sub_matches(left_match.length + right_match.length, text, vec![left_match, right_match]));
-				}
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			if let Some(left_match) = self.match_text_at(context, cursor) && let Some(right_match) = rhs.match_text_at(context, left_match.end()) {
+				return Some(MatchHit::new_at_with_sub_matches(cursor, left_match.length + right_match.length, context.contents_source(), vec![left_match, right_match]));
 			}
 			None
 		})
@@ -192,27 +686,41 @@ impl Mul<usize> for MatchExpr {
 	type Output = MatchExpr;
 
 	fn mul(self, rhs:usize) -> Self::Output {
-		MatchExpr::new(move |text:&str| {
-			let mut cursor:usize = 0;
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let mut end:usize = cursor;
 			let mut sub_results:Vec<MatchHit> = Vec::new();
 			for _ in 0..rhs {
-				let remaining_text:&str = if text.len() > cursor { &text[cursor..] } else { "" };
-				match self.match_text(remaining_text) {
+				match self.match_text_at(context, end) {
 					Some(match_result) => {
-						cursor += match_result.length;
+						end += match_result.length;
 						sub_results.push(match_result);
 					},
 					None => return None
 				}
 			}
-			let type_name:String = sub_results.iter().next().map(|result| result.type_name.clone()).unwrap_or(String::new());
-			Some(MatchHit::named_with_sub_matches(&type_name, cursor, text, sub_results))
+			let type_name:String = sub_results.first().map(|result| result.type_name.clone()).unwrap_or_default();
+			Some(MatchHit::named_at_with_sub_matches(&type_name, cursor, end - cursor, context.contents_source(), sub_results))
 		})
 	}
 }
+macro_rules! count_range_multiplier {
+	($($range_type:ty),*) => {
+		$(
+			impl Mul<$range_type> for MatchExpr {
+				type Output = MatchExpr;
+
+				fn mul(self, rhs:$range_type) -> Self::Output {
+					MatchExpr::repeat(self, rhs)
+				}
+			}
+		)*
+	};
+}
+count_range_multiplier!(Range<usize>, RangeInclusive<usize>, RangeFrom<usize>, RangeTo<usize>, RangeToInclusive<usize>);
 impl<T:TextPredicate + 'static> BitAnd<T> for MatchExpr {
 	type Output = MatchExpr;
 
+	#[allow(clippy::suspicious_arithmetic_impl)]
 	fn bitand(self, rhs:T) -> Self::Output {
 		self + rhs
 	}
@@ -221,14 +729,8 @@ impl<T:TextPredicate + 'static> BitOr<T> for MatchExpr {
 	type Output = MatchExpr;
 
 	fn bitor(self, rhs:T) -> Self::Output {
-		MatchExpr::new(move |text:&str| {
-			if let Some(match_result) = self.match_text(text) {
-				Some(match_result)
-			} else if let Some(match_result) = rhs.match_text(text) {
-				Some(match_result)
-			} else {
-				None
-			}
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			self.match_text_at(context, cursor).or_else(|| rhs.match_text_at(context, cursor))
 		})
 	}
 }
@@ -236,15 +738,29 @@ impl Not for MatchExpr {
 	type Output = MatchExpr;
 
 	fn not(self) -> Self::Output {
-		MatchExpr::new(move |text:&str| {
-			if text.is_empty() {
-				None
-			} else { 
-				match self.match_text(text) {
-					Some(_) => None,
-					None => Some(MatchHit::new(1, text))
+		MatchExpr::positional(move |context:&MatchContext, cursor:usize| {
+			match context.char_at(cursor) {
+				None => {
+					context.expect(cursor, Expectation::CharClass("any character".to_string()));
+					None
+				},
+				Some(first_char) => {
+					match context.without_expectations(|| self.match_text_at(context, cursor)) {
+						Some(_) => None,
+						None => Some(MatchHit::new_at(cursor, first_char.len_utf8(), context.contents_source()))
+					}
 				}
 			}
 		})
 	}
+}
+
+
+
+/// A predicate wrapping a function that matches at a cursor in the full text.
+struct PositionalPredicate<T:Fn(&MatchContext, usize) -> Option<MatchHit> + Send + Sync>(T);
+impl<T:Fn(&MatchContext, usize) -> Option<MatchHit> + Send + Sync> TextPredicate for PositionalPredicate<T> {
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		(self.0)(context, cursor)
+	}
 }
\ No newline at end of file
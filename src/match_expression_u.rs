@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-	use crate::{ MatchHit, MatchExpr, TextPredicate };
+	use crate::{ CaseMode, Expectation, GeneralCategory, MatchContext, MatchHit, MatchExpr, MatcherRegistry, TextPredicate };
 
 
 	#[test]
@@ -93,6 +93,37 @@ mod tests {
 
 
 
+	#[test]
+	fn test_matcher_spans() {
+		let matcher:MatchExpr = MatchExpr::named("a", 'a') + MatchExpr::named("b", MatchExpr::repeat_max('b')) + MatchExpr::named("c", MatchExpr::new("c") * 2);
+		let result:MatchHit = matcher.match_text("abbbcc").unwrap();
+		assert_eq!(result.span(), 0..6);
+		assert_eq!(result.find_child(|child| child.type_name == "a").unwrap().span(), 0..1);
+		assert_eq!(result.find_child(|child| child.type_name == "b").unwrap().span(), 1..4);
+		assert_eq!(result.find_child(|child| child.type_name == "c").unwrap().span(), 4..6);
+
+		let matcher:MatchExpr = MatchExpr::optional_repeat_max(MatchExpr::named("pair", MatchExpr::named("key", MatchExpr::word()) + '=' + MatchExpr::named("value", MatchExpr::unsigned_integer()) + ';'));
+		let result:MatchHit = matcher.match_text("a=1;bc=23;").unwrap();
+		let values:Vec<&MatchHit> = result.find_children(|child| child.type_name == "value");
+		assert_eq!(values.iter().map(|value| value.span()).collect::<Vec<_>>(), vec![2..3, 7..9]);
+		let keys:Vec<&MatchHit> = result.find_children(|child| child.type_name == "key");
+		assert_eq!(keys.iter().map(|key| key.span()).collect::<Vec<_>>(), vec![0..1, 4..6]);
+	}
+
+
+
+	#[test]
+	fn test_matcher_named_underscore() {
+		// Only hidden rules compiled from grammars keep the name of their match, names with an underscore are not special.
+		let matcher:MatchExpr = MatchExpr::named("_item", MatchExpr::named("number", MatchExpr::unsigned_integer()) | MatchExpr::word());
+		assert_eq!(matcher.match_text("12").unwrap().type_name, "_item");
+		assert_eq!(MatchExpr::named("_tag", MatchExpr::word()).match_text("abc").unwrap().type_name, "_tag");
+		assert_eq!(matcher.try_match_text("!").unwrap_err().expected, vec![Expectation::Rule("_item".to_string())]);
+		assert_eq!(MatcherRegistry::new().with_matcher("_x", MatchExpr::word()).match_text("abc").unwrap().type_name, "_x");
+	}
+
+
+
 	/* REPEATING MATCHER TESTS */
 
 	#[test]
@@ -111,6 +142,61 @@ mod tests {
 		assert_eq!(matcher.match_text("").unwrap().length, 0);
 	}
 
+	#[test]
+	fn test_matcher_repeat_range() {
+		let matcher:MatchExpr = MatchExpr::ascii_hexdigit() * (2..=4);
+		assert_eq!(matcher.match_text("f"), None);
+		assert_eq!(matcher.match_text("ff").unwrap().length, 2);
+		assert_eq!(matcher.match_text("ffffff").unwrap().length, 4);
+
+		let matcher:MatchExpr = MatchExpr::ascii_hexdigit() * (2..4);
+		assert_eq!(matcher.match_text("ffffff").unwrap().length, 3);
+
+		let matcher:MatchExpr = MatchExpr::new("xa") * (1..);
+		assert_eq!(matcher.match_text("xaxaxab").unwrap().length, 6);
+		assert_eq!(matcher.match_text("b"), None);
+
+		let matcher:MatchExpr = MatchExpr::new("xa") * (..2);
+		assert_eq!(matcher.match_text("xaxaxa").unwrap().length, 2);
+		assert_eq!(matcher.match_text("b").unwrap().length, 0);
+
+		let matcher:MatchExpr = MatchExpr::new("xa") * (..=2);
+		assert_eq!(matcher.match_text("xaxaxa").unwrap().length, 4);
+	}
+
+	#[test]
+	fn test_matcher_repeat_range_children() {
+		let ipv6_group:MatchExpr = MatchExpr::named("group", MatchExpr::ascii_hexdigit() * (1..=4));
+		let matcher:MatchExpr = ipv6_group.clone() + MatchExpr::repeat(MatchExpr::new(":") + ipv6_group, 7..=7);
+		let result:MatchHit = matcher.match_text("2001:db8:0:0:0:ff00:42:8329").unwrap();
+		assert_eq!(result.length, 27);
+		assert_eq!(result.find_children(|child| child.type_name == "group").iter().map(|group| group.contents.as_str()).collect::<Vec<&str>>(), vec!["2001", "db8", "0", "0", "0", "ff00", "42", "8329"]);
+		assert_eq!(matcher.match_text("2001:db8:0:0:0:ff00:42"), None);
+	}
+
+	#[test]
+	fn test_matcher_repeat_zero_length() {
+		let matcher:MatchExpr = MatchExpr::repeat(MatchExpr::optional("x"), 3..);
+		assert_eq!(matcher.match_text("xb").unwrap().length, 1);
+		assert_eq!(matcher.match_text("b").unwrap().length, 0);
+	}
+
+	#[test]
+	fn test_matcher_repeat_lazy() {
+		let matcher:MatchExpr = MatchExpr::repeat_lazy(!MatchExpr::linebreak(), 0.., "*/");
+		assert_eq!(matcher.match_text("comment */ code */").unwrap().contents, "comment */");
+		assert_eq!(matcher.match_text("comment"), None);
+
+		let matcher:MatchExpr = MatchExpr::repeat_lazy(MatchExpr::ascii_hexdigit(), 2..=3, MatchExpr::digit());
+		assert_eq!(matcher.match_text("a1b2").unwrap().contents, "a1b2");
+		assert_eq!(matcher.match_text("ab1").unwrap().contents, "ab1");
+		assert_eq!(matcher.match_text("abcdx"), None);
+
+		// The greedy variant consumes the digit the following element needs.
+		let matcher:MatchExpr = (MatchExpr::ascii_hexdigit() * (2..=3)) + MatchExpr::digit();
+		assert_eq!(matcher.match_text("ab1"), None);
+	}
+
 	#[test]
 	fn test_matcher_optional() {
 		let matcher:MatchExpr = MatchExpr::optional("xa");
@@ -121,6 +207,173 @@ mod tests {
 
 
 
+	/* LITERAL MATCHER TESTS */
+
+	#[test]
+	fn test_matcher_ignore_case() {
+		let matcher:MatchExpr = MatchExpr::ignore_ascii_case("true") | MatchExpr::ignore_ascii_case("false");
+		assert_eq!(matcher.match_text("TRUE").unwrap().length, 4);
+		assert_eq!(matcher.match_text("False").unwrap().length, 5);
+		assert_eq!(matcher.try_match_text("yes").unwrap_err().expected, vec![Expectation::Literal("true".to_string()), Expectation::Literal("false".to_string())]);
+
+		assert_eq!(MatchExpr::ignore_case("größe").match_text("GRÖSSE=1").unwrap().contents, "GRÖSSE");
+		assert_eq!(MatchExpr::ignore_ascii_case("größe").match_text("GRÖSSE=1"), None);
+		assert_eq!(MatchExpr::char_ignore_case('ж').match_text("Ж").unwrap().length, 2);
+		assert_eq!(MatchExpr::char_ignore_ascii_case('x').match_text("X").unwrap().length, 1);
+		assert_eq!(MatchExpr::char_ignore_ascii_case('ж').match_text("Ж"), None);
+	}
+
+	#[test]
+	fn test_matcher_context_case_mode() {
+		let matcher:MatchExpr = MatchExpr::new("select") + ' ' + MatchExpr::literal("ID", CaseMode::Sensitive);
+		assert_eq!(matcher.match_text("SELECT ID"), None);
+		let context:MatchContext = MatchContext::new("SELECT ID").with_case_mode(CaseMode::IgnoreAsciiCase);
+		assert_eq!(matcher.match_text_at(&context, 0).unwrap().length, 9);
+		let context:MatchContext = MatchContext::new("SELECT id").with_case_mode(CaseMode::IgnoreAsciiCase);
+		assert_eq!(matcher.match_text_at(&context, 0), None);
+	}
+
+
+
+	/* CHOICE MATCHER TESTS */
+
+	#[test]
+	fn test_matcher_choice() {
+		let matcher:MatchExpr = MatchExpr::choice(["xa", "xaba", "b"]);
+		assert_eq!(matcher.match_text("xaba").unwrap().length, 2);
+		assert_eq!(matcher.match_text("baba").unwrap().length, 1);
+		assert_eq!(matcher.match_text("aba"), None);
+		assert_eq!(MatchExpr::choice(Vec::<&str>::new()).match_text("x"), None);
+
+		let matcher:MatchExpr = MatchExpr::choice(vec![MatchExpr::named("number", MatchExpr::unsigned_integer()), MatchExpr::named("word", MatchExpr::word())]);
+		assert_eq!(matcher.match_text("abc").unwrap().type_name, "word");
+		assert_eq!(matcher.try_match_text("!").unwrap_err().expected, vec![Expectation::Rule("number".to_string()), Expectation::Rule("word".to_string())]);
+	}
+
+	#[test]
+	fn test_matcher_longest_of() {
+		let matcher:MatchExpr = MatchExpr::longest_of(["xa", "xaba", "b"]);
+		assert_eq!(matcher.match_text("xaba").unwrap().length, 4);
+		assert_eq!(matcher.match_text("xabc").unwrap().length, 2);
+		assert_eq!(matcher.match_text("aba"), None);
+
+		let matcher:MatchExpr = MatchExpr::longest_of(vec![MatchExpr::named("keyword", MatchExpr::new("if")), MatchExpr::named("identifier", MatchExpr::identifier())]);
+		assert_eq!(matcher.match_text("if x").unwrap().type_name, "keyword");
+		assert_eq!(matcher.match_text("iffy").unwrap().type_name, "identifier");
+	}
+
+
+
+	/* LIST MATCHER TESTS */
+
+	#[test]
+	fn test_matcher_separated_by() {
+		let matcher:MatchExpr = MatchExpr::separated_by(MatchExpr::unsigned_integer(), MatchExpr::new(",") + MatchExpr::optional_repeat_max(MatchExpr::inline_whitespace()));
+		let result:MatchHit = matcher.match_text("1, 22,333,").unwrap();
+		assert_eq!(result.length, 9);
+		assert_eq!(result.sub_matches.iter().map(|item| (item.contents.as_str(), item.span())).collect::<Vec<_>>(), vec![("1", 0..1), ("22", 3..5), ("333", 6..9)]);
+		assert_eq!(matcher.match_text("7").unwrap().sub_matches.len(), 1);
+		assert_eq!(matcher.match_text(",7"), None);
+	}
+
+	#[test]
+	fn test_matcher_separated_by_trailing() {
+		let matcher:MatchExpr = MatchExpr::separated_by_trailing(MatchExpr::word(), ",");
+		let result:MatchHit = matcher.match_text("a,bc,]").unwrap();
+		assert_eq!(result.contents, "a,bc,");
+		assert_eq!(result.sub_matches.iter().map(|item| item.contents.as_str()).collect::<Vec<&str>>(), vec!["a", "bc"]);
+		assert_eq!(matcher.match_text("a,,b").unwrap().contents, "a,");
+	}
+
+	#[test]
+	fn test_matcher_separated_by_named_separators() {
+		let matcher:MatchExpr = MatchExpr::separated_by(MatchExpr::named("number", MatchExpr::unsigned_integer()), MatchExpr::named("operator", MatchExpr::new("+") | "-"));
+		let result:MatchHit = matcher.match_text("1+2-3").unwrap();
+		assert_eq!(result.sub_matches.iter().map(|item| (item.type_name.as_str(), item.contents.as_str())).collect::<Vec<_>>(), vec![("number", "1"), ("operator", "+"), ("number", "2"), ("operator", "-"), ("number", "3")]);
+	}
+
+	#[test]
+	fn test_matcher_delimited() {
+		let matcher:MatchExpr = MatchExpr::delimited("(", MatchExpr::separated_by(MatchExpr::word(), ","), ")");
+		let result:MatchHit = matcher.match_text("(a,b)c").unwrap();
+		assert_eq!(result.span(), 0..5);
+		assert_eq!(result.sub_matches.len(), 1);
+		assert_eq!(result.sub_matches[0].span(), 1..4);
+		assert_eq!(result.sub_matches[0].sub_matches.iter().map(|item| item.contents.as_str()).collect::<Vec<&str>>(), vec!["a", "b"]);
+		assert_eq!(matcher.match_text("(a,b"), None);
+
+		let matcher:MatchExpr = MatchExpr::delimited(MatchExpr::named("open", "<"), MatchExpr::word(), ">");
+		assert_eq!(matcher.match_text("<a>").unwrap().sub_matches.iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["<", "a"]);
+	}
+
+	#[test]
+	fn test_matcher_surrounded_by() {
+		let matcher:MatchExpr = MatchExpr::surrounded_by(MatchExpr::optional_repeat_max(MatchExpr::char_not_in("\"")), '"');
+		let result:MatchHit = matcher.match_text("\"日本\" x").unwrap();
+		assert_eq!(result.contents, "\"日本\"");
+		assert_eq!(result.sub_matches[0].contents, "日本");
+		assert_eq!(matcher.match_text("\"unterminated"), None);
+	}
+
+
+
+	/* ASSERTION MATCHER TESTS */
+
+	#[test]
+	fn test_matcher_followed_by() {
+		let matcher:MatchExpr = MatchExpr::word() + MatchExpr::followed_by("(");
+		assert_eq!(matcher.match_text("call(1)").unwrap().contents, "call");
+		assert_eq!(matcher.match_text("call 1"), None);
+		assert_eq!(MatchExpr::followed_by("x").match_text("xyz").unwrap().span(), 0..0);
+	}
+
+	#[test]
+	fn test_matcher_not_followed_by() {
+		let keyword:MatchExpr = MatchExpr::new("if") + MatchExpr::not_followed_by(MatchExpr::xid_continue());
+		assert_eq!(keyword.match_text("if (x)").unwrap().length, 2);
+		assert_eq!(keyword.match_text("if").unwrap().length, 2);
+		assert_eq!(keyword.match_text("iffy"), None);
+		assert_eq!(keyword.match_text("ifé"), None);
+
+		let end_of_input:MatchExpr = MatchExpr::not_followed_by(MatchExpr::char_matching("any character", |_| true));
+		assert_eq!(end_of_input.match_text(""), Some(MatchHit::new(0, "")));
+		assert_eq!(end_of_input.match_text("x"), None);
+	}
+
+	#[test]
+	fn test_matcher_preceded_by() {
+		let matcher:MatchExpr = MatchExpr::repeat_max(MatchExpr::char_not_in("$")) + "$" + MatchExpr::preceded_by("US$") + MatchExpr::unsigned_integer();
+		assert_eq!(matcher.match_text("US$100").unwrap().length, 6);
+		assert_eq!(matcher.match_text("AU$100"), None);
+
+		let context:MatchContext = MatchContext::new("日本語");
+		assert_eq!(MatchExpr::preceded_by("本").match_text_at(&context, 6).unwrap().span(), 6..6);
+		assert_eq!(MatchExpr::preceded_by("日").match_text_at(&context, 6), None);
+		assert_eq!(MatchExpr::preceded_by(MatchExpr::repeat_max(MatchExpr::unicode_alphabetic())).match_text_at(&context, 9).unwrap().span(), 9..9);
+		assert_eq!(MatchExpr::preceded_by("x").match_text_at(&context, 0), None);
+	}
+
+	#[test]
+	fn test_matcher_not_preceded_by() {
+		let matcher:MatchExpr = MatchExpr::not_preceded_by("\\") + "\"";
+		let context:MatchContext = MatchContext::new("a\\\"b\"");
+		assert_eq!(matcher.match_text_at(&context, 2), None);
+		assert_eq!(matcher.match_text_at(&context, 4).unwrap().span(), 4..5);
+		assert_eq!(matcher.match_text("\"").unwrap().length, 1);
+	}
+
+	#[test]
+	fn test_matcher_preceded_by_window() {
+		let matcher:MatchExpr = MatchExpr::preceded_by(MatchExpr::new("x") + MatchExpr::repeat_max("a"));
+		let within_window:String = format!("x{}", "a".repeat(255));
+		assert!(matcher.match_text_at(&MatchContext::new(&within_window), within_window.len()).is_some());
+		let past_window:String = format!("x{}", "a".repeat(256));
+		assert_eq!(matcher.match_text_at(&MatchContext::new(&past_window), past_window.len()), None);
+		assert!(MatchExpr::not_preceded_by(MatchExpr::new("x") + MatchExpr::repeat_max("a")).match_text_at(&MatchContext::new(&past_window), past_window.len()).is_some());
+	}
+
+
+
 	/* WHITESPACE MATCHER TESTS */
 
 	#[test]
@@ -206,6 +459,9 @@ mod tests {
 		assert_eq!(MatchExpr::alphabetic().match_text("zAbA").unwrap().length, 1);
 		assert_eq!(MatchExpr::alphabetic().match_text("AbA").unwrap().length, 1);
 		assert_eq!(MatchExpr::alphabetic().match_text(""), None);
+		for char in ['[', '\\', ']', '^', '_', '`'] {
+			assert_eq!(MatchExpr::alphabetic().match_text(&char.to_string()), None);
+		}
 	}
 	
 	#[test]
@@ -234,4 +490,133 @@ mod tests {
 		assert_eq!(MatchExpr::word().match_text("AbA").unwrap().length, 3);
 		assert_eq!(MatchExpr::word().match_text(""), None);
 	}
+
+
+
+	/* UTF-8 TESTS */
+
+	#[test]
+	fn test_matcher_multi_byte_char_classes() {
+		assert_eq!(MatchExpr::whitespace().match_text("\u{3000}x").unwrap().length, 3); // Ideographic space.
+		assert_eq!(MatchExpr::inline_whitespace().match_text("\u{A0}x").unwrap().length, 2);
+		assert_eq!(MatchExpr::whitespace().match_text("日本"), None);
+		assert_eq!(MatchExpr::digit().match_text("日本"), None);
+		assert_eq!(MatchExpr::alphabetic().match_text("é"), None);
+		assert_eq!(MatchExpr::linebreak().match_text("😀"), None);
+		assert_eq!(MatchExpr::word().match_text("abc日本"), MatchExpr::word().match_text("abc"));
+	}
+
+	#[test]
+	fn test_matcher_multi_byte_not() {
+		let matcher:MatchExpr = !MatchExpr::new("]");
+		assert_eq!(matcher.match_text("日本").unwrap().contents, "日");
+		assert_eq!(matcher.match_text("😀x").unwrap().length, 4);
+		assert_eq!(matcher.match_text("]日"), None);
+
+		let matcher:MatchExpr = MatchExpr::repeat_max(!MatchExpr::linebreak());
+		assert_eq!(matcher.match_text("名前=太郎 😀\nx").unwrap().contents, "名前=太郎 😀");
+	}
+
+	#[test]
+	fn test_matcher_multi_byte_sequences() {
+		let matcher:MatchExpr = MatchExpr::named("key", MatchExpr::repeat_max(!MatchExpr::new("="))) + "=" + MatchExpr::named("value", MatchExpr::optional_repeat_max(!MatchExpr::linebreak()));
+		let result:MatchHit = matcher.match_text("ключ=значение").unwrap();
+		assert_eq!(&result["key"], "ключ");
+		assert_eq!(&result["value"], "значение");
+		assert_eq!(result.find_child(|child| child.type_name == "value").unwrap().span(), 9..25);
+
+		assert_eq!((MatchExpr::new('é') * 2).match_text("ééé").unwrap().length, 4);
+		assert_eq!((MatchExpr::new("日") | '本').match_text("本日").unwrap().contents, "本");
+		assert_eq!(MatchExpr::optional('😀').match_text("😀😀").unwrap().length, 4);
+	}
+
+
+
+	/* CHARACTER CLASS TESTS */
+
+	#[test]
+	fn test_matcher_ascii_classes() {
+		assert_eq!(MatchExpr::ascii_alphanumeric().match_text("7a").unwrap().length, 1);
+		assert_eq!(MatchExpr::ascii_alphanumeric().match_text("_"), None);
+		assert_eq!(MatchExpr::ascii_hexdigit().match_text("F").unwrap().length, 1);
+		assert_eq!(MatchExpr::ascii_hexdigit().match_text("g"), None);
+		assert_eq!(MatchExpr::ascii_punctuation().match_text("{").unwrap().length, 1);
+		assert_eq!(MatchExpr::ascii_punctuation().match_text("«"), None);
+	}
+
+	#[test]
+	fn test_matcher_unicode_classes() {
+		assert_eq!(MatchExpr::unicode_alphabetic().match_text("é").unwrap().length, 2);
+		assert_eq!(MatchExpr::unicode_alphabetic().match_text("_"), None);
+		assert_eq!(MatchExpr::unicode_lowercase().match_text("ж").unwrap().length, 2);
+		assert_eq!(MatchExpr::unicode_uppercase().match_text("ж"), None);
+		assert_eq!(MatchExpr::unicode_numeric().match_text("٣").unwrap().length, 2);
+		assert_eq!(MatchExpr::unicode_alphanumeric().match_text("日").unwrap().length, 3);
+		assert_eq!(MatchExpr::general_category(GeneralCategory::CurrencySymbol).match_text("€5").unwrap().length, 3);
+		assert_eq!(MatchExpr::general_category(GeneralCategory::CurrencySymbol).match_text("5€"), None);
+		assert_eq!(MatchExpr::general_categories(GeneralCategory::PUNCTUATION).match_text("«").unwrap().length, 2);
+		assert_eq!(MatchExpr::general_categories(GeneralCategory::PUNCTUATION).try_match_text("a").unwrap_err().expected, vec![Expectation::CharClass("Pc|Pd|Ps|Pe|Pi|Pf|Po".to_string())]);
+	}
+
+	#[test]
+	fn test_matcher_identifier() {
+		assert_eq!(MatchExpr::identifier().match_text("_foo1 bar").unwrap().contents, "_foo1");
+		assert_eq!(MatchExpr::identifier().match_text("größe=1").unwrap().contents, "größe");
+		assert_eq!(MatchExpr::identifier().match_text("変数2+1").unwrap().contents, "変数2");
+		assert_eq!(MatchExpr::identifier().match_text("1abc"), None);
+		assert_eq!(MatchExpr::xid_start().match_text("_"), None);
+		assert_eq!(MatchExpr::xid_continue().match_text("_").unwrap().length, 1);
+		assert_eq!(MatchExpr::xid_continue().match_text("\u{301}").unwrap().length, 2);
+	}
+
+	#[test]
+	fn test_matcher_user_defined_classes() {
+		let matcher:MatchExpr = MatchExpr::repeat_max(MatchExpr::char_in("a-zA-Z0-9_"));
+		assert_eq!(matcher.match_text("snake_Case9-x").unwrap().contents, "snake_Case9");
+		assert_eq!(MatchExpr::char_not_in("\n=").match_text("="), None);
+		assert_eq!(MatchExpr::char_not_in("\n=").match_text("é").unwrap().length, 2);
+		assert_eq!(MatchExpr::char_range('α'..='ω').match_text("λ").unwrap().length, 2);
+		assert_eq!(MatchExpr::char_range('α'..='ω').match_text("Λ"), None);
+		assert_eq!(MatchExpr::char_matching("vowel", |char| "aeiou".contains(char)).try_match_text("x").unwrap_err().expected, vec![Expectation::CharClass("vowel".to_string())]);
+		assert_eq!(MatchExpr::char_in("a-c").try_match_text("x").unwrap_err().expected, vec![Expectation::CharClass("[a-c]".to_string())]);
+	}
+
+
+
+	/* ANCHOR TESTS */
+
+	#[test]
+	fn test_matcher_bof_eof() {
+		let context:MatchContext = MatchContext::new("ab");
+		assert_eq!(MatchExpr::bof().match_text_at(&context, 0).unwrap().span(), 0..0);
+		assert_eq!(MatchExpr::bof().match_text_at(&context, 1), None);
+		assert_eq!(MatchExpr::eof().match_text_at(&context, 2).unwrap().span(), 2..2);
+		assert_eq!(MatchExpr::eof().match_text_at(&context, 1), None);
+		assert_eq!((MatchExpr::word() + MatchExpr::eof()).match_text("ab").unwrap().length, 2);
+		assert_eq!((MatchExpr::word() + MatchExpr::eof()).try_match_text("ab!").unwrap_err().expected, vec![Expectation::CharClass("alphabetic character".to_string()), Expectation::EndOfInput]);
+		assert_eq!(MatchExpr::eof().match_text("").unwrap().length, 0);
+	}
+
+	#[test]
+	fn test_matcher_bol_eol() {
+		let context:MatchContext = MatchContext::new("a\nb\r\nc\rd");
+		let line_starts:Vec<usize> = (0..=10).filter(|cursor| MatchExpr::bol().match_text_at(&context, *cursor).is_some()).collect();
+		assert_eq!(line_starts, vec![0, 2, 5, 7]);
+		let line_ends:Vec<usize> = (0..=10).filter(|cursor| MatchExpr::eol().match_text_at(&context, *cursor).is_some()).collect();
+		assert_eq!(line_ends, vec![1, 3, 6, 8]);
+
+		let line:MatchExpr = MatchExpr::bol() + "b" + MatchExpr::eol();
+		assert_eq!(line.match_text_at(&context, 2).unwrap().span(), 2..3);
+		assert_eq!(line.match_text_at(&context, 0), None);
+	}
+
+	#[test]
+	fn test_matcher_word_boundary() {
+		let whole_word:MatchExpr = MatchExpr::word_boundary() + "cat" + MatchExpr::word_boundary();
+		let context:MatchContext = MatchContext::new("cat concat cats cat_ cat.");
+		let found:Vec<usize> = (0..context.text().len()).filter(|cursor| whole_word.match_text_at(&context, *cursor).is_some()).collect();
+		assert_eq!(found, vec![0, 21]);
+		assert_eq!(MatchExpr::word_boundary().match_text("").map(|result| result.length), None);
+		assert_eq!(MatchExpr::word_boundary().match_text("é").unwrap().length, 0);
+	}
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+use std::{ error::Error, fmt::{ Display, Formatter, Result } };
+
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Expectation {
+	/// A literal string or character.
+	Literal(String),
+	/// A class of characters, like digits or whitespace.
+	CharClass(String),
+	/// A named rule, like the ones created by `MatchExpr::named`.
+	Rule(String),
+	/// The end of the text, when text remains after a complete match.
+	EndOfInput
+}
+impl Display for Expectation {
+	fn fmt(&self, f:&mut Formatter<'_>) -> Result {
+		match self {
+			Expectation::Literal(literal) => write!(f, "{literal:?}"),
+			Expectation::CharClass(description) => write!(f, "{description}"),
+			Expectation::Rule(name) => write!(f, "{name}"),
+			Expectation::EndOfInput => write!(f, "end of input")
+		}
+	}
+}
+
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct MatchFailure {
+	pub position:usize,
+	pub expected:Vec<Expectation>,
+	pub found:Option<char>
+}
+impl Display for MatchFailure {
+	fn fmt(&self, f:&mut Formatter<'_>) -> Result {
+		let expected:Vec<String> = self.expected.iter().map(|expectation| expectation.to_string()).collect();
+		match expected.len() {
+			0 => write!(f, "unexpected input")?,
+			1 => write!(f, "expected {}", expected[0])?,
+			_ => write!(f, "expected one of {}", expected.join(", "))?
+		}
+		match self.found {
+			Some(found) => write!(f, " at byte {}, found {found:?}", self.position),
+			None => write!(f, " at byte {}, found end of input", self.position)
+		}
+	}
+}
+impl Error for MatchFailure {}
\ No newline at end of file
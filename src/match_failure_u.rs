@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Expectation, MatchExpr, MatchFailure, MatcherRegistry, TextPredicate };
+
+
+
+	/* DISPLAY TESTS */
+
+	#[test]
+	fn test_failure_display() {
+		let failure:MatchFailure = MatchFailure { position: 3, expected: vec![Expectation::Literal("=".to_string())], found: Some('x') };
+		assert_eq!(failure.to_string(), "expected \"=\" at byte 3, found 'x'");
+
+		let failure:MatchFailure = MatchFailure { position: 5, expected: vec![Expectation::Rule("value".to_string()), Expectation::CharClass("digit".to_string())], found: None };
+		assert_eq!(failure.to_string(), "expected one of value, digit at byte 5, found end of input");
+
+		let failure:MatchFailure = MatchFailure { position: 0, expected: Vec::new(), found: Some('a') };
+		assert_eq!(failure.to_string(), "unexpected input at byte 0, found 'a'");
+	}
+
+
+
+	/* FAILURE REPORTING TESTS */
+
+	#[test]
+	fn test_failure_literals() {
+		assert_eq!(
+			(MatchExpr::new("ab") + 'c').try_match_text("abx"),
+			Err(MatchFailure { position: 2, expected: vec![Expectation::Literal("c".to_string())], found: Some('x') })
+		);
+		assert_eq!(MatchExpr::new("ab").try_match_text("abx").unwrap().length, 2);
+	}
+
+	#[test]
+	fn test_failure_merged_alternatives() {
+		let matcher:MatchExpr = MatchExpr::new("x") + (MatchExpr::new("a") | "b" | MatchExpr::digit());
+		assert_eq!(
+			matcher.try_match_text("x-"),
+			Err(MatchFailure {
+				position: 1,
+				expected: vec![Expectation::Literal("a".to_string()), Expectation::Literal("b".to_string()), Expectation::CharClass("digit".to_string())],
+				found: Some('-')
+			})
+		);
+	}
+
+	#[test]
+	fn test_failure_furthest_position() {
+		let matcher:MatchExpr = (MatchExpr::new("ab") + "cd") | "abc";
+		assert_eq!(matcher.try_match_text("abx").unwrap_err().position, 2);
+		assert_eq!(matcher.try_match_text("abx").unwrap_err().expected, vec![Expectation::Literal("cd".to_string())]);
+		assert_eq!(matcher.try_match_text("abcx").unwrap().length, 3);
+	}
+
+	#[test]
+	fn test_failure_repetition_continuation() {
+		let matcher:MatchExpr = MatchExpr::optional_repeat_max('a') + 'b';
+		assert_eq!(
+			matcher.try_match_text("aac").unwrap_err(),
+			MatchFailure { position: 2, expected: vec![Expectation::Literal("a".to_string()), Expectation::Literal("b".to_string())], found: Some('c') }
+		);
+	}
+
+	#[test]
+	fn test_failure_named_rules() {
+		let value:MatchExpr = MatchExpr::named("value", MatchExpr::unsigned_integer() | "true" | "false");
+		let matcher:MatchExpr = MatchExpr::named("key", MatchExpr::word()) + '=' + value;
+
+		// A rule failing at its start is reported by name.
+		assert_eq!(
+			matcher.try_match_text("age=?").unwrap_err(),
+			MatchFailure { position: 4, expected: vec![Expectation::Rule("value".to_string())], found: Some('?') }
+		);
+		assert_eq!(
+			matcher.try_match_text("age=tru").unwrap_err(),
+			MatchFailure { position: 4, expected: vec![Expectation::Rule("value".to_string())], found: Some('t') }
+		);
+
+		// A rule that got further than its start reports what it was missing.
+		let pair:MatchExpr = MatchExpr::named("pair", MatchExpr::new("(") + MatchExpr::digit() + ")");
+		assert_eq!(
+			pair.try_match_text("(1]").unwrap_err(),
+			MatchFailure { position: 2, expected: vec![Expectation::Literal(")".to_string())], found: Some(']') }
+		);
+	}
+
+	#[test]
+	fn test_failure_negation_hidden() {
+		let matcher:MatchExpr = MatchExpr::repeat_max(!MatchExpr::new("]")) + "]";
+		assert_eq!(
+			matcher.try_match_text("abc").unwrap_err(),
+			MatchFailure { position: 3, expected: vec![Expectation::CharClass("any character".to_string()), Expectation::Literal("]".to_string())], found: None }
+		);
+	}
+
+	#[test]
+	fn test_failure_registry() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer()), ("word", MatchExpr::word())]);
+		assert_eq!(
+			set.try_match_text("-12"),
+			Err(MatchFailure { position: 0, expected: vec![Expectation::Rule("number".to_string()), Expectation::Rule("word".to_string())], found: Some('-') })
+		);
+		assert_eq!(set.try_match_text("12").unwrap().type_name, "number");
+	}
+
+
+
+	/* COMPLETE PARSE TESTS */
+
+	#[test]
+	fn test_failure_parse_complete() {
+		let matcher:MatchExpr = MatchExpr::unsigned_integer();
+		assert_eq!(matcher.parse_complete("123").unwrap().length, 3);
+		assert_eq!(
+			matcher.parse_complete("12x"),
+			Err(MatchFailure { position: 2, expected: vec![Expectation::CharClass("digit".to_string()), Expectation::EndOfInput], found: Some('x') })
+		);
+		assert_eq!(matcher.parse_complete("12x").unwrap_err().to_string(), "expected one of digit, end of input at byte 2, found 'x'");
+		assert_eq!(matcher.parse_complete("x").unwrap_err().position, 0);
+	}
+
+	#[test]
+	fn test_failure_registry_parse_complete() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer()), ("space", MatchExpr::repeat_max(MatchExpr::whitespace()))]);
+		assert_eq!(set.parse_complete("12 34").unwrap().sub_matches.len(), 3);
+		assert_eq!(set.parse_complete("").unwrap().length, 0);
+		assert_eq!(
+			set.parse_complete("12 34!"),
+			Err(MatchFailure { position: 5, expected: vec![Expectation::CharClass("digit".to_string()), Expectation::Rule("number".to_string()), Expectation::Rule("space".to_string()), Expectation::EndOfInput], found: Some('!') })
+		);
+	}
+}
\ No newline at end of file
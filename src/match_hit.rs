@@ -1,10 +1,12 @@
-use std::{ ops::Index, fmt::{ Display, Formatter, Result } };
+use std::{ ops::{ Index, Range }, fmt::{ Display, Formatter, Result } };
+use crate::{ SourceIndex, SourcePosition };
 
 
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct MatchHit {
 	pub type_name:String,
+	pub start:usize,
 	pub length:usize,
 	pub contents:String,
 	pub sub_matches:Vec<MatchHit>
@@ -14,18 +16,30 @@ impl MatchHit {
 	/* CONSTRUCTOR METHODS */
 
 	/// Create a new result.
+	#[cfg(test)]
 	pub(crate) fn new(match_length:usize, source_text:&str) -> MatchHit {
+		MatchHit::new_at(0, match_length, source_text)
+	}
+
+	/// Create a new result starting at the given byte position in the full source text. The contents are left empty when the source text is empty, as when only spans are recorded.
+	pub(crate) fn new_at(start:usize, match_length:usize, source_text:&str) -> MatchHit {
 		MatchHit {
 			type_name: String::new(),
+			start,
 			length: match_length,
-			contents: source_text[..match_length].to_string(),
+			contents: if source_text.is_empty() { String::new() } else { source_text[start..start + match_length].to_string() },
 			sub_matches: Vec::new()
 		}
 	}
 
 	/// Create a new result.
 	pub(crate) fn new_with_sub_matches(match_length:usize, source_text:&str, sub_matches:Vec<MatchHit>) -> MatchHit {
-		let mut result:MatchHit = MatchHit::new(match_length, source_text);
+		MatchHit::new_at_with_sub_matches(0, match_length, source_text, sub_matches)
+	}
+
+	/// Create a new result starting at the given byte position in the full source text.
+	pub(crate) fn new_at_with_sub_matches(start:usize, match_length:usize, source_text:&str, sub_matches:Vec<MatchHit>) -> MatchHit {
+		let mut result:MatchHit = MatchHit::new_at(start, match_length, source_text);
 		result.sub_matches = sub_matches;
 		result.combine_sub_matches();
 		if result.sub_matches.len() == 1 {
@@ -34,7 +48,15 @@ impl MatchHit {
 		result
 	}
 
+	/// Create a new result starting at the given byte position in the full source text, keeping the sub-matches exactly as given instead of combining unnamed neighbours.
+	pub(crate) fn new_at_with_list(start:usize, match_length:usize, source_text:&str, sub_matches:Vec<MatchHit>) -> MatchHit {
+		let mut result:MatchHit = MatchHit::new_at(start, match_length, source_text);
+		result.sub_matches = sub_matches;
+		result
+	}
+
 	/// Create a new result with a name.
+	#[cfg(test)]
 	pub(crate) fn named(name:&str, match_length:usize, source_text:&str) -> MatchHit {
 		let mut result:MatchHit = MatchHit::new(match_length, source_text);
 		result.type_name = name.to_string();
@@ -42,13 +64,52 @@ impl MatchHit {
 	}
 
 	/// Create a new result.
+	#[cfg(test)]
 	pub(crate) fn named_with_sub_matches(name:&str, match_length:usize, source_text:&str, sub_matches:Vec<MatchHit>) -> MatchHit {
-		let mut result:MatchHit = MatchHit::named(name, match_length, source_text);
+		MatchHit::named_at_with_sub_matches(name, 0, match_length, source_text, sub_matches)
+	}
+
+	/// Create a new result with a name, starting at the given byte position in the full source text.
+	pub(crate) fn named_at_with_sub_matches(name:&str, start:usize, match_length:usize, source_text:&str, sub_matches:Vec<MatchHit>) -> MatchHit {
+		let mut result:MatchHit = MatchHit::new_at(start, match_length, source_text);
+		result.type_name = name.to_string();
 		result.sub_matches = sub_matches;
 		result.combine_sub_matches();
 		result
 	}
 
+	/// Return self with this and all sub-results moved the given amount of bytes forward in the source text.
+	pub(crate) fn offset(mut self, offset:usize) -> MatchHit {
+		if offset != 0 {
+			self.execute_recursive_mut(move |result| result.start += offset);
+		}
+		self
+	}
+
+
+
+	/* POSITION METHODS */
+
+	/// The byte position in the source text directly after the end of this result.
+	pub fn end(&self) -> usize {
+		self.start + self.length
+	}
+
+	/// The range of bytes this result covers in the source text.
+	pub fn span(&self) -> Range<usize> {
+		self.start..self.end()
+	}
+
+	/// Get the line and column this result starts at in the indexed source text.
+	pub fn start_position(&self, index:&SourceIndex) -> SourcePosition {
+		index.position(self.start)
+	}
+
+	/// Get the line and column directly after the end of this result in the indexed source text.
+	pub fn end_position(&self, index:&SourceIndex) -> SourcePosition {
+		index.position(self.end())
+	}
+
 
 
 	/* USAGE METHODS */
@@ -109,7 +170,7 @@ impl MatchHit {
 			depth_list.push((current_depth, self.type_name.clone()));
 			child_depth += 1;
 		}
-		depth_list.extend(self.sub_matches.iter().map(|child| child._type_name_tree(child_depth)).flatten().collect::<Vec<(usize, String)>>());
+		depth_list.extend(self.sub_matches.iter().flat_map(|child| child._type_name_tree(child_depth)).collect::<Vec<(usize, String)>>());
 		depth_list
 	}
 
@@ -176,7 +237,7 @@ impl Index<&str> for MatchHit {
 	type Output = str;
 
 	fn index(&self, path:&str) -> &Self::Output {
-		let path:Vec<&str> = path.split(' ').map(|word| word.split('.')).flatten().collect();
+		let path:Vec<&str> = path.split(' ').flat_map(|word| word.split('.')).collect();
 		self.find_child_by_type_path(&path).map(|child| child.contents.as_str()).unwrap_or_default()
 	}
 }
\ No newline at end of file
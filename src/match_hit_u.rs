@@ -12,6 +12,7 @@ mod tests {
 			MatchHit::new(3, "hello"),
 			MatchHit {
 				type_name: String::new(),
+				start: 0,
 				length: 3,
 				contents: "hel".to_string(),
 				sub_matches: Vec::new()
@@ -19,6 +20,15 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_new_at() {
+		let result:MatchHit = MatchHit::new_at(2, 3, "hello");
+		assert_eq!(result.start, 2);
+		assert_eq!(result.contents, "llo");
+		assert_eq!(result.end(), 5);
+		assert_eq!(result.span(), 2..5);
+	}
+
 	#[test]
 	fn test_new_with_sub_matches_merging() {
 		let combined:MatchHit = MatchHit::new_with_sub_matches(5, "abcdef", vec![
@@ -43,6 +53,7 @@ mod tests {
 			MatchHit::named("word", 4, "wordplay"),
 			MatchHit {
 				type_name: "word".to_string(),
+				start: 0,
 				length: 4,
 				contents: "word".to_string(),
 				sub_matches: Vec::new()
@@ -61,6 +72,15 @@ mod tests {
 
 
 
+	#[test]
+	fn test_offset() {
+		let result:MatchHit = MatchHit::named_with_sub_matches("main", 3, "abcdef", vec![MatchHit::named("sub", 2, "abcdef")]).offset(4);
+		assert_eq!(result.span(), 4..7);
+		assert_eq!(result.sub_matches[0].span(), 4..6);
+	}
+
+
+
 	/* CHILD SEARCH TESTS */
 
 	#[test]
@@ -0,0 +1,80 @@
+use crate::{ MatchContext, MatchHit, MatcherRegistry };
+
+
+
+/// A lazy iterator over all matches of a registry anywhere in a text, created by `MatcherRegistry::find_iter` or `MatcherRegistry::find_overlapping_iter`. Yields the start index and result of each match.
+pub struct FindIter<'a> {
+	registry:&'a MatcherRegistry,
+	context:MatchContext<'a>,
+	cursor:usize,
+	overlapping:bool
+}
+impl<'a> FindIter<'a> {
+
+	/// Create a new iterator. In overlapping mode the cursor advances by one character after each match instead of skipping the matched text.
+	pub(crate) fn new(registry:&'a MatcherRegistry, text:&'a str, overlapping:bool) -> FindIter<'a> {
+		FindIter {
+			registry,
+			context: registry.context(text),
+			cursor: 0,
+			overlapping
+		}
+	}
+
+	/// The byte position the next match will be searched from.
+	pub fn position(&self) -> usize {
+		self.cursor
+	}
+}
+impl Iterator for FindIter<'_> {
+	type Item = (usize, MatchHit);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		while self.cursor < self.context.text().len() {
+			let (match_result, next_cursor) = if self.overlapping {
+				(self.registry.match_text_at(&self.context, self.cursor), self.cursor + self.context.char_at(self.cursor).map(char::len_utf8).unwrap_or(1))
+			} else {
+				self.registry.find_step(&self.context, self.cursor)
+			};
+			self.cursor = next_cursor;
+			if let Some(match_result) = match_result {
+				return Some((match_result.start, match_result));
+			}
+		}
+		None
+	}
+}
+
+
+
+/// A lazy iterator over consecutive matches of a registry from the start of a text, created by `MatcherRegistry::tokens`. Yields the start index and result of each match, and stops at the first position nothing matches or a match is empty.
+pub struct Tokens<'a> {
+	registry:&'a MatcherRegistry,
+	context:MatchContext<'a>,
+	cursor:usize
+}
+impl<'a> Tokens<'a> {
+
+	/// Create a new iterator.
+	pub(crate) fn new(registry:&'a MatcherRegistry, text:&'a str) -> Tokens<'a> {
+		Tokens {
+			registry,
+			context: registry.context(text),
+			cursor: 0
+		}
+	}
+
+	/// The byte position directly after the last token. When the iterator is exhausted before the end of the text, this is where tokenizing stopped.
+	pub fn position(&self) -> usize {
+		self.cursor
+	}
+}
+impl Iterator for Tokens<'_> {
+	type Item = (usize, MatchHit);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let match_result:MatchHit = self.registry.match_text_at(&self.context, self.cursor).filter(|match_result| match_result.length > 0)?;
+		self.cursor += match_result.length;
+		Some((match_result.start, match_result))
+	}
+}
\ No newline at end of file
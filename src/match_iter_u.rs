@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ FindIter, MatchExpr, MatchHit, MatcherRegistry, Tokens };
+
+
+
+	#[test]
+	fn test_find_iter_equals_find_matches() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer()), ("word", MatchExpr::word())]);
+		let text:&str = "日1 abc 22, x";
+		assert_eq!(set.find_iter(text).collect::<Vec<(usize, MatchHit)>>(), set.find_matches(text));
+		assert_eq!(set.find_iter(text).map(|(offset, _)| offset).collect::<Vec<usize>>(), vec![3, 5, 9, 13]);
+	}
+
+	#[test]
+	fn test_find_iter_is_lazy() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer())]);
+		let text:String = "1 22 333 ".repeat(1000);
+		let mut iterator:FindIter = set.find_iter(&text);
+		assert_eq!(iterator.by_ref().take(2).map(|(_, result)| result.contents).collect::<Vec<String>>(), vec!["1", "22"]);
+		assert_eq!(iterator.position(), 4);
+		assert_eq!(set.find_iter(&text).filter(|(_, result)| result.length == 3).nth(1).unwrap().0, 14);
+	}
+
+	#[test]
+	fn test_find_overlapping_iter() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("pair", MatchExpr::new("aa"))]);
+		assert_eq!(set.find_iter("aaaa").map(|(offset, _)| offset).collect::<Vec<usize>>(), vec![0, 2]);
+		assert_eq!(set.find_overlapping_iter("aaaa").map(|(offset, _)| offset).collect::<Vec<usize>>(), vec![0, 1, 2]);
+
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer())]);
+		assert_eq!(set.find_overlapping_iter("日123").map(|(_, result)| result.contents).collect::<Vec<String>>(), vec!["123", "23", "3"]);
+	}
+
+	#[test]
+	fn test_tokens() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer()), ("word", MatchExpr::word()), ("space", MatchExpr::new(" "))]);
+		assert_eq!(set.tokens("ab 12 cd").map(|(_, result)| result).collect::<Vec<MatchHit>>(), set.multi_match_text("ab 12 cd").sub_matches);
+
+		let mut tokens:Tokens = set.tokens("ab 12;cd");
+		assert_eq!(tokens.by_ref().map(|(offset, result)| (offset, result.type_name)).collect::<Vec<(usize, String)>>(), vec![(0, "word".to_string()), (2, "space".to_string()), (3, "number".to_string())]);
+		assert_eq!(tokens.position(), 5);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+use std::ops::Range;
+use crate::MatchHit;
+
+
+
+/// A match result that borrows its contents from the source text instead of owning a copy, so large parse trees do not duplicate the text they were parsed from.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MatchSpan<'a> {
+	pub type_name:String,
+	pub start:usize,
+	pub length:usize,
+	pub sub_matches:Vec<MatchSpan<'a>>,
+	source_text:&'a str
+}
+impl<'a> MatchSpan<'a> {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a span tree from a result, positioned in the given source text. The contents of the result are not used.
+	pub(crate) fn from_hit(result:MatchHit, source_text:&'a str) -> MatchSpan<'a> {
+		MatchSpan {
+			type_name: result.type_name,
+			start: result.start,
+			length: result.length,
+			sub_matches: result.sub_matches.into_iter().map(|sub_match| MatchSpan::from_hit(sub_match, source_text)).collect(),
+			source_text
+		}
+	}
+
+	/// Create an owned result with copied contents from this span and all sub-spans.
+	pub fn to_owned(&self) -> MatchHit {
+		MatchHit {
+			type_name: self.type_name.clone(),
+			start: self.start,
+			length: self.length,
+			contents: self.contents().to_string(),
+			sub_matches: self.sub_matches.iter().map(MatchSpan::to_owned).collect()
+		}
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// The matched part of the source text.
+	pub fn contents(&self) -> &'a str {
+		&self.source_text[self.span()]
+	}
+
+	/// The full source text the span was matched in.
+	pub fn source_text(&self) -> &'a str {
+		self.source_text
+	}
+
+	/// The byte position in the source text directly after the end of this span.
+	pub fn end(&self) -> usize {
+		self.start + self.length
+	}
+
+	/// The byte range of this span in the source text.
+	pub fn span(&self) -> Range<usize> {
+		self.start..self.end()
+	}
+
+
+
+	/* CHILD METHODS */
+
+	/// Find a specific child by filter.
+	pub fn find_child<T:Fn(&MatchSpan) -> bool>(&self, filter:T) -> Option<&MatchSpan<'a>> {
+		self._find_child(&filter)
+	}
+	fn _find_child(&self, filter:&dyn Fn(&MatchSpan) -> bool) -> Option<&MatchSpan<'a>> {
+		if filter(self) {
+			return Some(self);
+		}
+		self.sub_matches.iter().find_map(|sub_match| sub_match._find_child(filter))
+	}
+
+	/// Find a list of specific children by filter.
+	pub fn find_children<T:Fn(&MatchSpan) -> bool>(&self, filter:T) -> Vec<&MatchSpan<'a>> {
+		let mut list:Vec<&MatchSpan<'a>> = Vec::new();
+		self._find_children(&filter, &mut list);
+		list
+	}
+	fn _find_children<'b>(&'b self, filter:&dyn Fn(&MatchSpan) -> bool, list:&mut Vec<&'b MatchSpan<'a>>) {
+		if filter(self) {
+			list.push(self);
+		}
+		for sub_match in &self.sub_matches {
+			sub_match._find_children(filter, list);
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ MatchExpr, MatchHit, MatchSpan, MatcherRegistry, TextPredicate };
+
+
+
+	#[test]
+	fn test_match_span_borrows_contents() {
+		let text:String = "key=value".to_string();
+		let expression:MatchExpr = MatchExpr::named("key", MatchExpr::word()) + '=' + MatchExpr::named("value", MatchExpr::word());
+		let span:MatchSpan = expression.match_spans(&text).unwrap();
+		assert_eq!(span.contents(), "key=value");
+		assert_eq!(span.find_child(|child| child.type_name == "key").unwrap().contents(), "key");
+		assert_eq!(span.find_child(|child| child.type_name == "value").unwrap().span(), 4..9);
+		assert_eq!(span.find_children(|child| !child.type_name.is_empty()).len(), 2);
+	}
+
+	#[test]
+	fn test_match_span_to_owned() {
+		let expression:MatchExpr = MatchExpr::named("number", MatchExpr::unsigned_integer()) + ',' + MatchExpr::named("number", MatchExpr::unsigned_integer());
+		let owned:MatchHit = expression.match_text("12,345").unwrap();
+		assert_eq!(expression.match_spans("12,345").unwrap().to_owned(), owned);
+		assert_eq!(expression.match_spans("12,"), None);
+	}
+
+	#[test]
+	fn test_match_span_registry() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer()), ("word", MatchExpr::word())]);
+
+		let spans:Vec<MatchSpan> = set.find_spans("日1 本 22");
+		assert_eq!(spans.iter().map(|span| (span.type_name.as_str(), span.contents())).collect::<Vec<(&str, &str)>>(), vec![("number", "1"), ("number", "22")]);
+		assert_eq!(set.find_spans("日1 本 22").iter().map(MatchSpan::to_owned).collect::<Vec<MatchHit>>(), set.find_matches("日1 本 22").into_iter().map(|(_, result)| result).collect::<Vec<MatchHit>>());
+		assert_eq!(set.multi_match_spans("ab12cd").sub_matches.iter().map(MatchSpan::contents).collect::<Vec<&str>>(), vec!["ab", "12", "cd"]);
+	}
+
+	#[test]
+	fn test_match_span_registry_empty_matches() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matcher("o", MatchExpr::optional("x"));
+		let spans:Vec<MatchSpan> = set.find_spans("éax日");
+		assert_eq!(spans.iter().map(MatchSpan::span).collect::<Vec<_>>(), vec![0..0, 2..2, 3..4, 4..4]);
+		assert_eq!(spans.iter().map(MatchSpan::to_owned).collect::<Vec<MatchHit>>(), set.find_matches("éax日").into_iter().map(|(_, result)| result).collect::<Vec<MatchHit>>());
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_match_span_multi_match_empty_panics() {
+		MatcherRegistry::new().with_matcher("o", MatchExpr::optional("x")).multi_match_spans("éa");
+	}
+}
\ No newline at end of file
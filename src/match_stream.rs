@@ -0,0 +1,135 @@
+use std::{ collections::VecDeque, io::{ BufRead, Error, ErrorKind } };
+use crate::{ MatchContext, MatchHit, MatcherRegistry };
+
+
+
+const DEFAULT_LOOKAHEAD:usize = 1024;
+
+
+
+/// An iterator finding all matches of a registry in text pulled from a reader, like `MatcherRegistry::find_matches`. Yields the absolute start index and result of each match as soon as it is complete, and discards consumed input.
+/// A match is only considered complete when at least `lookahead` bytes of input follow both its end and the furthest position any predicate failed at, so matches straddling a refill are retried with more input.
+/// Predicates inside negations like `not_followed_by` and `not_preceded_by` do not register failures, so the text they inspect past a match is only covered by the lookahead.
+pub struct MatchStream<'a, T:BufRead> {
+	registry:&'a MatcherRegistry,
+	reader:T,
+	lookahead:usize,
+	buffer:String,
+	buffer_offset:usize,
+	cursor:usize,
+	incomplete_bytes:Vec<u8>,
+	pending_results:VecDeque<(usize, MatchHit)>,
+	end_of_input:bool
+}
+impl<'a, T:BufRead> MatchStream<'a, T> {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new stream matching the registry against the text in the reader.
+	pub fn new(registry:&'a MatcherRegistry, reader:T) -> MatchStream<'a, T> {
+		MatchStream {
+			registry,
+			reader,
+			lookahead: DEFAULT_LOOKAHEAD,
+			buffer: String::new(),
+			buffer_offset: 0,
+			cursor: 0,
+			incomplete_bytes: Vec::new(),
+			pending_results: VecDeque::new(),
+			end_of_input: false
+		}
+	}
+
+	/// Return self with the given amount of bytes required after a match before it is considered complete, at least 1. This is also the amount of consumed input kept for look-behind predicates, so predicates looking further back than one character need a larger lookahead.
+	pub fn with_lookahead(mut self, lookahead:usize) -> Self {
+		self.lookahead = lookahead.max(1);
+		self
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// The absolute byte position in the input up to which all matches have been found.
+	pub fn position(&self) -> usize {
+		self.buffer_offset + self.cursor
+	}
+
+	/// The amount of bytes of input currently held in memory.
+	pub fn buffered_len(&self) -> usize {
+		self.buffer.len()
+	}
+
+
+
+	/* SCANNING METHODS */
+
+	/// Find all matches in the buffer that can no longer change by reading more input.
+	fn scan_buffer(&mut self) {
+		let context:MatchContext = self.registry.context(&self.buffer);
+		while self.cursor < self.buffer.len() {
+			let match_result:Option<MatchHit> = self.registry.match_text_at(&context, self.cursor);
+			let inspected_end:usize = match_result.as_ref().map(MatchHit::end).unwrap_or(self.cursor).max(context.failure().position);
+			if !self.end_of_input && inspected_end + self.lookahead >= self.buffer.len() {
+				return;
+			}
+			match match_result {
+				Some(match_result) if match_result.length > 0 => {
+					self.cursor += match_result.length;
+					self.pending_results.push_back((self.buffer_offset + match_result.start, match_result.offset(self.buffer_offset)));
+				},
+				match_result => {
+					self.cursor += context.char_at(self.cursor).map(char::len_utf8).unwrap_or(1);
+					self.pending_results.extend(match_result.map(|match_result| (self.buffer_offset + match_result.start, match_result.offset(self.buffer_offset))));
+				}
+			}
+		}
+	}
+
+	/// Discard consumed input except for the look-behind margin, then read more input into the buffer.
+	fn refill(&mut self) -> Result<(), Error> {
+		let mut discarded_length:usize = self.cursor.saturating_sub(self.lookahead);
+		while !self.buffer.is_char_boundary(discarded_length) {
+			discarded_length -= 1;
+		}
+		self.buffer.drain(..discarded_length);
+		self.buffer_offset += discarded_length;
+		self.cursor -= discarded_length;
+
+		let bytes:&[u8] = self.reader.fill_buf()?;
+		let read_length:usize = bytes.len();
+		if read_length == 0 {
+			self.end_of_input = true;
+			return if self.incomplete_bytes.is_empty() { Ok(()) } else { Err(Error::new(ErrorKind::InvalidData, "stream did not end with valid UTF-8")) };
+		}
+		self.incomplete_bytes.extend_from_slice(bytes);
+		self.reader.consume(read_length);
+		let valid_length:usize = match std::str::from_utf8(&self.incomplete_bytes) {
+			Ok(_) => self.incomplete_bytes.len(),
+			Err(error) if error.error_len().is_none() => error.valid_up_to(),
+			Err(error) => return Err(Error::new(ErrorKind::InvalidData, error))
+		};
+		self.buffer += std::str::from_utf8(&self.incomplete_bytes[..valid_length]).unwrap();
+		self.incomplete_bytes.drain(..valid_length);
+		Ok(())
+	}
+}
+impl<T:BufRead> Iterator for MatchStream<'_, T> {
+	type Item = Result<(usize, MatchHit), Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(result) = self.pending_results.pop_front() {
+				return Some(Ok(result));
+			}
+			if self.end_of_input {
+				return None;
+			}
+			if let Err(error) = self.refill() {
+				self.end_of_input = true;
+				return Some(Err(error));
+			}
+			self.scan_buffer();
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+	use std::io::{ BufReader, ErrorKind };
+	use crate::{ MatchExpr, MatchHit, MatchStream, MatcherRegistry };
+
+
+
+	#[test]
+	fn test_match_stream_equals_find_matches() {
+		let set:MatcherRegistry = MatcherRegistry::from_grammar("number = [0-9]+\nword = [a-zé]+").unwrap();
+		let text:String = (0..300).map(|index| format!("entry{index} é{}\n", index * 7919)).collect();
+		let expected:Vec<(usize, MatchHit)> = set.find_matches(&text);
+		for capacity in [1, 2, 3, 7, 64, 8192] {
+			let results:Vec<(usize, MatchHit)> = set.stream_matches(BufReader::with_capacity(capacity, text.as_bytes())).with_lookahead(4).map(Result::unwrap).collect();
+			assert_eq!(results, expected);
+		}
+	}
+
+	#[test]
+	fn test_match_stream_straddling_refills() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("block", MatchExpr::new("{") + MatchExpr::optional_repeat_max(!MatchExpr::new("}")) + "}"), ("keyword", MatchExpr::new("begin"))]);
+		let text:String = "x {a long block\nspanning many refills} begin beg {}".to_string();
+		let results:Vec<(usize, MatchHit)> = set.stream_matches(BufReader::with_capacity(4, text.as_bytes())).with_lookahead(8).map(Result::unwrap).collect();
+		assert_eq!(results, set.find_matches(&text));
+		assert_eq!(results.iter().map(|(_, result)| result.contents.as_str()).collect::<Vec<&str>>(), vec!["{a long block\nspanning many refills}", "begin", "{}"]);
+	}
+
+	#[test]
+	fn test_match_stream_discards_consumed_input() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer())]);
+		let text:String = "12 ".repeat(10_000);
+		let mut stream:MatchStream<BufReader<&[u8]>> = set.stream_matches(BufReader::with_capacity(16, text.as_bytes())).with_lookahead(8);
+		let mut count:usize = 0;
+		while let Some(result) = stream.next() {
+			assert_eq!(result.unwrap().0, count * 3);
+			assert!(stream.buffered_len() < 64);
+			count += 1;
+		}
+		assert_eq!(count, 10_000);
+		assert_eq!(stream.position(), text.len());
+	}
+
+	#[test]
+	fn test_match_stream_invalid_utf8() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer())]);
+		let bytes:&[u8] = b"12 \xFF 34";
+		let results:Vec<_> = set.stream_matches(BufReader::with_capacity(2, bytes)).with_lookahead(1).collect();
+		assert_eq!(results.last().unwrap().as_ref().unwrap_err().kind(), ErrorKind::InvalidData);
+
+		let truncated:&[u8] = "12 日".as_bytes();
+		let results:Vec<_> = set.stream_matches(&truncated[..truncated.len() - 1]).collect();
+		assert_eq!(results.last().unwrap().as_ref().unwrap_err().kind(), ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn test_match_stream_minimum_lookahead() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("line", MatchExpr::bol() + 'a'), ("pair", MatchExpr::optional("ab"))]);
+		let text:&str = "xab\naab\nb";
+		for capacity in [1, 2, 3] {
+			let results:Vec<(usize, MatchHit)> = set.stream_matches(BufReader::with_capacity(capacity, text.as_bytes())).with_lookahead(0).map(Result::unwrap).collect();
+			assert_eq!(results, set.find_matches(text));
+		}
+	}
+
+	#[test]
+	fn test_match_stream_regex_straddling_refills() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matcher("block", MatchExpr::regex(r"\{[^}]*\}"));
+		let text:&str = "x {a long block spanning many refills} y {}";
+		let results:Vec<(usize, MatchHit)> = set.stream_matches(BufReader::with_capacity(4, text.as_bytes())).with_lookahead(8).map(Result::unwrap).collect();
+		assert_eq!(results.len(), 2);
+		assert_eq!(results, set.find_matches(text));
+	}
+}
\ No newline at end of file
@@ -1,10 +1,20 @@
-use crate::{ MatchHit, MatchExpr, TextPredicate };
-use std::ops::Index;
+use crate::{ grammar::compile_grammar, replacer::replace_matches, CaseMode, ChunkBoundary, Expectation, FindIter, GrammarError, MatchContext, MatchFailure, MatchHit, MatchSpan, MatchStream, MatchExpr, Replacer, TextPredicate, Tokens };
+use std::{ io::BufRead, ops::{ Index, Range }, thread };
 
 
 
 pub struct MatcherRegistry {
-	matchers:Vec<(String, MatchExpr)>
+	matchers:Vec<(String, MatchExpr)>,
+	rules:Vec<(String, MatchExpr)>,
+	hidden_rules:Vec<String>,
+	memoize:bool,
+	longest_match:bool,
+	case_mode:CaseMode
+}
+impl Default for MatcherRegistry {
+	fn default() -> Self {
+		MatcherRegistry::new()
+	}
 }
 impl MatcherRegistry {
 
@@ -13,10 +23,22 @@ impl MatcherRegistry {
 	/// Create a new registry.
 	pub fn new() -> MatcherRegistry {
 		MatcherRegistry {
-			matchers: Vec::new()
+			matchers: Vec::new(),
+			rules: Vec::new(),
+			hidden_rules: Vec::new(),
+			memoize: false,
+			longest_match: false,
+			case_mode: CaseMode::Sensitive
 		}
 	}
 
+	/// Create a registry from a grammar written as text, like `pair = key:[a-z]+ "=" value:(!"\n")*`. Each rule `name = expression` becomes a matcher, hidden rules starting with an underscore become rules that can only be referenced and whose results keep the name of whatever they matched.
+	/// Expressions are sequences of elements, with alternatives separated by `|`. Elements are string literals (`"abc"`, or `"abc"i` ignoring case), character classes (`[a-z_]`, `[^\n]`), `.` for any character, rule references and parenthesized expressions, nested up to 250 deep.
+	/// Elements can be labeled as `label:element`, prefixed with `!` to match one character the element does not start with or `&` to require the element without consuming it, and followed by `*`, `+`, `?` or a count range like `{2,4}`. Comments start with `#`.
+	pub fn from_grammar(source:&str) -> Result<MatcherRegistry, GrammarError> {
+		compile_grammar(source)
+	}
+
 	/// Return self with an additional matcher to the set.
 	pub fn with_matcher<T:TextPredicate + 'static>(mut self, name:&str, matcher_source:T) -> Self {
 		self.matchers.push((name.to_string(), MatchExpr::new(matcher_source)));
@@ -31,46 +53,154 @@ impl MatcherRegistry {
 		self
 	}
 
+	/// Return self with an additional rule. Rules are not matched by themselves, but can be referenced by matchers and other rules using `MatchExpr::reference`.
+	pub fn with_rule<T:TextPredicate + 'static>(mut self, name:&str, rule_source:T) -> Self {
+		self.rules.push((name.to_string(), MatchExpr::new(rule_source)));
+		self
+	}
+
+	/// Return self with an additional hidden rule. Results of hidden rules keep the name of whatever the rule matched, while failures are still reported as the rule.
+	pub(crate) fn with_hidden_rule<T:TextPredicate + 'static>(mut self, name:&str, rule_source:T) -> Self {
+		self.hidden_rules.push(name.to_string());
+		self.with_rule(name, rule_source)
+	}
+
+	/// Return self with multiple additional rules.
+	pub fn with_rules<T:TextPredicate + 'static>(mut self, sources:Vec<(&str, T)>) -> Self {
+		for (name, rule_source) in sources {
+			self = self.with_rule(name, rule_source)
+		}
+		self
+	}
+
+
+
+	/// Return self with memoization enabled for every match started from this registry. See `MatchContext::with_memoization`.
+	pub fn with_memoization(mut self) -> Self {
+		self.memoize = true;
+		self
+	}
+
+
+
+	/// Return self trying all matchers at every position and using the longest match, instead of the first matcher that matches. When multiple matchers match the same length, the one registered first wins.
+	pub fn with_longest_match(mut self) -> Self {
+		self.longest_match = true;
+		self
+	}
+
+
+
+	/// Return self matching all plain string and character literals in the given case mode, like `CaseMode::IgnoreAsciiCase` to treat every keyword case-insensitively.
+	pub fn with_case_mode(mut self, case_mode:CaseMode) -> Self {
+		self.case_mode = case_mode;
+		self
+	}
+
 
 
 	/* USAGE METHODS */
 
-	/// Get a match-expression by name.
+	/// Get a match-expression by name. Searches both matchers and rules.
 	pub fn matcher_by_name(&self, name:&str) -> Option<&(String, MatchExpr)> {
-		self.matchers.iter().find(|(matcher_name, _)| matcher_name == name)
+		self.matchers.iter().chain(&self.rules).find(|(matcher_name, _)| matcher_name == name)
+	}
+
+	/// Check if the rule with the given name is hidden.
+	pub(crate) fn is_hidden_rule(&self, name:&str) -> bool {
+		self.hidden_rules.iter().any(|hidden_name| hidden_name == name)
+	}
+
+	/// Create a context to match the given text in, in which references to the rules of this registry can be resolved.
+	pub fn context<'a>(&'a self, text:&'a str) -> MatchContext<'a> {
+		let context:MatchContext = MatchContext::new(text).with_registry(self).with_case_mode(self.case_mode);
+		if self.memoize { context.with_memoization() } else { context }
 	}
 
 	/// Try to match any of the match-expressions to the given text. Returns MatchResult in case of a match.
 	pub fn match_text(&self, text:&str) -> Option<MatchHit> {
-		for (matcher_name, matcher) in &self.matchers {
-			if let Some(mut match_result) = matcher.match_text(text) {
-				match_result.type_name = matcher_name.to_string();
-				return Some(match_result);
-			}
-		}
-		None
+		self.match_text_at(&self.context(text), 0)
+	}
+
+	/// Try to match any of the match-expressions to the given text. Returns MatchResult in case of a match, or a MatchFailure describing the furthest position reached and what was expected there.
+	pub fn try_match_text(&self, text:&str) -> Result<MatchHit, MatchFailure> {
+		let context:MatchContext = self.context(text);
+		self.match_text_at(&context, 0).ok_or_else(|| context.failure())
+	}
+
+	/// Try to match any of the match-expressions to the text of the context at the given byte cursor. Returns MatchResult in case of a match.
+	pub fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		let mut results = self.matchers.iter().filter_map(|(matcher_name, matcher)| context.match_rule(matcher.id(), matcher_name, false, matcher, cursor));
+		if self.longest_match { MatchExpr::longest(results) } else { results.next() }
 	}
 
 	/// Keep matching as much of the given text as possible. Returns a list of MatchResults.
+	/// Panics when a match-expression matches with length 0, as matching would repeat indefinitely.
 	pub fn multi_match_text(&self, text:&str) -> MatchHit {
+		let (length, results) = self.multi_match_in(&self.context(text));
+		MatchHit::new_with_sub_matches(length, text, results)
+	}
+
+	/// Keep matching the given text until all of it is matched. Returns the same tree as `multi_match_text`, or a MatchFailure at the first position none of the match-expressions could match.
+	pub fn parse_complete(&self, text:&str) -> Result<MatchHit, MatchFailure> {
+		let context:MatchContext = self.context(text);
 		let mut cursor:usize = 0;
-		let mut remaining_text:&str = text;
 		let mut results:Vec<MatchHit> = Vec::new();
-		while let Some(match_result) = self.match_text(remaining_text) {
-			results.push(match_result.clone());
+		while cursor < text.len() {
+			match self.match_text_at(&context, cursor) {
+				Some(match_result) if match_result.length > 0 => {
+					cursor += match_result.length;
+					results.push(match_result);
+				},
+				_ => {
+					context.expect(cursor, Expectation::EndOfInput);
+					return Err(context.failure());
+				}
+			}
+		}
+		Ok(MatchHit::new_with_sub_matches(cursor, text, results))
+	}
+
+	/// Keep matching as much of the given text as possible, recording only the spans of the results. See `multi_match_text`.
+	/// Panics when a match-expression matches with length 0, as matching would repeat indefinitely.
+	pub fn multi_match_spans<'a>(&self, text:&'a str) -> MatchSpan<'a> {
+		let context:MatchContext = self.context(text).with_span_only();
+		let (length, results) = self.multi_match_in(&context);
+		MatchSpan::from_hit(MatchHit::new_with_sub_matches(length, context.contents_source(), results), text)
+	}
+
+	/// Keep matching the text of the context from its start. Returns the matched length and the results.
+	fn multi_match_in(&self, context:&MatchContext) -> (usize, Vec<MatchHit>) {
+		let mut cursor:usize = 0;
+		let mut results:Vec<MatchHit> = Vec::new();
+		while let Some(match_result) = self.match_text_at(context, cursor) {
 			cursor += match_result.length;
 			if match_result.length == 0 {
 				panic!("Matched a {} with length 0 in multi_match_text. As this will increase the cursor, this would repeat indefinitely. Aborting rest of parsing.", match_result.type_name);
 			}
-			remaining_text = if text.len() > cursor { &text[cursor..] } else { "" };
+			results.push(match_result);
 		}
-		MatchHit::new_with_sub_matches(cursor, text, results)
+		(cursor, results)
+	}
+
+	/// Find all possible matches anywhere in the given text, recording only the spans of the results. See `find_matches`.
+	pub fn find_spans<'a>(&self, text:&'a str) -> Vec<MatchSpan<'a>> {
+		let context:MatchContext = self.context(text).with_span_only();
+		let mut results:Vec<MatchSpan<'a>> = Vec::new();
+		let mut cursor:usize = 0;
+		while cursor < text.len() {
+			let (match_result, next_cursor) = self.find_step(&context, cursor);
+			results.extend(match_result.map(|match_result| MatchSpan::from_hit(match_result, text)));
+			cursor = next_cursor;
+		}
+		results
 	}
 
 	/// Find any match anywhere in the given text. Returns the start index where it was found and MatchResult in case of a match.
 	pub fn find_match(&self, text:&str) -> Option<(usize, MatchHit)> {
-		for cursor in 0..text.len() {
-			if let Some(match_result) = self.match_text(&text[cursor..]) {
+		let context:MatchContext = self.context(text);
+		for (cursor, _) in text.char_indices() {
+			if let Some(match_result) = self.match_text_at(&context, cursor) {
 				return Some((cursor, match_result));
 			}
 		}
@@ -79,19 +209,97 @@ impl MatcherRegistry {
 
 	/// Find all possible matches anywhere in the given text. Returns the start index where it was found and MatchResult in case of a match.
 	pub fn find_matches(&self, text:&str) -> Vec<(usize, MatchHit)> {
+		self.find_iter(text).collect()
+	}
+
+	/// Create a lazy iterator over all matches anywhere in the given text. Yields the same results as `find_matches`.
+	pub fn find_iter<'a>(&'a self, text:&'a str) -> FindIter<'a> {
+		FindIter::new(self, text, false)
+	}
+
+	/// Create a lazy iterator over all matches anywhere in the given text, including matches starting inside earlier matches. After each match the search continues one character further instead of after the match.
+	pub fn find_overlapping_iter<'a>(&'a self, text:&'a str) -> FindIter<'a> {
+		FindIter::new(self, text, true)
+	}
+
+	/// Create a lazy iterator over consecutive matches from the start of the given text. Yields the same results as the sub-matches of `multi_match_text`.
+	pub fn tokens<'a>(&'a self, text:&'a str) -> Tokens<'a> {
+		Tokens::new(self, text)
+	}
+
+	/// Find all possible matches anywhere in the given text, scanning chunks split at the given boundary on up to `thread_count` threads. Returns the same results as `find_matches`, including matches crossing chunk boundaries.
+	pub fn find_matches_parallel(&self, text:&str, boundary:&ChunkBoundary, thread_count:usize) -> Vec<(usize, MatchHit)> {
+		let chunks:Vec<Range<usize>> = boundary.split_chunks(text, thread_count);
+		let chunk_results:Vec<(Vec<(usize, MatchHit)>, usize)> = thread::scope(|scope| {
+			let handles:Vec<_> = chunks.iter().map(|chunk| scope.spawn(|| {
+				let mut chunk_results:Vec<(usize, MatchHit)> = Vec::new();
+				let chunk_end:usize = self.find_matches_in(&self.context(text), chunk.clone(), &mut chunk_results);
+				(chunk_results, chunk_end)
+			})).collect();
+			handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+		});
+
+		// Merge the chunks in order. When a match crosses into the next chunk, scan on from its end until reaching a position the thread of that chunk also scanned, after which both find the same matches.
+		let context:MatchContext = self.context(text);
 		let mut results:Vec<(usize, MatchHit)> = Vec::new();
-		let text_end:usize = text.len();
 		let mut cursor:usize = 0;
-		while cursor < text_end {
-			if let Some(match_result) = self.match_text(&text[cursor..]) {
-				results.push((cursor, match_result.clone()));
-				cursor += match_result.length;
-			} else {
-				cursor += 1;
+		for (chunk, (chunk_results, chunk_end)) in chunks.into_iter().zip(chunk_results) {
+			let mut chunk_results = chunk_results.into_iter().peekable();
+			while cursor > chunk.start && cursor < chunk.end {
+				match chunk_results.peek() {
+					Some((start, result)) if *start < cursor && result.end() <= cursor => {
+						chunk_results.next();
+					},
+					Some((start, _)) if *start < cursor => {
+						cursor = self.find_matches_in(&context, cursor..cursor + 1, &mut results);
+					},
+					_ => break
+				}
+			}
+			if cursor < chunk.end {
+				results.extend(chunk_results);
+				cursor = chunk_end;
 			}
 		}
 		results
 	}
+
+	/// Create a copy of the text with every match `find_matches` finds replaced. See `Replacer` for templates and closures.
+	pub fn replace_all<T:Replacer>(&self, text:&str, replacer:T) -> String {
+		replace_matches(text, self.find_iter(text), usize::MAX, replacer)
+	}
+
+	/// Create a copy of the text with the first `limit` matches `find_matches` finds replaced. See `Replacer` for templates and closures.
+	pub fn replace_n<T:Replacer>(&self, text:&str, limit:usize, replacer:T) -> String {
+		replace_matches(text, self.find_iter(text), limit, replacer)
+	}
+
+	/// Create an iterator finding all matches in text pulled from the given reader, without loading all of it into memory. Yields the same results as `find_matches` would for the full text.
+	pub fn stream_matches<T:BufRead>(&self, reader:T) -> MatchStream<'_, T> {
+		MatchStream::new(self, reader)
+	}
+
+	/// Find all matches starting in the given range of the text, adding them to the results. Returns the position the scan stopped at, which is past the range when the last match crosses its end.
+	fn find_matches_in(&self, context:&MatchContext, range:Range<usize>, results:&mut Vec<(usize, MatchHit)>) -> usize {
+		let mut cursor:usize = range.start;
+		while cursor < range.end {
+			let (match_result, next_cursor) = self.find_step(context, cursor);
+			results.extend(match_result.map(|match_result| (match_result.start, match_result)));
+			cursor = next_cursor;
+		}
+		cursor
+	}
+
+	/// Try to match at the given cursor while searching for matches. Returns the result and the cursor to continue searching from, which is after the result unless it is empty.
+	pub(crate) fn find_step(&self, context:&MatchContext, cursor:usize) -> (Option<MatchHit>, usize) {
+		match self.match_text_at(context, cursor) {
+			Some(match_result) if match_result.length > 0 => {
+				let next_cursor:usize = cursor + match_result.length;
+				(Some(match_result), next_cursor)
+			},
+			match_result => (match_result, cursor + context.char_at(cursor).map(char::len_utf8).unwrap_or(1))
+		}
+	}
 }
 impl Index<&str> for MatcherRegistry {
 	type Output = (String, MatchExpr);
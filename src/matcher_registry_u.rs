@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
-	use crate::{ MatchHit, MatcherRegistry, TextPredicate };
+	use std::{ sync::OnceLock, thread };
+	use crate::{ CaseMode, ChunkBoundary, MatchExpr, MatchHit, MatcherRegistry, TextPredicate };
 
 
 
@@ -20,9 +21,9 @@ mod tests {
 	fn test_matcher_set_match_global() {
 		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("a", 'a'), ("b", 'b')]);
 
-		assert_eq!(set.match_text("abax").unwrap(), MatchHit { type_name: "a".to_string(), length: 1, contents: "a".to_string(), sub_matches: Vec::new() });
-		assert_eq!(set.match_text("bax").unwrap(), MatchHit { type_name: "b".to_string(), length: 1, contents: "b".to_string(), sub_matches: Vec::new() });
-		assert_eq!(set.match_text("ax").unwrap(), MatchHit { type_name: "a".to_string(), length: 1, contents: "a".to_string(), sub_matches: Vec::new() });
+		assert_eq!(set.match_text("abax").unwrap(), MatchHit { type_name: "a".to_string(), start: 0, length: 1, contents: "a".to_string(), sub_matches: Vec::new() });
+		assert_eq!(set.match_text("bax").unwrap(), MatchHit { type_name: "b".to_string(), start: 0, length: 1, contents: "b".to_string(), sub_matches: Vec::new() });
+		assert_eq!(set.match_text("ax").unwrap(), MatchHit { type_name: "a".to_string(), start: 0, length: 1, contents: "a".to_string(), sub_matches: Vec::new() });
 		assert_eq!(set.match_text("x"), None);
 	}
 
@@ -33,10 +34,10 @@ mod tests {
 		assert_eq!(
 			set.multi_match_text("abaxa").sub_matches,
 			vec![
-				MatchHit { type_name: "a".to_string(), length: 1, contents: "a".to_string(), sub_matches: Vec::new() },
-				MatchHit { type_name: "b".to_string(), length: 1, contents: "b".to_string(), sub_matches: Vec::new() },
-				MatchHit { type_name: "a".to_string(), length: 1, contents: "a".to_string(), sub_matches: Vec::new() },
-				MatchHit { type_name: "x".to_string(), length: 2, contents: "xa".to_string(), sub_matches: Vec::new() }
+				MatchHit { type_name: "a".to_string(), start: 0, length: 1, contents: "a".to_string(), sub_matches: Vec::new() },
+				MatchHit { type_name: "b".to_string(), start: 1, length: 1, contents: "b".to_string(), sub_matches: Vec::new() },
+				MatchHit { type_name: "a".to_string(), start: 2, length: 1, contents: "a".to_string(), sub_matches: Vec::new() },
+				MatchHit { type_name: "x".to_string(), start: 3, length: 2, contents: "xa".to_string(), sub_matches: Vec::new() }
 			]
 		);
 	}
@@ -47,7 +48,7 @@ mod tests {
 
 		assert_eq!(
 			set.find_match("ooabaxa").unwrap(),
-			(2, MatchHit { type_name: "a".to_string(), length: 1, contents: "a".to_string(), sub_matches: Vec::new() })
+			(2, MatchHit { type_name: "a".to_string(), start: 2, length: 1, contents: "a".to_string(), sub_matches: Vec::new() })
 		);
 	}
 
@@ -58,10 +59,100 @@ mod tests {
 		assert_eq!(
 			set.find_matches("ooabaxa"),
 			vec![
-				(2, MatchHit { type_name: "a".to_string(), length: 1, contents: "a".to_string(), sub_matches: Vec::new() }),
-				(4, MatchHit { type_name: "a".to_string(), length: 1, contents: "a".to_string(), sub_matches: Vec::new() }),
-				(6, MatchHit { type_name: "a".to_string(), length: 1, contents: "a".to_string(), sub_matches: Vec::new() }),
+				(2, MatchHit { type_name: "a".to_string(), start: 2, length: 1, contents: "a".to_string(), sub_matches: Vec::new() }),
+				(4, MatchHit { type_name: "a".to_string(), start: 4, length: 1, contents: "a".to_string(), sub_matches: Vec::new() }),
+				(6, MatchHit { type_name: "a".to_string(), start: 6, length: 1, contents: "a".to_string(), sub_matches: Vec::new() }),
 			]
 		);
 	}
+
+	#[test]
+	fn test_matcher_set_find_matches_spans() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("pair", MatchExpr::named("key", MatchExpr::word()) + '=' + MatchExpr::named("value", MatchExpr::unsigned_integer()))]);
+
+		let results:Vec<(usize, MatchHit)> = set.find_matches("..a=1 bc=23");
+		assert_eq!(results.iter().map(|(offset, result)| (*offset, result.span())).collect::<Vec<_>>(), vec![(2, 2..5), (6, 6..11)]);
+		assert_eq!(results[1].1.find_child(|child| child.type_name == "key").unwrap().span(), 6..8);
+		assert_eq!(results[1].1.find_child(|child| child.type_name == "value").unwrap().span(), 9..11);
+	}
+
+	#[test]
+	fn test_matcher_set_find_multi_byte() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer())]);
+
+		assert_eq!(set.find_match("価格は😀12円").unwrap().0, 13);
+		assert_eq!(set.find_matches("日1本22語").iter().map(|(offset, result)| (*offset, result.contents.as_str())).collect::<Vec<(usize, &str)>>(), vec![(3, "1"), (7, "22")]);
+	}
+
+	#[test]
+	fn test_matcher_set_longest_match() {
+		let matchers = vec![("keyword", MatchExpr::new("if") | "else"), ("identifier", MatchExpr::identifier()), ("number", MatchExpr::unsigned_integer())];
+		let first_match_set:MatcherRegistry = MatcherRegistry::new().with_matchers(matchers.clone());
+		let longest_match_set:MatcherRegistry = MatcherRegistry::new().with_matchers(matchers).with_longest_match();
+
+		assert_eq!(first_match_set.match_text("iffy").unwrap().contents, "if");
+		assert_eq!(longest_match_set.match_text("iffy").unwrap().type_name, "identifier");
+		assert_eq!(longest_match_set.match_text("iffy").unwrap().contents, "iffy");
+
+		// Matches of the same length go to the matcher registered first.
+		assert_eq!(longest_match_set.match_text("if x").unwrap().type_name, "keyword");
+		assert_eq!(longest_match_set.find_matches("else elsewhere 12").iter().map(|(_, result)| result.type_name.as_str()).collect::<Vec<&str>>(), vec!["keyword", "identifier", "number"]);
+	}
+
+	#[test]
+	fn test_matcher_set_case_mode() {
+		let matchers = vec![("boolean", MatchExpr::new("true") | "false"), ("header", MatchExpr::new("Content-Type") + ':')];
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(matchers.clone()).with_case_mode(CaseMode::IgnoreAsciiCase);
+		assert_eq!(set.match_text("TRUE").unwrap().type_name, "boolean");
+		assert_eq!(set.match_text("content-type:").unwrap().type_name, "header");
+		assert_eq!(set.find_matches("x=FALSE, y=True").iter().map(|(_, result)| result.contents.as_str()).collect::<Vec<&str>>(), vec!["FALSE", "True"]);
+		assert_eq!(MatcherRegistry::new().with_matchers(matchers).match_text("TRUE"), None);
+	}
+
+	#[test]
+	fn test_matcher_set_shared_between_threads() {
+		static REGISTRY:OnceLock<MatcherRegistry> = OnceLock::new();
+		let registry:&MatcherRegistry = REGISTRY.get_or_init(|| MatcherRegistry::from_grammar("number = [0-9]+\nword = [a-z]+").unwrap());
+
+		let counts:Vec<usize> = thread::scope(|scope| {
+			let handles = ["a 1 b 2", "33 cc", "x"].map(|text| scope.spawn(move || registry.find_matches(text).len()));
+			handles.map(|handle| handle.join().unwrap()).to_vec()
+		});
+		assert_eq!(counts, vec![4, 2, 1]);
+	}
+
+	#[test]
+	fn test_matcher_set_find_matches_parallel() {
+		let set:MatcherRegistry = MatcherRegistry::from_grammar("number = [0-9]+\nword = [a-zé]+").unwrap();
+		let text:String = (0..200).map(|index| format!("entry{index} é{}\n", index * 7)).collect();
+		let expected:Vec<(usize, MatchHit)> = set.find_matches(&text);
+		for thread_count in [1, 2, 3, 8, 1000] {
+			assert_eq!(set.find_matches_parallel(&text, &ChunkBoundary::LineBreak, thread_count), expected);
+		}
+	}
+
+	#[test]
+	fn test_matcher_set_find_matches_parallel_crossing_chunks() {
+		// Matches spanning lines cross the chunk boundaries, so following chunks need to resynchronize.
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("block", MatchExpr::new("{") + MatchExpr::optional_repeat_max(!MatchExpr::new("}")) + "}"), ("word", MatchExpr::word())]);
+		let text:String = (0..50).map(|index| if index % 3 == 0 { format!("a {{\nb\nc}} d{index}\n") } else { format!("{{e}}f{index}\n") }).collect();
+		let expected:Vec<(usize, MatchHit)> = set.find_matches(&text);
+		assert!(expected.iter().any(|(_, result)| result.contents.contains('\n')));
+		for thread_count in 1..20 {
+			assert_eq!(set.find_matches_parallel(&text, &ChunkBoundary::LineBreak, thread_count), expected);
+			assert_eq!(set.find_matches_parallel(&text, &ChunkBoundary::custom(|_, _| true), thread_count), expected);
+		}
+	}
+
+	#[test]
+	fn test_matcher_set_find_matches_parallel_empty_matches() {
+		// An empty match right where a crossing match ends must survive the resynchronization.
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("block", MatchExpr::new("ab\ncd")), ("empty", MatchExpr::optional("z"))]);
+		let text:&str = "ab\ncd\nef\ngh\n";
+		let expected:Vec<(usize, MatchHit)> = set.find_matches(text);
+		assert!(expected.iter().any(|(start, result)| *start == 5 && result.length == 0));
+		for thread_count in 1..8 {
+			assert_eq!(set.find_matches_parallel(text, &ChunkBoundary::LineBreak, thread_count), expected);
+		}
+	}
 }
\ No newline at end of file
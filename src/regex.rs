@@ -0,0 +1,647 @@
+use std::{ error::Error, iter::Peekable, fmt::{ Display, Formatter } };
+use crate::{ unicode_tables, Expectation, MatchContext, MatchHit, TextPredicate };
+
+
+
+/// The highest count a counted quantifier like `{n,m}` may use.
+const MAX_REPETITION_COUNT:usize = 1000;
+
+/// The highest amount of instructions a pattern may compile to. Counted quantifiers repeat the instructions of what they repeat, so nested quantifiers multiply in size.
+const MAX_PROGRAM_SIZE:usize = 100_000;
+
+/// The deepest groups may be nested, as parsing and compiling recurse into each group.
+const MAX_GROUP_DEPTH:usize = 250;
+
+
+
+/// An error in the pattern of a regex.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RegexError {
+	pub position:usize,
+	pub message:String
+}
+impl Display for RegexError {
+	fn fmt(&self, f:&mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} at byte {} of the pattern", self.message, self.position)
+	}
+}
+impl Error for RegexError {}
+
+
+
+/// A regular expression, matched anchored at the cursor. Named capture groups like `(?<name>...)` or `(?P<name>...)` become named sub-matches of the result.
+/// Supports literals, `.`, character classes, `\d \w \s` and their negations, `^ $ \b \B`, groups, alternation and greedy or lazy quantifiers `* + ? {n} {n,} {n,m}` with counts up to 1000. Groups nest up to 250 deep. Alternatives are preferred from left to right.
+#[derive(Clone, Debug)]
+pub struct Regex {
+	pattern:String,
+	program:Vec<Instruction>,
+	group_names:Vec<Option<String>>
+}
+impl Regex {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Compile a new regex from a pattern.
+	pub fn new(pattern:&str) -> Result<Regex, RegexError> {
+		let mut parser:Parser = Parser { chars: pattern.char_indices().collect(), index: 0, pattern_length: pattern.len(), group_names: vec![None], group_depth: 0 };
+		let node:Node = parser.parse_alternation()?;
+		if parser.index < parser.chars.len() {
+			return Err(parser.error("unmatched ')'"));
+		}
+
+		if program_size(&node) > MAX_PROGRAM_SIZE {
+			return Err(RegexError { position: 0, message: format!("pattern compiles to more than {MAX_PROGRAM_SIZE} instructions") });
+		}
+		let mut program:Vec<Instruction> = vec![Instruction::Save(0)];
+		compile(&node, &mut program);
+		program.push(Instruction::Save(1));
+		program.push(Instruction::Match);
+		Ok(Regex {
+			pattern: pattern.to_string(),
+			program,
+			group_names: parser.group_names
+		})
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Get the pattern the regex was compiled from.
+	pub fn pattern(&self) -> &str {
+		&self.pattern
+	}
+
+	/// Run the program anchored at the cursor. Returns the capture slots of the preferred match and the furthest position any thread inspected.
+	fn run(&self, text:&str, cursor:usize) -> (Option<Vec<Option<usize>>>, usize) {
+		if !text.is_char_boundary(cursor) {
+			return (None, cursor);
+		}
+		let slot_count:usize = self.group_names.len() * 2;
+		let mut threads:Vec<Thread> = Vec::new();
+		let mut visited:Vec<bool> = vec![false; self.program.len()];
+		self.add_thread(&mut threads, &mut visited, 0, vec![None; slot_count], text, cursor);
+
+		let mut matched:Option<Vec<Option<usize>>> = None;
+		let mut position:usize = cursor;
+		while !threads.is_empty() {
+			let current_char:Option<char> = text[position..].chars().next();
+			let next_position:usize = position + current_char.map(char::len_utf8).unwrap_or(0);
+			let mut next_threads:Vec<Thread> = Vec::new();
+			visited.fill(false);
+			for thread in threads {
+				let accepted:bool = match (&self.program[thread.program_counter], current_char) {
+					(Instruction::Match, _) => {
+						matched = Some(thread.slots);
+
+						// Threads after this one have a lower priority than this match.
+						break;
+					},
+					(Instruction::Char(expected), Some(char)) => *expected == char,
+					(Instruction::AnyExceptNewline, Some(char)) => char != '\n',
+					(Instruction::Class(class), Some(char)) => class.contains(char),
+					_ => false
+				};
+				if accepted {
+					self.add_thread(&mut next_threads, &mut visited, thread.program_counter + 1, thread.slots, text, next_position);
+				}
+			}
+			if current_char.is_none() || next_threads.is_empty() {
+				break;
+			}
+			threads = next_threads;
+			position = next_position;
+		}
+		(matched, position)
+	}
+
+	/// Add a thread at the given program counter, following all instructions that do not consume a character. Uses a work stack instead of recursion, as long chains of optional items would overflow the call stack.
+	fn add_thread(&self, threads:&mut Vec<Thread>, visited:&mut [bool], program_counter:usize, slots:Vec<Option<usize>>, text:&str, position:usize) {
+		let mut pending:Vec<(usize, Vec<Option<usize>>)> = vec![(program_counter, slots)];
+		while let Some((program_counter, mut slots)) = pending.pop() {
+			if visited[program_counter] {
+				continue;
+			}
+			visited[program_counter] = true;
+			match &self.program[program_counter] {
+				Instruction::Jump(target) => pending.push((*target, slots)),
+				Instruction::Split(preferred, other) => {
+
+					// The preferred branch is pushed last so it is followed first.
+					pending.push((*other, slots.clone()));
+					pending.push((*preferred, slots));
+				},
+				Instruction::Save(slot) => {
+					slots[*slot] = Some(position);
+					pending.push((program_counter + 1, slots));
+				},
+				Instruction::Assert(assertion) => {
+					if assertion.holds(text, position) {
+						pending.push((program_counter + 1, slots));
+					}
+				},
+				_ => threads.push(Thread { program_counter, slots })
+			}
+		}
+	}
+
+	/// Create the named sub-matches from the capture slots, nesting groups that lie within other groups.
+	fn named_sub_matches(&self, slots:&[Option<usize>], text:&str) -> Vec<MatchHit> {
+		let mut groups:Vec<(usize, usize, &str)> = self.group_names.iter().enumerate().filter_map(|(group_index, name)| {
+			match (name, slots[group_index * 2], slots[group_index * 2 + 1]) {
+				(Some(name), Some(start), Some(end)) => Some((start, end, name.as_str())),
+				_ => None
+			}
+		}).collect();
+		groups.sort_by_key(|(start, end, _)| (*start, usize::MAX - end));
+		nest_groups(&mut groups.into_iter().peekable(), usize::MAX, text)
+	}
+}
+impl TextPredicate for Regex {
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		let (matched, furthest_position) = self.run(context.text(), cursor);
+		match matched {
+			Some(slots) => {
+				let end:usize = slots[1].unwrap_or(cursor);
+
+				// Threads preferred over the match may have read further, so more text there could change the result.
+				if furthest_position > end {
+					context.expect(furthest_position, Expectation::CharClass(format!("/{}/", self.pattern)));
+				}
+				Some(MatchHit::new_at_with_list(cursor, end - cursor, context.contents_source(), self.named_sub_matches(&slots, context.contents_source())))
+			},
+			None => {
+				context.expect(furthest_position, Expectation::CharClass(format!("/{}/", self.pattern)));
+				None
+			}
+		}
+	}
+}
+
+
+
+/* NESTING HELPERS */
+
+/// Create hits for all groups ending before the given end, nesting following groups that lie within each group.
+fn nest_groups<'a, T:Iterator<Item = (usize, usize, &'a str)>>(groups:&mut Peekable<T>, parent_end:usize, text:&str) -> Vec<MatchHit> {
+	let mut hits:Vec<MatchHit> = Vec::new();
+	while let Some((_, end, _)) = groups.peek() && *end <= parent_end {
+		let (start, end, name) = groups.next().unwrap();
+		let children:Vec<MatchHit> = nest_groups(groups, end, text);
+		let mut hit:MatchHit = MatchHit::new_at_with_list(start, end - start, text, children);
+		hit.type_name = name.to_string();
+		hits.push(hit);
+	}
+	hits
+}
+
+
+
+/* PROGRAM */
+
+#[derive(Clone, Debug)]
+enum Instruction {
+	Char(char),
+	AnyExceptNewline,
+	Class(Class),
+	Assert(Assertion),
+	Split(usize, usize),
+	Jump(usize),
+	Save(usize),
+	Match
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Assertion {
+	Start,
+	End,
+	WordBoundary,
+	NotWordBoundary
+}
+impl Assertion {
+
+	/// Check if the assertion holds at the given position of the text.
+	fn holds(&self, text:&str, position:usize) -> bool {
+		let is_word_boundary = || is_word_char(text[..position].chars().next_back()) != is_word_char(text[position..].chars().next());
+		match self {
+			Assertion::Start => position == 0,
+			Assertion::End => position == text.len(),
+			Assertion::WordBoundary => is_word_boundary(),
+			Assertion::NotWordBoundary => !is_word_boundary()
+		}
+	}
+}
+
+/// A thread of the virtual machine, waiting at an instruction that consumes a character.
+struct Thread {
+	program_counter:usize,
+	slots:Vec<Option<usize>>
+}
+
+/// Get an upper bound of the amount of instructions the node compiles to.
+fn program_size(node:&Node) -> usize {
+	match node {
+		Node::Empty => 0,
+		Node::Char(_) | Node::AnyExceptNewline | Node::Class(_) | Node::Assert(_) => 1,
+		Node::Group(inner, _) => program_size(inner).saturating_add(2),
+		Node::Concat(nodes) => nodes.iter().fold(0, |size:usize, node| size.saturating_add(program_size(node))),
+		Node::Alternate(nodes) => nodes.iter().fold(0, |size:usize, node| size.saturating_add(program_size(node)).saturating_add(2)),
+		Node::Repeat(inner, min_count, max_count, _) => program_size(inner).saturating_add(2).saturating_mul(max_count.unwrap_or(*min_count).max(*min_count).saturating_add(1))
+	}
+}
+
+/// Compile the node into instructions added to the program.
+fn compile(node:&Node, program:&mut Vec<Instruction>) {
+	match node {
+		Node::Empty => {},
+		Node::Char(char) => program.push(Instruction::Char(*char)),
+		Node::AnyExceptNewline => program.push(Instruction::AnyExceptNewline),
+		Node::Class(class) => program.push(Instruction::Class(class.clone())),
+		Node::Assert(assertion) => program.push(Instruction::Assert(*assertion)),
+		Node::Group(inner, capture_index) => {
+			if let Some(capture_index) = capture_index {
+				program.push(Instruction::Save(capture_index * 2));
+				compile(inner, program);
+				program.push(Instruction::Save(capture_index * 2 + 1));
+			} else {
+				compile(inner, program);
+			}
+		},
+		Node::Concat(nodes) => {
+			for node in nodes {
+				compile(node, program);
+			}
+		},
+		Node::Alternate(nodes) => {
+			let mut jumps_to_end:Vec<usize> = Vec::new();
+			for (index, node) in nodes.iter().enumerate() {
+				if index + 1 < nodes.len() {
+					let split_index:usize = program.len();
+					program.push(Instruction::Split(split_index + 1, 0));
+					compile(node, program);
+					jumps_to_end.push(program.len());
+					program.push(Instruction::Jump(0));
+					let next_alternative:usize = program.len();
+					program[split_index] = Instruction::Split(split_index + 1, next_alternative);
+				} else {
+					compile(node, program);
+				}
+			}
+			let end:usize = program.len();
+			for jump_index in jumps_to_end {
+				program[jump_index] = Instruction::Jump(end);
+			}
+		},
+		Node::Repeat(inner, min_count, max_count, greedy) => {
+			for _ in 0..*min_count {
+				compile(inner, program);
+			}
+			let split = |repeat:usize, skip:usize| if *greedy { Instruction::Split(repeat, skip) } else { Instruction::Split(skip, repeat) };
+			match max_count {
+				None => {
+					let split_index:usize = program.len();
+					program.push(Instruction::Split(0, 0));
+					compile(inner, program);
+					program.push(Instruction::Jump(split_index));
+					program[split_index] = split(split_index + 1, program.len());
+				},
+				Some(max_count) => {
+					let mut split_indices:Vec<usize> = Vec::new();
+					for _ in *min_count..*max_count {
+						split_indices.push(program.len());
+						program.push(Instruction::Split(0, 0));
+						compile(inner, program);
+					}
+					let end:usize = program.len();
+					for split_index in split_indices {
+						program[split_index] = split(split_index + 1, end);
+					}
+				}
+			}
+		}
+	}
+}
+
+
+
+/* CHARACTER CLASSES */
+
+#[derive(Clone, Debug)]
+enum ClassItem {
+	Range(char, char),
+	Digit(bool),
+	Word(bool),
+	Space(bool)
+}
+impl ClassItem {
+
+	/// Check if the item contains the given character.
+	fn contains(&self, char:char) -> bool {
+		match self {
+			ClassItem::Range(start, end) => (*start..=*end).contains(&char),
+			ClassItem::Digit(negated) => char.is_ascii_digit() != *negated,
+			ClassItem::Word(negated) => is_word_char(Some(char)) != *negated,
+			ClassItem::Space(negated) => char.is_whitespace() != *negated
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+struct Class {
+	items:Vec<ClassItem>,
+	negated:bool
+}
+impl Class {
+
+	/// Check if the class contains the given character.
+	fn contains(&self, char:char) -> bool {
+		self.items.iter().any(|item| item.contains(char)) != self.negated
+	}
+}
+
+/// Check if the character is a word character, being any XID_Continue character.
+fn is_word_char(char:Option<char>) -> bool {
+	char.is_some_and(|char| unicode_tables::in_ranges(char, unicode_tables::XID_CONTINUE))
+}
+
+
+
+/* PARSER */
+
+enum Node {
+	Empty,
+	Char(char),
+	AnyExceptNewline,
+	Class(Class),
+	Assert(Assertion),
+	Group(Box<Node>, Option<usize>),
+	Concat(Vec<Node>),
+	Alternate(Vec<Node>),
+	Repeat(Box<Node>, usize, Option<usize>, bool)
+}
+
+struct Parser {
+	chars:Vec<(usize, char)>,
+	index:usize,
+	pattern_length:usize,
+	group_names:Vec<Option<String>>,
+	group_depth:usize
+}
+impl Parser {
+
+	/// Parse alternatives separated by '|'.
+	fn parse_alternation(&mut self) -> Result<Node, RegexError> {
+		let mut alternatives:Vec<Node> = vec![self.parse_concatenation()?];
+		while self.eat('|') {
+			alternatives.push(self.parse_concatenation()?);
+		}
+		Ok(if alternatives.len() == 1 { alternatives.remove(0) } else { Node::Alternate(alternatives) })
+	}
+
+	/// Parse a sequence of repeated atoms.
+	fn parse_concatenation(&mut self) -> Result<Node, RegexError> {
+		let mut nodes:Vec<Node> = Vec::new();
+		while let Some(char) = self.peek() && char != '|' && char != ')' {
+			let atom:Node = self.parse_atom()?;
+			nodes.push(self.parse_quantifiers(atom)?);
+		}
+		Ok(match nodes.len() {
+			0 => Node::Empty,
+			1 => nodes.remove(0),
+			_ => Node::Concat(nodes)
+		})
+	}
+
+	/// Parse any quantifiers following an atom.
+	fn parse_quantifiers(&mut self, mut atom:Node) -> Result<Node, RegexError> {
+		loop {
+			let (min_count, max_count) = match self.peek() {
+				Some('*') => { self.index += 1; (0, None) },
+				Some('+') => { self.index += 1; (1, None) },
+				Some('?') => { self.index += 1; (0, Some(1)) },
+				Some('{') => match self.parse_counted_quantifier()? {
+					Some(counts) => counts,
+					None => return Ok(atom)
+				},
+				_ => return Ok(atom)
+			};
+			if matches!(atom, Node::Repeat(..)) {
+				return Err(self.error("nested quantifier"));
+			}
+			let greedy:bool = !self.eat('?');
+			atom = Node::Repeat(Box::new(atom), min_count, max_count, greedy);
+		}
+	}
+
+	/// Parse a quantifier like `{2}`, `{2,}` or `{2,5}`. Returns None without consuming anything when the brace does not start a valid quantifier, in which case it is a literal.
+	fn parse_counted_quantifier(&mut self) -> Result<Option<(usize, Option<usize>)>, RegexError> {
+		let start_index:usize = self.index;
+		self.index += 1;
+		let min_count:Option<usize> = self.parse_number()?;
+		let max_count:Option<Option<usize>> = if self.eat(',') { Some(self.parse_number()?) } else { None };
+		if min_count.is_none() || !self.eat('}') {
+			self.index = start_index;
+			return Ok(None);
+		}
+		let min_count:usize = min_count.unwrap();
+		let max_count:Option<usize> = max_count.unwrap_or(Some(min_count));
+		if max_count.is_some_and(|max_count| max_count < min_count) {
+			self.index = start_index;
+			return Err(self.error("quantifier maximum is smaller than its minimum"));
+		}
+		if min_count > MAX_REPETITION_COUNT || max_count.is_some_and(|max_count| max_count > MAX_REPETITION_COUNT) {
+			self.index = start_index;
+			return Err(self.error(&format!("quantifier count exceeds {MAX_REPETITION_COUNT}")));
+		}
+		Ok(Some((min_count, max_count)))
+	}
+
+	/// Parse a decimal number. Returns an error when the number does not fit in a usize.
+	fn parse_number(&mut self) -> Result<Option<usize>, RegexError> {
+		let mut number:Option<usize> = None;
+		while let Some(digit) = self.peek().and_then(|char| char.to_digit(10)) {
+			number = Some(number.unwrap_or(0).checked_mul(10).and_then(|number| number.checked_add(digit as usize)).ok_or_else(|| self.error("quantifier count is too large"))?);
+			self.index += 1;
+		}
+		Ok(number)
+	}
+
+	/// Parse a single atom.
+	fn parse_atom(&mut self) -> Result<Node, RegexError> {
+		let char:char = self.next().unwrap();
+		Ok(match char {
+			'(' => self.parse_group()?,
+			'[' => Node::Class(self.parse_class()?),
+			'.' => Node::AnyExceptNewline,
+			'^' => Node::Assert(Assertion::Start),
+			'$' => Node::Assert(Assertion::End),
+			'\\' => self.parse_escape(false)?,
+			'*' | '+' | '?' => {
+				self.index -= 1;
+				return Err(self.error("quantifier without anything to repeat"));
+			},
+			_ => Node::Char(char)
+		})
+	}
+
+	/// Parse a group after its opening parenthesis.
+	fn parse_group(&mut self) -> Result<Node, RegexError> {
+		let mut capture_name:Option<String> = None;
+		let mut capturing:bool = true;
+		if self.eat('?') {
+			if self.eat(':') {
+				capturing = false;
+			} else if self.eat('<') || (self.eat('P') && self.eat('<')) {
+				let mut name:String = String::new();
+				while let Some(char) = self.next() && char != '>' {
+					name.push(char);
+				}
+				if name.is_empty() || !name.chars().all(|char| char.is_alphanumeric() || char == '_') {
+					return Err(self.error("invalid group name"));
+				}
+				capture_name = Some(name);
+			} else {
+				return Err(self.error("unsupported group syntax"));
+			}
+		}
+		let capture_index:Option<usize> = if capturing {
+			self.group_names.push(capture_name);
+			Some(self.group_names.len() - 1)
+		} else {
+			None
+		};
+		if self.group_depth == MAX_GROUP_DEPTH {
+			return Err(self.error(&format!("groups nested deeper than {MAX_GROUP_DEPTH}")));
+		}
+		self.group_depth += 1;
+		let inner:Node = self.parse_alternation()?;
+		self.group_depth -= 1;
+		if !self.eat(')') {
+			return Err(self.error("unclosed group"));
+		}
+		Ok(Node::Group(Box::new(inner), capture_index))
+	}
+
+	/// Parse a character class after its opening bracket.
+	fn parse_class(&mut self) -> Result<Class, RegexError> {
+		let negated:bool = self.eat('^');
+		let mut items:Vec<ClassItem> = Vec::new();
+		let mut first:bool = true;
+		loop {
+			let char:char = match self.next() {
+				None => return Err(self.error("unclosed character class")),
+				Some(']') if !first => return Ok(Class { items, negated }),
+				Some(char) => char
+			};
+			first = false;
+			let start:char = if char == '\\' {
+				match self.parse_escape(true)? {
+					Node::Char(char) => char,
+					Node::Class(class) => {
+						items.extend(class.items);
+						continue;
+					},
+					_ => return Err(self.error("invalid escape in character class"))
+				}
+			} else {
+				char
+			};
+			if self.peek() == Some('-') && self.chars.get(self.index + 1).is_some_and(|(_, char)| *char != ']') {
+				self.index += 1;
+				let end:char = match self.next() {
+					Some('\\') => match self.parse_escape(true)? {
+						Node::Char(char) => char,
+						_ => return Err(self.error("invalid range end in character class"))
+					},
+					Some(char) => char,
+					None => return Err(self.error("unclosed character class"))
+				};
+				if end < start {
+					return Err(self.error("reversed range in character class"));
+				}
+				items.push(ClassItem::Range(start, end));
+			} else {
+				items.push(ClassItem::Range(start, start));
+			}
+		}
+	}
+
+	/// Parse an escape sequence after its backslash.
+	fn parse_escape(&mut self, in_class:bool) -> Result<Node, RegexError> {
+		let shorthand = |item:ClassItem| Node::Class(Class { items: vec![item], negated: false });
+		let char:char = match self.next() {
+			Some(char) => char,
+			None => return Err(self.error("unfinished escape"))
+		};
+		Ok(match char {
+			'd' => shorthand(ClassItem::Digit(false)),
+			'D' => shorthand(ClassItem::Digit(true)),
+			'w' => shorthand(ClassItem::Word(false)),
+			'W' => shorthand(ClassItem::Word(true)),
+			's' => shorthand(ClassItem::Space(false)),
+			'S' => shorthand(ClassItem::Space(true)),
+			'b' if !in_class => Node::Assert(Assertion::WordBoundary),
+			'B' if !in_class => Node::Assert(Assertion::NotWordBoundary),
+			'n' => Node::Char('\n'),
+			'r' => Node::Char('\r'),
+			't' => Node::Char('\t'),
+			'f' => Node::Char('\u{C}'),
+			'v' => Node::Char('\u{B}'),
+			'0' => Node::Char('\0'),
+			'x' | 'u' => Node::Char(self.parse_code_point()?),
+			_ if char.is_ascii_alphanumeric() => {
+				self.index -= 1;
+				return Err(self.error("unknown escape"));
+			},
+			_ => Node::Char(char)
+		})
+	}
+
+	/// Parse a hexadecimal code point like `41` or `{1F600}` after `\x` or `\u`.
+	fn parse_code_point(&mut self) -> Result<char, RegexError> {
+		let braced:bool = self.eat('{');
+		let mut digits:String = String::new();
+		while let Some(char) = self.peek() && char.is_ascii_hexdigit() && (braced || digits.len() < 2) {
+			self.index += 1;
+			digits.push(char);
+		}
+		if digits.is_empty() || (braced && !self.eat('}')) {
+			return Err(self.error("invalid code point escape"));
+		}
+		u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32).ok_or_else(|| self.error("invalid code point"))
+	}
+
+
+
+	/// Get the next character without consuming it.
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.index).map(|(_, char)| *char)
+	}
+
+	/// Consume and return the next character.
+	fn next(&mut self) -> Option<char> {
+		let char:Option<char> = self.peek();
+		if char.is_some() {
+			self.index += 1;
+		}
+		char
+	}
+
+	/// Consume the next character if it is the expected one.
+	fn eat(&mut self, expected:char) -> bool {
+		if self.peek() == Some(expected) {
+			self.index += 1;
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Create an error at the current position.
+	fn error(&self, message:&str) -> RegexError {
+		RegexError {
+			position: self.chars.get(self.index).map(|(position, _)| *position).unwrap_or(self.pattern_length),
+			message: message.to_string()
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Expectation, MatchContext, MatchExpr, MatchHit, MatcherRegistry, Regex, RegexError, TextPredicate };
+
+
+
+	/* SYNTAX TESTS */
+
+	#[test]
+	fn test_regex_literals_and_classes() {
+		assert_eq!(MatchExpr::regex("abc").match_text("abcd").unwrap().length, 3);
+		assert_eq!(MatchExpr::regex("abc").match_text("xabc"), None);
+		assert_eq!(MatchExpr::regex("a.c").match_text("a日c").unwrap().length, 5);
+		assert_eq!(MatchExpr::regex("a.c").match_text("a\nc"), None);
+		assert_eq!(MatchExpr::regex("[a-f0-9]+").match_text("c0ffee!").unwrap().contents, "c0ffee");
+		assert_eq!(MatchExpr::regex("[^\\s=]+").match_text("キー=値").unwrap().contents, "キー");
+		assert_eq!(MatchExpr::regex("[]-]+").match_text("]-]x").unwrap().length, 3);
+		assert_eq!(MatchExpr::regex("\\d+\\.\\d*").match_text("3.14").unwrap().length, 4);
+		assert_eq!(MatchExpr::regex("\\w+").match_text("größe=1").unwrap().contents, "größe");
+		assert_eq!(MatchExpr::regex("\\x41\\u{1F600}").match_text("A😀").unwrap().length, 5);
+	}
+
+	#[test]
+	fn test_regex_quantifiers() {
+		assert_eq!(MatchExpr::regex("a*").match_text("bbb").unwrap().length, 0);
+		assert_eq!(MatchExpr::regex("a+b?").match_text("aaab").unwrap().length, 4);
+		assert_eq!(MatchExpr::regex("[0-9a-f]{2}").match_text("abc").unwrap().length, 2);
+		assert_eq!(MatchExpr::regex("x{2,}").match_text("xxxxy").unwrap().length, 4);
+		assert_eq!(MatchExpr::regex("x{2,3}").match_text("xxxxy").unwrap().length, 3);
+		assert_eq!(MatchExpr::regex("x{2,3}").match_text("xy"), None);
+		assert_eq!(MatchExpr::regex("a{,2}").match_text("a{,2}").unwrap().length, 5);
+		assert_eq!(MatchExpr::regex("<.+>").match_text("<a><b>").unwrap().length, 6);
+		assert_eq!(MatchExpr::regex("<.+?>").match_text("<a><b>").unwrap().length, 3);
+		assert_eq!(MatchExpr::regex("(a*)*b").match_text("aaab").unwrap().length, 4);
+	}
+
+	#[test]
+	fn test_regex_alternation_and_anchors() {
+		assert_eq!(MatchExpr::regex("xa|xaba").match_text("xaba").unwrap().length, 2);
+		assert_eq!(MatchExpr::regex("(?:xa|xaba)$").match_text("xaba").unwrap().length, 4);
+		assert_eq!(MatchExpr::regex("if\\b").match_text("if(x)").unwrap().length, 2);
+		assert_eq!(MatchExpr::regex("if\\b").match_text("iffy"), None);
+		assert_eq!(MatchExpr::regex("\\Bf").match_text_at(&MatchContext::new("iffy"), 2).unwrap().length, 1);
+
+		let context:MatchContext = MatchContext::new("ab");
+		assert_eq!(MatchExpr::regex("^b").match_text_at(&context, 1), None);
+		assert_eq!(MatchExpr::regex("b$").match_text_at(&context, 1).unwrap().span(), 1..2);
+	}
+
+	#[test]
+	fn test_regex_errors() {
+		assert_eq!(Regex::new("(ab").unwrap_err(), RegexError { position: 3, message: "unclosed group".to_string() });
+		assert_eq!(Regex::new("ab)").unwrap_err().position, 2);
+		assert_eq!(Regex::new("[a-").unwrap_err().message, "unclosed character class");
+		assert_eq!(Regex::new("*a").unwrap_err().to_string(), "quantifier without anything to repeat at byte 0 of the pattern");
+		assert_eq!(Regex::new("\\q").unwrap_err().message, "unknown escape");
+		assert_eq!(Regex::new("[z-a]").unwrap_err().message, "reversed range in character class");
+		assert_eq!(Regex::new("a{3,2}").unwrap_err().message, "quantifier maximum is smaller than its minimum");
+		assert!(Regex::new("(?<>a)").is_err());
+	}
+
+	#[test]
+	fn test_regex_count_limits() {
+		assert_eq!(Regex::new("a{99999999999999999999999}").unwrap_err().message, "quantifier count is too large");
+		assert_eq!(Regex::new("a{1000000}").unwrap_err(), RegexError { position: 1, message: "quantifier count exceeds 1000".to_string() });
+		assert_eq!(Regex::new("a{2,1000000}").unwrap_err().position, 1);
+		assert_eq!(Regex::new("(a{1000}){1000}").unwrap_err().message, "pattern compiles to more than 100000 instructions");
+		assert_eq!(MatchExpr::regex("a{1000}").match_text(&"a".repeat(1001)).unwrap().length, 1000);
+		assert!(Regex::new("(a{100}){100}").is_ok());
+	}
+
+	#[test]
+	fn test_regex_group_depth_limit() {
+		let nested = |depth:usize| format!("{}a{}", "(".repeat(depth), ")".repeat(depth));
+		assert_eq!(MatchExpr::regex(&nested(250)).match_text("a").unwrap().length, 1);
+		assert_eq!(Regex::new(&nested(251)).unwrap_err(), RegexError { position: 251, message: "groups nested deeper than 250".to_string() });
+		assert_eq!(Regex::new(&"(".repeat(100_000)).unwrap_err().message, "groups nested deeper than 250");
+	}
+
+	#[test]
+	fn test_regex_long_optional_chains() {
+		let text:String = "a".repeat(100);
+		assert_eq!(MatchExpr::regex(&"a?".repeat(16000)).match_text(&text).unwrap().length, 100);
+		assert_eq!(MatchExpr::regex("(?:(?:a?){1000}){8}").match_text(&text).unwrap().length, 100);
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_regex_invalid_pattern_panics() {
+		MatchExpr::regex("(");
+	}
+
+
+
+	/* CAPTURE TESTS */
+
+	#[test]
+	fn test_regex_named_groups() {
+		let matcher:MatchExpr = MatchExpr::regex("(?<key>[^=\\n]+)=(?P<value>.*)");
+		let result:MatchHit = matcher.match_text("名前=太郎\nx").unwrap();
+		assert_eq!(result.contents, "名前=太郎");
+		assert_eq!(result.sub_matches.iter().map(|child| (child.type_name.as_str(), child.contents.as_str(), child.span())).collect::<Vec<_>>(), vec![("key", "名前", 0..6), ("value", "太郎", 7..13)]);
+		assert_eq!(&result["value"], "太郎");
+	}
+
+	#[test]
+	fn test_regex_nested_and_optional_groups() {
+		let matcher:MatchExpr = MatchExpr::regex("(?<date>(?<year>\\d{4})-(?<month>\\d\\d))(?: (?<time>\\d\\d:\\d\\d))?");
+		let result:MatchHit = matcher.match_text("2024-05").unwrap();
+		assert_eq!(result.sub_matches.len(), 1);
+		assert_eq!(result.sub_matches[0].type_name, "date");
+		assert_eq!(result.sub_matches[0].sub_matches.iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["2024", "05"]);
+
+		let result:MatchHit = matcher.match_text("2024-05 13:37").unwrap();
+		assert_eq!(result.sub_matches.iter().map(|child| child.type_name.as_str()).collect::<Vec<&str>>(), vec!["date", "time"]);
+		assert_eq!(result.find_child_by_type_path(&["date", "month"]).unwrap().span(), 5..7);
+	}
+
+	#[test]
+	fn test_regex_repeated_group() {
+		let result:MatchHit = MatchExpr::regex("(?:(?<item>\\w+),?)+").match_text("a,bc,d").unwrap();
+		assert_eq!(result.length, 6);
+		assert_eq!(result.sub_matches.iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["d"]);
+	}
+
+
+
+	/* COMBINATION TESTS */
+
+	#[test]
+	fn test_regex_combinators() {
+		let matcher:MatchExpr = MatchExpr::named("key", MatchExpr::regex("[a-z_]+")) + "=" + MatchExpr::named("number", MatchExpr::regex("0x[0-9a-fA-F]+|\\d+"));
+		let result:MatchHit = matcher.match_text("mask=0xFF").unwrap();
+		assert_eq!(&result["number"], "0xFF");
+		assert_eq!(result.find_child(|child| child.type_name == "number").unwrap().span(), 5..9);
+		assert_eq!(matcher.try_match_text("mask=?").unwrap_err().expected, vec![Expectation::Rule("number".to_string())]);
+
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", Regex::new("\\d+").unwrap())]);
+		assert_eq!(set.find_matches("a1b22").iter().map(|(offset, result)| (*offset, result.contents.as_str())).collect::<Vec<(usize, &str)>>(), vec![(1, "1"), (3, "22")]);
+		assert_eq!(Regex::new("\\d+").unwrap().try_match_text("x").unwrap_err().expected, vec![Expectation::CharClass("/\\d+/".to_string())]);
+	}
+}
\ No newline at end of file
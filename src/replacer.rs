@@ -0,0 +1,55 @@
+use crate::MatchHit;
+
+
+
+/// Something that produces the replacement text for a match, used by the `replace_all` and `replace_n` methods.
+/// Strings are templates where `${path}` is replaced by the contents of the named sub-match at that path, as in `result["path"]`, and `$$` by a single `$`. Closures receive the match and return the replacement.
+pub trait Replacer {
+
+	/// Append the replacement for the given match to the destination.
+	fn append_replacement(&mut self, result:&MatchHit, destination:&mut String);
+}
+impl Replacer for &str {
+	fn append_replacement(&mut self, result:&MatchHit, destination:&mut String) {
+		let mut remaining:&str = self;
+		while let Some(dollar_index) = remaining.find('$') {
+			destination.push_str(&remaining[..dollar_index]);
+			remaining = &remaining[dollar_index + 1..];
+			if let Some(after_dollar) = remaining.strip_prefix('$') {
+				destination.push('$');
+				remaining = after_dollar;
+			} else if let Some((path, after_path)) = remaining.strip_prefix('{').and_then(|remaining| remaining.split_once('}')) {
+				destination.push_str(&result[path]);
+				remaining = after_path;
+			} else {
+				destination.push('$');
+			}
+		}
+		destination.push_str(remaining);
+	}
+}
+impl Replacer for String {
+	fn append_replacement(&mut self, result:&MatchHit, destination:&mut String) {
+		self.as_str().append_replacement(result, destination);
+	}
+}
+impl<T:FnMut(&MatchHit) -> String> Replacer for T {
+	fn append_replacement(&mut self, result:&MatchHit, destination:&mut String) {
+		destination.push_str(&self(result));
+	}
+}
+
+
+
+/// Build a copy of the text with at most `limit` of the given non-overlapping matches, in order, replaced.
+pub(crate) fn replace_matches<T:Iterator<Item = (usize, MatchHit)>, U:Replacer>(text:&str, matches:T, limit:usize, mut replacer:U) -> String {
+	let mut result:String = String::with_capacity(text.len());
+	let mut cursor:usize = 0;
+	for (start, match_result) in matches.take(limit) {
+		result.push_str(&text[cursor..start]);
+		replacer.append_replacement(&match_result, &mut result);
+		cursor = match_result.end();
+	}
+	result.push_str(&text[cursor..]);
+	result
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ MatchExpr, MatchHit, MatcherRegistry };
+
+
+
+	/* TEMPLATE TESTS */
+
+	#[test]
+	fn test_replace_template() {
+		let pair:MatchExpr = MatchExpr::named("key", MatchExpr::word()) + '=' + MatchExpr::named("value", MatchExpr::unsigned_integer());
+		assert_eq!(pair.replace_all("a=1, bc=23; d=x", "${value}=${key}"), "1=a, 23=bc; d=x");
+		assert_eq!(pair.replace_all("a=1", "$$${key} costs $5 ${missing}{}"), "$a costs $5 {}");
+		assert_eq!(pair.replace_n("a=1 b=2 c=3", 2, "${key}".to_string()), "a b c=3");
+		assert_eq!(pair.replace_n("a=1 b=2", 0, "x"), "a=1 b=2");
+	}
+
+	#[test]
+	fn test_replace_template_path() {
+		let set:MatcherRegistry = MatcherRegistry::from_grammar("call = name:[a-z]+ \"(\" arguments:(first:[0-9]+ \",\" second:[0-9]+) \")\"").unwrap();
+		assert_eq!(set.replace_all("f(1,2) + g(22,3)", "${name}(${arguments.second},${arguments.first})"), "f(2,1) + g(3,22)");
+	}
+
+
+
+	/* CLOSURE TESTS */
+
+	#[test]
+	fn test_replace_closure() {
+		let number:MatchExpr = MatchExpr::unsigned_integer();
+		assert_eq!(number.replace_all("日1 x22", |result:&MatchHit| (result.contents.parse::<usize>().unwrap() * 2).to_string()), "日2 x44");
+
+		let mut count:usize = 0;
+		let set:MatcherRegistry = MatcherRegistry::new().with_matchers(vec![("number", MatchExpr::unsigned_integer()), ("word", MatchExpr::word())]);
+		assert_eq!(set.replace_all("ab 12 cd", |result:&MatchHit| { count += 1; format!("{}{count}", result.type_name) }), "word1 number2 word3");
+		assert_eq!(set.replace_n("ab 12 cd", 1, |result:&MatchHit| result.contents.to_uppercase()), "AB 12 cd");
+	}
+
+	#[test]
+	fn test_replace_empty_matches() {
+		let boundary:MatchExpr = MatchExpr::word_boundary();
+		assert_eq!(boundary.replace_all("ab cd.", "|"), "|ab| |cd|.");
+		assert_eq!(boundary.replace_all("ab cd", "|"), "|ab| |cd|");
+		assert_eq!(boundary.split("ab cd").last(), Some((5, "")));
+		assert_eq!(MatchExpr::new("").replace_all("ab", "-"), "ab".replace("", "-"));
+	}
+}
\ No newline at end of file
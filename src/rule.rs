@@ -0,0 +1,56 @@
+use std::sync::{ Arc, OnceLock };
+use crate::{ MatchContext, MatchExpr, MatchHit, TextPredicate };
+
+
+
+/// A named match-expression that can be declared before it is defined. This allows rules to reference themselves or each other recursively, including left-recursive definitions like `sum = sum "+" number | number`.
+/// Note that a rule referencing itself forms a reference cycle, so its definition is never freed. Build grammars once and reuse them.
+#[derive(Clone)]
+pub struct Rule {
+	name:Arc<String>,
+	definition:Arc<OnceLock<MatchExpr>>
+}
+impl Rule {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Declare a new rule. The rule needs to be defined before it is matched.
+	pub fn new(name:&str) -> Rule {
+		Rule {
+			name: Arc::new(name.to_string()),
+			definition: Arc::new(OnceLock::new())
+		}
+	}
+
+	/// Define the match-expression of the rule. Panics if the rule was already defined.
+	pub fn define<T:TextPredicate + 'static>(&self, definition:T) {
+		if self.definition.set(MatchExpr::new(definition)).is_err() {
+			panic!("Rule {} was defined twice.", self.name);
+		}
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Get the name of the rule.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Check if the rule has been defined.
+	pub fn is_defined(&self) -> bool {
+		self.definition.get().is_some()
+	}
+
+	/// Get a match-expression matching this rule.
+	pub fn expr(&self) -> MatchExpr {
+		MatchExpr::new(self.clone())
+	}
+}
+impl TextPredicate for Rule {
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		let definition:&MatchExpr = self.definition.get().unwrap_or_else(|| panic!("Rule {} was matched before being defined.", self.name));
+		context.match_rule(Arc::as_ptr(&self.definition).addr(), &self.name, false, definition, cursor)
+	}
+}
\ No newline at end of file
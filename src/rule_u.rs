@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Expectation, MatchContext, MatchExpr, MatchHit, MatcherRegistry, Rule, TextPredicate };
+
+
+
+	/* RULE HANDLE TESTS */
+
+	#[test]
+	fn test_rule_self_reference() {
+		let parens:Rule = Rule::new("parens");
+		parens.define(MatchExpr::new("(") + MatchExpr::optional_repeat_max(parens.clone()) + ")");
+
+		assert_eq!(parens.match_text("(()(()))").unwrap().length, 8);
+		assert_eq!(parens.match_text("(()(())"), None);
+		assert_eq!(parens.match_text("()x").unwrap().contents, "()");
+		assert_eq!(parens.match_text("(()(()))").unwrap().find_children(|child| child.type_name == "parens").len(), 4);
+	}
+
+	#[test]
+	fn test_rule_mutual_reference() {
+		let value:Rule = Rule::new("value");
+		let list:Rule = Rule::new("list");
+		value.define(MatchExpr::unsigned_integer() | list.clone());
+		list.define(MatchExpr::new("[") + MatchExpr::optional(value.expr() + MatchExpr::optional_repeat_max(MatchExpr::new(",") + value.clone())) + "]");
+
+		let result:MatchHit = value.match_text("[1,[2,[]],3]").unwrap();
+		assert_eq!(result.length, 12);
+		assert_eq!(result.type_name, "value"); // The outer rule names the result, like MatchExpr::named does.
+		assert_eq!(result.find_children(|child| child.type_name == "value").iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["[1,[2,[]],3]", "1", "[2,[]]", "2", "[]", "3"]);
+	}
+
+	#[test]
+	fn test_rule_failure_reported_by_name() {
+		let value:Rule = Rule::new("value");
+		value.define(MatchExpr::unsigned_integer());
+		assert_eq!((MatchExpr::new("=") + value).try_match_text("=x").unwrap_err().expected, vec![Expectation::Rule("value".to_string())]);
+	}
+
+	#[test]
+	fn test_rule_properties() {
+		let rule:Rule = Rule::new("rule");
+		assert_eq!(rule.name(), "rule");
+		assert!(!rule.is_defined());
+		rule.define("x");
+		assert!(rule.is_defined());
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_rule_defined_twice() {
+		let rule:Rule = Rule::new("rule");
+		rule.define("x");
+		rule.define("y");
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_rule_undefined() {
+		Rule::new("rule").match_text("x");
+	}
+
+
+
+	/* REGISTRY REFERENCE TESTS */
+
+	#[test]
+	fn test_registry_references() {
+		let set:MatcherRegistry = MatcherRegistry::new()
+			.with_matcher("block", MatchExpr::new("{") + MatchExpr::optional_repeat_max(MatchExpr::reference("entry")) + "}")
+			.with_rules(vec![
+				("entry", MatchExpr::reference("number") | MatchExpr::reference("block")),
+				("number", MatchExpr::unsigned_integer() + MatchExpr::optional(";"))
+			]);
+
+		let result:MatchHit = set.match_text("{1;{2;{}}3}").unwrap();
+		assert_eq!(result.type_name, "block");
+		assert_eq!(result.length, 11);
+		assert_eq!(result.find_children(|child| child.type_name == "entry").iter().map(|child| child.span()).collect::<Vec<_>>(), vec![1..3, 3..9, 4..6, 6..8, 9..10]);
+	}
+
+	#[test]
+	fn test_registry_rules_not_matched_directly() {
+		let set:MatcherRegistry = MatcherRegistry::new()
+			.with_matcher("pair", MatchExpr::reference("number") + "=" + MatchExpr::reference("number"))
+			.with_rule("number", MatchExpr::unsigned_integer());
+
+		assert_eq!(set.match_text("12"), None);
+		assert_eq!(set.match_text("1=2").unwrap().type_name, "pair");
+		assert_eq!(set.find_matches("1=2 3 4=5").iter().map(|(offset, _)| *offset).collect::<Vec<usize>>(), vec![0, 6]);
+		assert_eq!(set["number"].0, "number");
+	}
+
+	#[test]
+	fn test_registry_reference_missing() {
+		let set:MatcherRegistry = MatcherRegistry::new().with_matcher("x", MatchExpr::reference("y"));
+		assert_eq!(set.match_text("y"), None);
+		assert_eq!(set.try_match_text("y").unwrap_err().expected, vec![Expectation::Rule("x".to_string())]); // Nothing got further than the matcher itself.
+	}
+
+	#[test]
+	fn test_reference_outside_registry() {
+		assert_eq!(MatchExpr::reference("y").match_text("y"), None);
+		assert_eq!(MatchExpr::reference("y").try_match_text("y").unwrap_err().expected, vec![Expectation::Rule("y".to_string())]);
+
+		// Expressions taken from a registry only resolve their references in a context of that registry.
+		let set:MatcherRegistry = MatcherRegistry::from_grammar("a = \"x\" b\nb = \"y\"").unwrap();
+		assert_eq!(set["a"].1.match_text("xy"), None);
+		assert_eq!(set["a"].1.match_text_at(&set.context("xy"), 0).unwrap().length, 2);
+	}
+
+
+
+	/* LEFT RECURSION TESTS */
+
+	#[test]
+	fn test_rule_direct_left_recursion() {
+		let expression:Rule = Rule::new("expression");
+		let term:MatchExpr = MatchExpr::named("term", MatchExpr::unsigned_integer());
+		expression.define((expression.expr() + "+" + term.clone()) | (expression.expr() + "-" + term.clone()) | term);
+
+		let result:MatchHit = expression.match_text("1+22-3x").unwrap();
+		assert_eq!(result.contents, "1+22-3");
+		assert_eq!(result.type_name_tree(), "expression\n| expression\n| | expression\n| | term\n| term");
+		assert_eq!(result.find_children(|child| child.type_name == "expression").iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["1+22-3", "1+22", "1"]); // Left-associative.
+		assert_eq!(expression.match_text("7").unwrap().contents, "7");
+		assert_eq!(expression.match_text("x"), None);
+	}
+
+	#[test]
+	fn test_rule_indirect_left_recursion() {
+		let access:Rule = Rule::new("access");
+		let target:Rule = Rule::new("target");
+		access.define((target.expr() + "." + MatchExpr::word()) | (target.expr() + "()") | MatchExpr::word());
+		target.define(access.clone());
+
+		for context in [MatchContext::new("a.b().c d"), MatchContext::new("a.b().c d").with_memoization()] {
+			let result:MatchHit = access.match_text_at(&context, 0).unwrap();
+			assert_eq!(result.contents, "a.b().c");
+			assert_eq!(result.find_children(|child| child.type_name == "target").iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["a.b()", "a.b", "a"]);
+		}
+	}
+
+	#[test]
+	fn test_registry_left_recursion() {
+		let set:MatcherRegistry = MatcherRegistry::new()
+			.with_matcher("sum", (MatchExpr::reference("sum") + "+" + MatchExpr::reference("product")) | MatchExpr::reference("product"))
+			.with_rule("product", (MatchExpr::reference("product") + "*" + MatchExpr::reference("number")) | MatchExpr::reference("number"))
+			.with_rule("number", MatchExpr::unsigned_integer())
+			.with_memoization();
+
+		let result:MatchHit = set.match_text("1*2+3*4*5+6").unwrap();
+		assert_eq!(result.length, 11);
+		assert_eq!(result.find_children(|child| child.type_name == "product" && child.sub_matches.len() > 1).iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["3*4*5", "3*4"]);
+		assert_eq!(result.find_children(|child| child.type_name == "sum").iter().map(|child| child.contents.as_str()).collect::<Vec<&str>>(), vec!["1*2+3*4*5+6", "1*2+3*4*5", "1*2"]);
+	}
+}
\ No newline at end of file
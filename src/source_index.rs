@@ -0,0 +1,135 @@
+use std::{ ops::RangeInclusive, fmt::{ Display, Formatter, Result } };
+use crate::unicode_tables::{ self, GRAPHEME_EXTEND };
+
+
+
+const REGIONAL_INDICATORS:RangeInclusive<char> = '\u{1F1E6}'..='\u{1F1FF}';
+
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColumnMode {
+	/// Count columns in UTF-8 bytes.
+	Utf8,
+	/// Count columns in UTF-16 code units, as used by most editors and the language server protocol.
+	Utf16,
+	/// Count columns in user-perceived characters. Uses an approximation of extended grapheme clusters that keeps combining marks, emoji modifiers, zero width joiner sequences and flags together.
+	Grapheme
+}
+
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SourcePosition {
+	pub line:usize,
+	pub column:usize
+}
+impl Display for SourcePosition {
+	fn fmt(&self, f:&mut Formatter<'_>) -> Result {
+		write!(f, "line {}, col {}", self.line, self.column)
+	}
+}
+
+
+
+pub struct SourceIndex<'a> {
+	text:&'a str,
+	line_starts:Vec<usize>,
+	column_mode:ColumnMode
+}
+impl<'a> SourceIndex<'a> {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new index of the line starts in the given text. Counts columns in UTF-8 bytes by default.
+	pub fn new(text:&'a str) -> SourceIndex<'a> {
+		let bytes:&[u8] = text.as_bytes();
+		let mut line_starts:Vec<usize> = vec![0];
+		for (index, byte) in bytes.iter().enumerate() {
+			if *byte == b'\n' || (*byte == b'\r' && bytes.get(index + 1) != Some(&b'\n')) {
+				line_starts.push(index + 1);
+			}
+		}
+		SourceIndex {
+			text,
+			line_starts,
+			column_mode: ColumnMode::Utf8
+		}
+	}
+
+	/// Return self with a different way of counting columns.
+	pub fn with_column_mode(mut self, column_mode:ColumnMode) -> Self {
+		self.column_mode = column_mode;
+		self
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Get the amount of lines in the text.
+	pub fn line_count(&self) -> usize {
+		self.line_starts.len()
+	}
+
+	/// Get the 1-based line and column of the given byte offset. Offsets past the end of the text are clamped to the end, offsets inside a character to the start of that character.
+	pub fn position(&self, offset:usize) -> SourcePosition {
+		let mut offset:usize = offset.min(self.text.len());
+		while !self.text.is_char_boundary(offset) {
+			offset -= 1;
+		}
+		let line_index:usize = self.line_starts.partition_point(|line_start| *line_start <= offset) - 1;
+		let line_start:usize = self.line_starts[line_index];
+		let column:usize = match self.column_mode {
+			ColumnMode::Utf8 => offset - line_start,
+			ColumnMode::Utf16 => self.text[line_start..offset].encode_utf16().count(),
+			ColumnMode::Grapheme => {
+				let line_end:usize = self.line_starts.get(line_index + 1).copied().unwrap_or(self.text.len());
+				SourceIndex::grapheme_column(&self.text[line_start..line_end], offset - line_start)
+			}
+		};
+		SourcePosition { line: line_index + 1, column: column + 1 }
+	}
+
+	/// Get the byte offset of the given 1-based line and column. Returns None if the position does not exist in the text.
+	pub fn offset(&self, position:SourcePosition) -> Option<usize> {
+		if position.line == 0 || position.column == 0 || position.line > self.line_starts.len() {
+			return None;
+		}
+		let line_start:usize = self.line_starts[position.line - 1];
+		let line_end:usize = self.line_starts.get(position.line).copied().unwrap_or(self.text.len());
+		(line_start..=line_end).filter(|offset| self.text.is_char_boundary(*offset)).find(|offset| self.position(*offset).column == position.column)
+	}
+
+
+
+	/* HELPER METHODS */
+
+	/// Get the 0-based index of the grapheme cluster containing the given byte offset of the line. An offset at the end of the line gets the index directly after the last cluster.
+	fn grapheme_column(line:&str, offset:usize) -> usize {
+		let mut cluster_starts:usize = 0;
+		let mut previous:Option<char> = None;
+		let mut regional_indicator_run:usize = 0;
+		for (index, char) in line.char_indices() {
+			if index > offset {
+				break;
+			}
+			let is_regional_indicator:bool = REGIONAL_INDICATORS.contains(&char);
+			let extends_previous:bool = match previous {
+				None => false,
+				Some(previous) => {
+					unicode_tables::in_ranges(char, GRAPHEME_EXTEND) ||
+					previous == '\u{200D}' ||
+					(previous == '\r' && char == '\n') ||
+					(is_regional_indicator && regional_indicator_run % 2 == 1)
+				}
+			};
+			regional_indicator_run = if is_regional_indicator { regional_indicator_run + 1 } else { 0 };
+			if !extends_previous {
+				cluster_starts += 1;
+			}
+			previous = Some(char);
+		}
+		if offset < line.len() { cluster_starts - 1 } else { cluster_starts }
+	}
+}
\ No newline at end of file
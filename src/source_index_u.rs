@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ ColumnMode, MatchContext, MatchExpr, MatchHit, SourceIndex, SourcePosition, TextPredicate };
+
+
+
+	/* LINE TESTS */
+
+	#[test]
+	fn test_line_starts() {
+		let index:SourceIndex = SourceIndex::new("ab\ncd\r\nef\rgh");
+		assert_eq!(index.line_count(), 4);
+		assert_eq!(index.position(0), SourcePosition { line: 1, column: 1 });
+		assert_eq!(index.position(2), SourcePosition { line: 1, column: 3 });
+		assert_eq!(index.position(3), SourcePosition { line: 2, column: 1 });
+		assert_eq!(index.position(7), SourcePosition { line: 3, column: 1 });
+		assert_eq!(index.position(10), SourcePosition { line: 4, column: 1 });
+		assert_eq!(index.position(12), SourcePosition { line: 4, column: 3 });
+		assert_eq!(index.position(100), SourcePosition { line: 4, column: 3 });
+	}
+
+	#[test]
+	fn test_empty_text() {
+		let index:SourceIndex = SourceIndex::new("");
+		assert_eq!(index.line_count(), 1);
+		assert_eq!(index.position(0), SourcePosition { line: 1, column: 1 });
+	}
+
+	#[test]
+	fn test_position_display() {
+		assert_eq!(SourcePosition { line: 14, column: 3 }.to_string(), "line 14, col 3");
+	}
+
+
+
+	/* COLUMN MODE TESTS */
+
+	#[test]
+	fn test_column_modes() {
+		const TEXT:&str = "x\nh\u{E9}\u{1F600}e\u{301}!";
+		let offset:usize = TEXT.find('!').unwrap();
+		assert_eq!(SourceIndex::new(TEXT).position(offset), SourcePosition { line: 2, column: 11 });
+		assert_eq!(SourceIndex::new(TEXT).with_column_mode(ColumnMode::Utf8).position(offset), SourcePosition { line: 2, column: 11 });
+		assert_eq!(SourceIndex::new(TEXT).with_column_mode(ColumnMode::Utf16).position(offset), SourcePosition { line: 2, column: 7 });
+		assert_eq!(SourceIndex::new(TEXT).with_column_mode(ColumnMode::Grapheme).position(offset), SourcePosition { line: 2, column: 5 });
+	}
+
+	#[test]
+	fn test_grapheme_sequences() {
+		const FAMILY:&str = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+		const FLAGS:&str = "\u{1F1F3}\u{1F1F1}\u{1F1EF}\u{1F1F5}";
+		let text:String = format!("{FAMILY}{FLAGS}\u{1F44D}\u{1F3FD}x");
+		let index:SourceIndex = SourceIndex::new(&text).with_column_mode(ColumnMode::Grapheme);
+		assert_eq!(index.position(FAMILY.len()).column, 2);
+		assert_eq!(index.position(FAMILY.len() + 4).column, 2); // Halfway through a flag.
+		assert_eq!(index.position(FAMILY.len() + 8).column, 3);
+		assert_eq!(index.position(text.len() - 1).column, 5);
+	}
+
+	#[test]
+	fn test_position_inside_character() {
+		let index:SourceIndex = SourceIndex::new("\u{E9}x");
+		assert_eq!(index.position(1), SourcePosition { line: 1, column: 1 });
+		assert_eq!(index.position(2), SourcePosition { line: 1, column: 3 });
+	}
+
+	#[test]
+	fn test_offset() {
+		const TEXT:&str = "ab\n\u{1F600}cd";
+		let index:SourceIndex = SourceIndex::new(TEXT).with_column_mode(ColumnMode::Utf16);
+		assert_eq!(index.offset(SourcePosition { line: 1, column: 2 }), Some(1));
+		assert_eq!(index.offset(SourcePosition { line: 2, column: 3 }), Some(7));
+		assert_eq!(index.offset(SourcePosition { line: 2, column: 2 }), None); // Inside the surrogate pair.
+		assert_eq!(index.offset(SourcePosition { line: 3, column: 1 }), None);
+		assert_eq!(index.offset(SourcePosition { line: 0, column: 1 }), None);
+	}
+
+
+
+	/* MATCH HIT TESTS */
+
+	#[test]
+	fn test_match_hit_positions() {
+		const TEXT:&str = "a = 1\nbb = 22";
+		let index:SourceIndex = SourceIndex::new(TEXT);
+		let matcher:MatchExpr = MatchExpr::named("value", MatchExpr::unsigned_integer());
+		let result:MatchHit = matcher.match_text_at(&MatchContext::new(TEXT), 11).unwrap();
+		assert_eq!(result.start_position(&index), SourcePosition { line: 2, column: 6 });
+		assert_eq!(result.end_position(&index), SourcePosition { line: 2, column: 8 });
+	}
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+use crate::{ MatchContext, MatchExpr, MatchHit, TextPredicate };
+
+
+
+/// A lazy iterator over the pieces of a text between matches of a separator, created by `MatchExpr::split`, `MatchExpr::splitn` or `MatchExpr::split_inclusive`. Yields the start index and text of each piece.
+pub struct Split<'a> {
+	separator:&'a MatchExpr,
+	context:MatchContext<'a>,
+	cursor:usize,
+	last_separator_end:Option<usize>,
+	piece_start:usize,
+	remaining_pieces:usize,
+	inclusive:bool,
+	finished:bool
+}
+impl<'a> Split<'a> {
+
+	/// Create a new iterator yielding at most the given amount of pieces. Inclusive pieces end with the separator that terminated them.
+	pub(crate) fn new(separator:&'a MatchExpr, text:&'a str, max_pieces:usize, inclusive:bool) -> Split<'a> {
+		Split {
+			separator,
+			context: MatchContext::new(text),
+			cursor: 0,
+			last_separator_end: None,
+			piece_start: 0,
+			remaining_pieces: max_pieces,
+			inclusive,
+			finished: max_pieces == 0
+		}
+	}
+
+	/// Find the next match of the separator at or after the cursor, moving the cursor past it. Like `str::split`, an empty separator also matches at the end of the text, but never directly after the previous separator.
+	fn find_separator(&mut self) -> Option<MatchHit> {
+		while self.cursor <= self.context.text().len() {
+			let match_result:Option<MatchHit> = self.separator.match_text_at(&self.context, self.cursor).filter(|match_result| match_result.length > 0 || self.last_separator_end != Some(match_result.start));
+			self.cursor += match &match_result {
+				Some(match_result) if match_result.length > 0 => match_result.length,
+				_ => self.context.char_at(self.cursor).map(char::len_utf8).unwrap_or(1)
+			};
+			if match_result.is_some() {
+				self.last_separator_end = match_result.as_ref().map(MatchHit::end);
+				return match_result;
+			}
+		}
+		None
+	}
+}
+impl<'a> Iterator for Split<'a> {
+	type Item = (usize, &'a str);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.finished {
+			return None;
+		}
+		let text:&'a str = self.context.text();
+		let piece_start:usize = self.piece_start;
+		self.remaining_pieces -= 1;
+		match if self.remaining_pieces == 0 { None } else { self.find_separator() } {
+			Some(separator_match) => {
+				let piece_end:usize = if self.inclusive { separator_match.end() } else { separator_match.start };
+				self.piece_start = separator_match.end();
+				Some((piece_start, &text[piece_start..piece_end]))
+			},
+			None => {
+				self.finished = true;
+				if self.inclusive && piece_start == text.len() {
+					return None;
+				}
+				Some((piece_start, &text[piece_start..]))
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+	use crate::MatchExpr;
+
+
+
+	#[test]
+	fn test_split() {
+		let separator:MatchExpr = MatchExpr::new(',') + MatchExpr::optional_repeat_max(' ');
+		assert_eq!(separator.split("a, b,c,  d").collect::<Vec<(usize, &str)>>(), vec![(0, "a"), (3, "b"), (5, "c"), (9, "d")]);
+		assert_eq!(separator.split(",a,").map(|(_, piece)| piece).collect::<Vec<&str>>(), vec!["", "a", ""]);
+		assert_eq!(separator.split("").collect::<Vec<(usize, &str)>>(), vec![(0, "")]);
+		assert_eq!(MatchExpr::new(',').split("日,本").collect::<Vec<(usize, &str)>>(), vec![(0, "日"), (4, "本")]);
+	}
+
+	#[test]
+	fn test_split_empty_separator() {
+		// Like `str::split`, empty separators match at both ends of the text, but not directly after another separator.
+		let empty:MatchExpr = MatchExpr::new("");
+		assert_eq!(empty.split("ab").map(|(_, piece)| piece).collect::<Vec<&str>>(), "ab".split("").collect::<Vec<&str>>());
+		assert_eq!(empty.split("日本").map(|(_, piece)| piece).collect::<Vec<&str>>(), vec!["", "日", "本", ""]);
+		assert_eq!(MatchExpr::optional(',').split("a,b").map(|(_, piece)| piece).collect::<Vec<&str>>(), vec!["", "a", "b", ""]);
+	}
+
+	#[test]
+	fn test_split_does_not_shadow_str_split() {
+		#[allow(unused_imports)]
+		use crate::*;
+		let text:String = String::from("a,b,c");
+		assert_eq!(text.split(",").collect::<Vec<&str>>(), vec!["a", "b", "c"]);
+		assert_eq!("a,b".splitn(2, ',').count(), 2);
+	}
+
+	#[test]
+	fn test_splitn() {
+		let separator:MatchExpr = MatchExpr::repeat_max(MatchExpr::whitespace());
+		assert_eq!(separator.splitn("GET /index.html HTTP/1.1", 2).collect::<Vec<(usize, &str)>>(), vec![(0, "GET"), (4, "/index.html HTTP/1.1")]);
+		assert_eq!(separator.splitn("a b", 5).count(), 2);
+		assert_eq!(separator.splitn("a b", 0).count(), 0);
+	}
+
+	#[test]
+	fn test_split_inclusive() {
+		let line_end:MatchExpr = MatchExpr::new("\r\n") | '\n';
+		assert_eq!(line_end.split_inclusive("a\r\nb\nc").map(|(_, piece)| piece).collect::<Vec<&str>>(), vec!["a\r\n", "b\n", "c"]);
+		assert_eq!(line_end.split_inclusive("a\n\n").map(|(_, piece)| piece).collect::<Vec<&str>>(), vec!["a\n", "\n"]);
+		assert_eq!(line_end.split_inclusive("").count(), 0);
+	}
+
+	#[test]
+	fn test_split_multi_line_records() {
+		// Splitting before each record start keeps continuation lines with their record.
+		let date:MatchExpr = MatchExpr::repeat(MatchExpr::digit(), 4..=4) + '-' + MatchExpr::repeat(MatchExpr::digit(), 2..=2) + '-' + MatchExpr::repeat(MatchExpr::digit(), 2..=2);
+		let record_start:MatchExpr = MatchExpr::bol() + MatchExpr::not_preceded_by(MatchExpr::bof()) + MatchExpr::followed_by(date);
+		let log:&str = "2024-01-01 error\n  at main.rs\n2024-01-02 ok\n2024-01-03 error\n  at lib.rs\n";
+		assert_eq!(record_start.split(log).collect::<Vec<(usize, &str)>>(), vec![
+			(0, "2024-01-01 error\n  at main.rs\n"),
+			(30, "2024-01-02 ok\n"),
+			(44, "2024-01-03 error\n  at lib.rs\n")
+		]);
+	}
+}
\ No newline at end of file
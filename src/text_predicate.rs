@@ -1,44 +1,71 @@
 use std::ops::Range;
-use crate::MatchHit;
+use crate::{ Expectation, MatchContext, MatchFailure, MatchHit, MatchSpan };
 
 
 
-pub trait TextPredicate {
+pub trait TextPredicate: Send + Sync {
 
 	/// Try to match the given text. Returns a MatchHit in case of a match.
-	fn match_text(&self, text:&str) -> Option<MatchHit>;
+	fn match_text(&self, text:&str) -> Option<MatchHit> {
+		self.match_text_at(&MatchContext::new(text), 0)
+	}
+
+	/// Try to match the given text. Returns a MatchHit in case of a match, or a MatchFailure describing the furthest position reached and what was expected there.
+	fn try_match_text(&self, text:&str) -> Result<MatchHit, MatchFailure> {
+		let context:MatchContext = MatchContext::new(text);
+		self.match_text_at(&context, 0).ok_or_else(|| context.failure())
+	}
+
+	/// Try to match the given text, recording only the spans of the results. Returns a MatchSpan borrowing its contents from the text in case of a match.
+	fn match_spans<'a>(&self, text:&'a str) -> Option<MatchSpan<'a>> {
+		self.match_text_at(&MatchContext::new(text).with_span_only(), 0).map(|result| MatchSpan::from_hit(result, text))
+	}
+
+	/// Try to match the full given text. Returns a MatchHit when the match covers the whole text, or a MatchFailure describing where matching stopped.
+	fn parse_complete(&self, text:&str) -> Result<MatchHit, MatchFailure> {
+		let context:MatchContext = MatchContext::new(text);
+		match self.match_text_at(&context, 0) {
+			Some(result) if result.length == text.len() => Ok(result),
+			Some(result) => {
+				context.expect(result.end(), Expectation::EndOfInput);
+				Err(context.failure())
+			},
+			None => Err(context.failure())
+		}
+	}
+
+	/// Try to match the text of the context starting at the given byte cursor. Returns a MatchHit positioned absolutely in the context's text in case of a match.
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit>;
 }
 
 
 
 /* ATOM IMPLEMENTATIONS */
 impl TextPredicate for char {
-	fn match_text(&self, text:&str) -> Option<MatchHit> {
-		if let Some(first_char) = text.chars().next() {
-			if first_char == *self {
-				return Some(MatchHit::new(1, text));
-			}
-		}
-		None
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		let mut buffer:[u8; 4] = [0; 4];
+		let literal:&str = self.encode_utf8(&mut buffer);
+		literal.match_text_at(context, cursor)
 	}
 }
 impl TextPredicate for &str {
-	fn match_text(&self, text:&str) -> Option<MatchHit> {
-		if text.starts_with(self) {
-			Some(MatchHit::new(self.len(), text))
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		if let Some(length) = context.case_mode().match_prefix(context.remaining(cursor), self) {
+			Some(MatchHit::new_at(cursor, length, context.contents_source()))
 		} else {
+			context.expect(cursor, Expectation::Literal(self.to_string()));
 			None
 		}
 	}
 }
 impl TextPredicate for String {
-	fn match_text(&self, text:&str) -> Option<MatchHit> {
-		self.as_str().match_text(text)
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		self.as_str().match_text_at(context, cursor)
 	}
 }
-impl<T> TextPredicate for T where T:Fn(&str) -> Option<MatchHit> {
-	fn match_text(&self, text:&str) -> Option<MatchHit> {
-		self(text)
+impl<T> TextPredicate for T where T:Fn(&str) -> Option<MatchHit> + Send + Sync {
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		self(context.remaining(cursor)).map(|result| result.offset(cursor))
 	}
 }
 
@@ -46,45 +73,35 @@ impl<T> TextPredicate for T where T:Fn(&str) -> Option<MatchHit> {
 
 /* LIST IMPLEMENTATIONS */
 impl<T> TextPredicate for [T] where T:TextPredicate {
-	fn match_text(&self, text:&str) -> Option<MatchHit> {
-		let mut cursor:usize = 0;
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		let mut end:usize = cursor;
 		let mut sub_matches:Vec<MatchHit> = Vec::new();
-		let text_len:usize = text.len();
 		for matcher in self {
-			let text_remainder:&str = if text_len > cursor { &text[cursor..] } else { "" }; // Next matcher could match empty.
-			if let Some(match_result) = matcher.match_text(&text_remainder) {
-				cursor += match_result.length;
-				sub_matches.push(match_result);
-			} else {
-				return None;
-			};
+			let match_result:MatchHit = matcher.match_text_at(context, end)?;
+			end += match_result.length;
+			sub_matches.push(match_result);
 		}
-		Some(MatchHit::new_with_sub_matches(cursor, text, sub_matches))
+		Some(MatchHit::new_at_with_sub_matches(cursor, end - cursor, context.contents_source(), sub_matches))
 	}
 }
 impl<T> TextPredicate for Vec<T> where T:TextPredicate {
-	fn match_text(&self, text:&str) -> Option<MatchHit> {
-		self[..].match_text(text)
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		self[..].match_text_at(context, cursor)
 	}
 }
 
 macro_rules! tuple_matcher {
 	($($name:ident $idx:tt), +) => {
 		impl<$($name:TextPredicate),+> TextPredicate for ($($name,)+) {
-			fn match_text(&self, text:&str) -> Option<MatchHit> {
-				let mut cursor = 0;
+			fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+				let mut end:usize = cursor;
 				let mut sub_matches:Vec<MatchHit> = Vec::new();
-				let text_len:usize = text.len();
 				$(
-					let text_remainder:&str = if text_len > cursor { &text[cursor..] } else { "" }; // Next matcher could match empty.
-					if let Some(match_result) = self.$idx.match_text(&text_remainder) {
-						cursor += match_result.length;
-						sub_matches.push(match_result);
-					} else {
-						return None;
-					}
+					let match_result:MatchHit = self.$idx.match_text_at(context, end)?;
+					end += match_result.length;
+					sub_matches.push(match_result);
 				)+
-				Some(MatchHit::new(cursor, text))
+				Some(MatchHit::new_at_with_sub_matches(cursor, end - cursor, context.contents_source(), sub_matches))
 			}
 		}
 	};
@@ -125,11 +142,11 @@ tuple_matcher!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 1
 
 /* MISCELLANEOUS IMPLEMENTATIONS */
 impl<T:TextPredicate> TextPredicate for Range<T> {
-	fn match_text(&self, text:&str) -> Option<MatchHit> {
-		if let Some(start_match) = self.start.match_text(text) {
-			for cursor in start_match.length..text.len() {
-				if let Some(end_match) = self.end.match_text(&text[cursor..]) {
-					return Some(MatchHit::new(cursor + end_match.length, text));
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		if let Some(start_match) = self.start.match_text_at(context, cursor) {
+			for end_cursor in context.remaining(start_match.end()).char_indices().map(|(index, _)| start_match.end() + index) {
+				if let Some(end_match) = self.end.match_text_at(context, end_cursor) {
+					return Some(MatchHit::new_at(cursor, end_match.end() - cursor, context.contents_source()));
 				}
 			}
 		}
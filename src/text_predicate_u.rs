@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-	use crate::{ MatchHit, TextPredicate };
+	use crate::{ MatchContext, MatchExpr, MatchHit, TextPredicate };
 
 
 
@@ -11,6 +11,8 @@ mod tests {
 		assert_eq!('a'.match_text("xaba"), None); // Full mismatch
 		assert_eq!('x'.match_text("xxaba").unwrap().length, 1); // Non-repeating match
 		assert_eq!('x'.match_text(""), None); // Empty text mismatch
+		assert_eq!('日'.match_text("日本").unwrap().contents, "日"); // Multi-byte match
+		assert_eq!('x'.match_text("日本"), None); // Multi-byte mismatch
 	}
 
 	#[test]
@@ -38,6 +40,14 @@ mod tests {
 		assert_eq!((|text:&str| if text == "daba" { Some(MatchHit::new(3, text)) } else { None }).match_text(""), None);
 	}
 
+	#[test]
+	fn test_text_predicate_atom_fn_offset() {
+		let context:MatchContext = MatchContext::new("xxaba");
+		let result:MatchHit = (|text:&str| Some(MatchHit::new(2, text))).match_text_at(&context, 2).unwrap();
+		assert_eq!(result.span(), 2..4);
+		assert_eq!(result.contents, "ab");
+	}
+
 
 
 	/* LIST IMPLEMENTATION TESTS */
@@ -76,6 +86,16 @@ mod tests {
 		assert_eq!(('x', "ab", 'a', "").match_text(""), None); // Empty text mismatch
 	}
 
+	#[test]
+	fn test_text_predicate_list_spans() {
+		let context:MatchContext = MatchContext::new("..xaba");
+		let result:MatchHit = ["x", "ab"].match_text_at(&context, 2).unwrap();
+		assert_eq!(result.span(), 2..5);
+		let result:MatchHit = (MatchExpr::named("x", 'x'), "ab", MatchExpr::named("a", 'a')).match_text_at(&context, 2).unwrap();
+		assert_eq!(result.span(), 2..6);
+		assert_eq!(result.sub_matches.iter().map(|sub_match| sub_match.span()).collect::<Vec<_>>(), vec![2..3, 3..5, 5..6]);
+	}
+
 
 
 	/* MISCELLANEOUS IMPLEMENTATION TESTS */
@@ -86,5 +106,7 @@ mod tests {
 		assert_eq!(("x".."a").match_text("xoba").unwrap().length, 4); // Full match
 		assert_eq!(("b".."a").match_text("xoba"), None); // Full mismatch
 		assert_eq!(("x".."b").match_text(""), None); // Empty text mismatch
+		assert_eq!(("「".."」").match_text("「日本」です").unwrap().contents, "「日本」"); // Multi-byte match
+		assert_eq!(("x".."b").match_text_at(&MatchContext::new("..xoba"), 2).unwrap().span(), 2..5); // Absolute span
 	}
 }
\ No newline at end of file
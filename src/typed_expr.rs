@@ -0,0 +1,221 @@
+use std::{ ops::{ Add, BitOr, RangeBounds }, sync::{ Arc, OnceLock } };
+use crate::{ Expectation, MatchContext, MatchExpr, MatchFailure, MatchHit, TextPredicate };
+
+
+
+type TypedMatchFunction<T> = Arc<dyn Fn(&MatchContext, usize) -> Option<(MatchHit, T)> + Send + Sync>;
+
+
+
+/// A match-expression that produces a typed value along with its MatchHit. Created with `MatchExpr::map`, and composed into tuples with `+`, alternatives with `|`, `Vec`s with `repeat` and `Option`s with `optional`.
+pub struct TypedExpr<T>(TypedMatchFunction<T>);
+impl<T:'static> TypedExpr<T> {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new typed match-expression from a function that receives the full context and the byte cursor to match at.
+	pub fn positional<U:Fn(&MatchContext, usize) -> Option<(MatchHit, T)> + Send + Sync + 'static>(match_function:U) -> TypedExpr<T> {
+		TypedExpr(Arc::new(match_function))
+	}
+
+	/// Create a typed match-expression that can reference itself, like `value = number | "[" value* "]"`. The function receives the expression being defined. Left-recursive definitions are not supported.
+	/// Note that the definition references itself, so it is never freed. Build recursive expressions once and reuse them.
+	pub fn recursive<U:FnOnce(TypedExpr<T>) -> TypedExpr<T>>(define:U) -> TypedExpr<T> {
+		let definition:Arc<OnceLock<TypedExpr<T>>> = Arc::new(OnceLock::new());
+		let reference:Arc<OnceLock<TypedExpr<T>>> = definition.clone();
+		let recursive_expr:TypedExpr<T> = define(TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			reference.get().expect("Recursive expression was matched before being defined.").match_value_at(context, cursor)
+		}));
+		let _ = definition.set(recursive_expr.clone());
+		recursive_expr
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Try to match the text of the context starting at the given byte cursor. Returns the MatchHit and value in case of a match.
+	pub fn match_value_at(&self, context:&MatchContext, cursor:usize) -> Option<(MatchHit, T)> {
+		(self.0)(context, cursor)
+	}
+
+	/// Try to match the start of the given text. Returns the value in case of a match.
+	pub fn parse_value(&self, text:&str) -> Option<T> {
+		self.match_value_at(&MatchContext::new(text), 0).map(|(_, value)| value)
+	}
+
+	/// Try to match the full given text. Returns the value when the match covers the whole text, or a MatchFailure describing where matching stopped.
+	pub fn parse_complete_value(&self, text:&str) -> Result<T, MatchFailure> {
+		let context:MatchContext = MatchContext::new(text);
+		match self.match_value_at(&context, 0) {
+			Some((result, value)) if result.length == text.len() => Ok(value),
+			Some((result, _)) => {
+				context.expect(result.end(), Expectation::EndOfInput);
+				Err(context.failure())
+			},
+			None => Err(context.failure())
+		}
+	}
+
+
+
+	/* VALUE MODIFICATION METHODS */
+
+	/// Convert the value of every match.
+	pub fn map<U:'static, V:Fn(T) -> U + Send + Sync + 'static>(self, action:V) -> TypedExpr<U> {
+		TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			self.match_value_at(context, cursor).map(|(result, value)| (result, action(value)))
+		})
+	}
+
+	/// Convert the value of every match, with access to the MatchHit, for example for its span.
+	pub fn map_with_hit<U:'static, V:Fn(&MatchHit, T) -> U + Send + Sync + 'static>(self, action:V) -> TypedExpr<U> {
+		TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			self.match_value_at(context, cursor).map(|(result, value)| {
+				let value:U = action(&result, value);
+				(result, value)
+			})
+		})
+	}
+
+
+
+	/* SEQUENCE METHODS */
+
+	/// Match this expression followed by the given expression, producing both values.
+	pub fn then<U:'static>(self, following:TypedExpr<U>) -> TypedExpr<(T, U)> {
+		TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let (left_match, left_value) = self.match_value_at(context, cursor)?;
+			let (right_match, right_value) = following.match_value_at(context, left_match.end())?;
+			Some((MatchHit::new_at_with_sub_matches(cursor, left_match.length + right_match.length, context.contents_source(), vec![left_match, right_match]), (left_value, right_value)))
+		})
+	}
+
+	/// Match this expression followed by the given untyped expression, keeping only the value of this one.
+	pub fn then_ignore<U:TextPredicate + 'static>(self, following:U) -> TypedExpr<T> {
+		TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let (left_match, value) = self.match_value_at(context, cursor)?;
+			let right_match:MatchHit = following.match_text_at(context, left_match.end())?;
+			Some((MatchHit::new_at_with_sub_matches(cursor, left_match.length + right_match.length, context.contents_source(), vec![left_match, right_match]), value))
+		})
+	}
+
+	/// Match the given untyped expression followed by this expression, keeping only the value of this one.
+	pub fn after<U:TextPredicate + 'static>(self, preceding:U) -> TypedExpr<T> {
+		TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let left_match:MatchHit = preceding.match_text_at(context, cursor)?;
+			let (right_match, value) = self.match_value_at(context, left_match.end())?;
+			Some((MatchHit::new_at_with_sub_matches(cursor, left_match.length + right_match.length, context.contents_source(), vec![left_match, right_match]), value))
+		})
+	}
+
+
+
+	/* CHOICE METHODS */
+
+	/// Match the first of the given alternatives that matches, in order.
+	pub fn choice<U:IntoIterator<Item = TypedExpr<T>>>(alternatives:U) -> TypedExpr<T> {
+		let alternatives:Vec<TypedExpr<T>> = alternatives.into_iter().collect();
+		TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			alternatives.iter().find_map(|alternative| alternative.match_value_at(context, cursor))
+		})
+	}
+
+
+
+	/* REPETITION METHODS */
+
+	/// Repeat this expression as many times as possible within the given range of counts, collecting the values. Will return None when matched fewer times than the range allows.
+	pub fn repeat<U:RangeBounds<usize>>(self, count_range:U) -> TypedExpr<Vec<T>> {
+		let (min_count, max_count) = MatchExpr::count_bounds(&count_range);
+		TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let mut end:usize = cursor;
+			let mut sub_matches:Vec<MatchHit> = Vec::new();
+			let mut values:Vec<T> = Vec::new();
+			while values.len() < max_count {
+				match self.match_value_at(context, end) {
+					Some((match_result, value)) => {
+						let length:usize = match_result.length;
+						end += length;
+						sub_matches.push(match_result);
+						values.push(value);
+						if length == 0 {
+							break;
+						}
+					},
+					None => break
+				}
+			}
+			// A repetition of length 0 would repeat identically, so it satisfies any minimum count.
+			if values.len() >= min_count || sub_matches.last().is_some_and(|last| last.length == 0) {
+				Some((MatchHit::new_at_with_sub_matches(cursor, end - cursor, context.contents_source(), sub_matches), values))
+			} else {
+				None
+			}
+		})
+	}
+
+	/// Match one or more items separated by the given untyped separator, collecting the values of the items.
+	pub fn separated_by<U:TextPredicate + 'static>(self, separator:U) -> TypedExpr<Vec<T>> {
+		TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			let (first_match, first_value) = self.match_value_at(context, cursor)?;
+			let mut end:usize = first_match.end();
+			let mut sub_matches:Vec<MatchHit> = vec![first_match];
+			let mut values:Vec<T> = vec![first_value];
+			while let Some(separator_match) = separator.match_text_at(context, end) {
+				match self.match_value_at(context, separator_match.end()) {
+					Some((item_match, value)) if item_match.end() > end => {
+						end = item_match.end();
+						sub_matches.push(item_match);
+						values.push(value);
+					},
+					_ => break
+				}
+			}
+			Some((MatchHit::new_at_with_list(cursor, end - cursor, context.contents_source(), sub_matches), values))
+		})
+	}
+
+	/// Try to match this expression, producing None with a match of length 0 on mismatch.
+	pub fn optional(self) -> TypedExpr<Option<T>> {
+		TypedExpr::positional(move |context:&MatchContext, cursor:usize| {
+			Some(match self.match_value_at(context, cursor) {
+				Some((match_result, value)) => (match_result, Some(value)),
+				None => (MatchHit::new_at(cursor, 0, context.contents_source()), None)
+			})
+		})
+	}
+
+
+
+	/* CONVERSION METHODS */
+
+	/// Get an untyped match-expression matching the same text, discarding the values.
+	pub fn expr(&self) -> MatchExpr {
+		MatchExpr::new(self.clone())
+	}
+}
+impl<T> Clone for TypedExpr<T> {
+	fn clone(&self) -> Self {
+		TypedExpr(self.0.clone())
+	}
+}
+impl<T:'static> TextPredicate for TypedExpr<T> {
+	fn match_text_at(&self, context:&MatchContext, cursor:usize) -> Option<MatchHit> {
+		self.match_value_at(context, cursor).map(|(result, _)| result)
+	}
+}
+impl<T:'static, U:'static> Add<TypedExpr<U>> for TypedExpr<T> {
+	type Output = TypedExpr<(T, U)>;
+
+	fn add(self, rhs:TypedExpr<U>) -> Self::Output {
+		self.then(rhs)
+	}
+}
+impl<T:'static> BitOr<TypedExpr<T>> for TypedExpr<T> {
+	type Output = TypedExpr<T>;
+
+	fn bitor(self, rhs:TypedExpr<T>) -> Self::Output {
+		TypedExpr::choice([self, rhs])
+	}
+}
\ No newline at end of file
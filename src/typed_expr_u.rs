@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Expectation, MatchExpr, MatchHit, TextPredicate, TypedExpr };
+
+
+
+	#[derive(Clone, PartialEq, Debug)]
+	enum Value {
+		Number(u64),
+		Boolean(bool),
+		List(Vec<Value>)
+	}
+
+	fn number() -> TypedExpr<u64> {
+		MatchExpr::map(MatchExpr::unsigned_integer(), |result:&MatchHit| result.contents.parse::<u64>().unwrap())
+	}
+
+
+
+	/* COMBINATOR TESTS */
+
+	#[test]
+	fn test_typed_expr_map() {
+		assert_eq!(number().parse_value("123abc"), Some(123));
+		assert_eq!(number().map(|value| value * 2).parse_value("21"), Some(42));
+		assert_eq!(number().map_with_hit(|result, value| (result.span(), value)).parse_value("7"), Some((0..1, 7)));
+		assert_eq!(number().parse_value("abc"), None);
+	}
+
+	#[test]
+	fn test_typed_expr_sequence() {
+		let pair:TypedExpr<(String, u64)> = MatchExpr::map(MatchExpr::word(), |result:&MatchHit| result.contents.clone()).then_ignore('=') + number();
+		assert_eq!(pair.parse_value("age=42"), Some(("age".to_string(), 42)));
+		assert_eq!(pair.parse_value("age=x"), None);
+
+		let range:TypedExpr<(u64, u64)> = number().then_ignore("..") + number().after(MatchExpr::optional('='));
+		assert_eq!(range.parse_value("1..=5"), Some((1, 5)));
+		assert_eq!(range.parse_value("1..5"), Some((1, 5)));
+	}
+
+	#[test]
+	fn test_typed_expr_repetition() {
+		let list:TypedExpr<Vec<u64>> = number().separated_by(MatchExpr::new(',') + MatchExpr::optional(' '));
+		assert_eq!(list.parse_value("1, 2,3"), Some(vec![1, 2, 3]));
+		assert_eq!(number().then_ignore(' ').repeat(2..=3).parse_value("1 2 3 4 "), Some(vec![1, 2, 3]));
+		assert_eq!(number().then_ignore(' ').repeat(2..).parse_value("1 "), None);
+		assert_eq!(number().optional().parse_value("x"), Some(None));
+		assert_eq!(number().optional().parse_value("5"), Some(Some(5)));
+	}
+
+	#[test]
+	fn test_typed_expr_untyped_use() {
+		// Typed expressions are predicates themselves, so they can be used in untyped expressions.
+		let untyped:MatchExpr = MatchExpr::new('[') + number().expr() + ']';
+		assert_eq!(untyped.match_text("[12]").unwrap().length, 4);
+		assert_eq!(number().match_text("12").unwrap().contents, "12");
+	}
+
+
+
+	/* AST TESTS */
+
+	#[test]
+	fn test_typed_expr_enum_ast() {
+		let value:TypedExpr<Value> = TypedExpr::recursive(|value| {
+			let boolean:TypedExpr<Value> = MatchExpr::map("true", |_| Value::Boolean(true)) | MatchExpr::map("false", |_| Value::Boolean(false));
+			let list:TypedExpr<Value> = value.separated_by(',').optional().after('[').then_ignore(']').map(|values| Value::List(values.unwrap_or_default()));
+			TypedExpr::choice([number().map(Value::Number), boolean, list])
+		});
+		assert_eq!(value.parse_complete_value("[1,[true,[]],false]"), Ok(Value::List(vec![
+			Value::Number(1),
+			Value::List(vec![Value::Boolean(true), Value::List(Vec::new())]),
+			Value::Boolean(false)
+		])));
+
+		let failure = value.parse_complete_value("[1,2]x").unwrap_err();
+		assert_eq!(failure.position, 5);
+		assert!(failure.expected.contains(&Expectation::EndOfInput));
+	}
+}
\ No newline at end of file
@@ -0,0 +1,1568 @@
+// Generated from the Unicode Character Database, version 14.0.0.
+use std::cmp::Ordering;
+use crate::GeneralCategory::{ self, * };
+
+
+
+/// Find the index of the range containing the given character in the given sorted, non-overlapping ranges.
+fn find_range<T>(char:char, ranges:&[T], bounds:fn(&T) -> (char, char)) -> Option<usize> {
+	ranges.binary_search_by(|range| {
+		let (start, end) = bounds(range);
+		if char < start {
+			Ordering::Greater
+		} else if char > end {
+			Ordering::Less
+		} else {
+			Ordering::Equal
+		}
+	}).ok()
+}
+
+/// Check if the given character lies within any of the given sorted, non-overlapping ranges.
+pub(crate) fn in_ranges(char:char, ranges:&[(char, char)]) -> bool {
+	find_range(char, ranges, |range| *range).is_some()
+}
+
+/// Get the general category of the given character.
+pub(crate) fn general_category(char:char) -> GeneralCategory {
+	find_range(char, GENERAL_CATEGORIES, |(start, end, _)| (*start, *end)).map(|index| GENERAL_CATEGORIES[index].2).unwrap_or(Unassigned)
+}
+
+
+
+/// Characters that never start a grapheme cluster: combining marks (Mn, Mc, Me), the zero width joiner, emoji modifiers and tag characters.
+pub(crate) const GRAPHEME_EXTEND:&[(char, char)] = &[
+	('\u{300}', '\u{36F}'), ('\u{483}', '\u{489}'), ('\u{591}', '\u{5BD}'), ('\u{5BF}', '\u{5BF}'),
+	('\u{5C1}', '\u{5C2}'), ('\u{5C4}', '\u{5C5}'), ('\u{5C7}', '\u{5C7}'), ('\u{610}', '\u{61A}'),
+	('\u{64B}', '\u{65F}'), ('\u{670}', '\u{670}'), ('\u{6D6}', '\u{6DC}'), ('\u{6DF}', '\u{6E4}'),
+	('\u{6E7}', '\u{6E8}'), ('\u{6EA}', '\u{6ED}'), ('\u{711}', '\u{711}'), ('\u{730}', '\u{74A}'),
+	('\u{7A6}', '\u{7B0}'), ('\u{7EB}', '\u{7F3}'), ('\u{7FD}', '\u{7FD}'), ('\u{816}', '\u{819}'),
+	('\u{81B}', '\u{823}'), ('\u{825}', '\u{827}'), ('\u{829}', '\u{82D}'), ('\u{859}', '\u{85B}'),
+	('\u{898}', '\u{89F}'), ('\u{8CA}', '\u{8E1}'), ('\u{8E3}', '\u{903}'), ('\u{93A}', '\u{93C}'),
+	('\u{93E}', '\u{94F}'), ('\u{951}', '\u{957}'), ('\u{962}', '\u{963}'), ('\u{981}', '\u{983}'),
+	('\u{9BC}', '\u{9BC}'), ('\u{9BE}', '\u{9C4}'), ('\u{9C7}', '\u{9C8}'), ('\u{9CB}', '\u{9CD}'),
+	('\u{9D7}', '\u{9D7}'), ('\u{9E2}', '\u{9E3}'), ('\u{9FE}', '\u{9FE}'), ('\u{A01}', '\u{A03}'),
+	('\u{A3C}', '\u{A3C}'), ('\u{A3E}', '\u{A42}'), ('\u{A47}', '\u{A48}'), ('\u{A4B}', '\u{A4D}'),
+	('\u{A51}', '\u{A51}'), ('\u{A70}', '\u{A71}'), ('\u{A75}', '\u{A75}'), ('\u{A81}', '\u{A83}'),
+	('\u{ABC}', '\u{ABC}'), ('\u{ABE}', '\u{AC5}'), ('\u{AC7}', '\u{AC9}'), ('\u{ACB}', '\u{ACD}'),
+	('\u{AE2}', '\u{AE3}'), ('\u{AFA}', '\u{AFF}'), ('\u{B01}', '\u{B03}'), ('\u{B3C}', '\u{B3C}'),
+	('\u{B3E}', '\u{B44}'), ('\u{B47}', '\u{B48}'), ('\u{B4B}', '\u{B4D}'), ('\u{B55}', '\u{B57}'),
+	('\u{B62}', '\u{B63}'), ('\u{B82}', '\u{B82}'), ('\u{BBE}', '\u{BC2}'), ('\u{BC6}', '\u{BC8}'),
+	('\u{BCA}', '\u{BCD}'), ('\u{BD7}', '\u{BD7}'), ('\u{C00}', '\u{C04}'), ('\u{C3C}', '\u{C3C}'),
+	('\u{C3E}', '\u{C44}'), ('\u{C46}', '\u{C48}'), ('\u{C4A}', '\u{C4D}'), ('\u{C55}', '\u{C56}'),
+	('\u{C62}', '\u{C63}'), ('\u{C81}', '\u{C83}'), ('\u{CBC}', '\u{CBC}'), ('\u{CBE}', '\u{CC4}'),
+	('\u{CC6}', '\u{CC8}'), ('\u{CCA}', '\u{CCD}'), ('\u{CD5}', '\u{CD6}'), ('\u{CE2}', '\u{CE3}'),
+	('\u{D00}', '\u{D03}'), ('\u{D3B}', '\u{D3C}'), ('\u{D3E}', '\u{D44}'), ('\u{D46}', '\u{D48}'),
+	('\u{D4A}', '\u{D4D}'), ('\u{D57}', '\u{D57}'), ('\u{D62}', '\u{D63}'), ('\u{D81}', '\u{D83}'),
+	('\u{DCA}', '\u{DCA}'), ('\u{DCF}', '\u{DD4}'), ('\u{DD6}', '\u{DD6}'), ('\u{DD8}', '\u{DDF}'),
+	('\u{DF2}', '\u{DF3}'), ('\u{E31}', '\u{E31}'), ('\u{E34}', '\u{E3A}'), ('\u{E47}', '\u{E4E}'),
+	('\u{EB1}', '\u{EB1}'), ('\u{EB4}', '\u{EBC}'), ('\u{EC8}', '\u{ECD}'), ('\u{F18}', '\u{F19}'),
+	('\u{F35}', '\u{F35}'), ('\u{F37}', '\u{F37}'), ('\u{F39}', '\u{F39}'), ('\u{F3E}', '\u{F3F}'),
+	('\u{F71}', '\u{F84}'), ('\u{F86}', '\u{F87}'), ('\u{F8D}', '\u{F97}'), ('\u{F99}', '\u{FBC}'),
+	('\u{FC6}', '\u{FC6}'), ('\u{102B}', '\u{103E}'), ('\u{1056}', '\u{1059}'), ('\u{105E}', '\u{1060}'),
+	('\u{1062}', '\u{1064}'), ('\u{1067}', '\u{106D}'), ('\u{1071}', '\u{1074}'), ('\u{1082}', '\u{108D}'),
+	('\u{108F}', '\u{108F}'), ('\u{109A}', '\u{109D}'), ('\u{135D}', '\u{135F}'), ('\u{1712}', '\u{1715}'),
+	('\u{1732}', '\u{1734}'), ('\u{1752}', '\u{1753}'), ('\u{1772}', '\u{1773}'), ('\u{17B4}', '\u{17D3}'),
+	('\u{17DD}', '\u{17DD}'), ('\u{180B}', '\u{180D}'), ('\u{180F}', '\u{180F}'), ('\u{1885}', '\u{1886}'),
+	('\u{18A9}', '\u{18A9}'), ('\u{1920}', '\u{192B}'), ('\u{1930}', '\u{193B}'), ('\u{1A17}', '\u{1A1B}'),
+	('\u{1A55}', '\u{1A5E}'), ('\u{1A60}', '\u{1A7C}'), ('\u{1A7F}', '\u{1A7F}'), ('\u{1AB0}', '\u{1ACE}'),
+	('\u{1B00}', '\u{1B04}'), ('\u{1B34}', '\u{1B44}'), ('\u{1B6B}', '\u{1B73}'), ('\u{1B80}', '\u{1B82}'),
+	('\u{1BA1}', '\u{1BAD}'), ('\u{1BE6}', '\u{1BF3}'), ('\u{1C24}', '\u{1C37}'), ('\u{1CD0}', '\u{1CD2}'),
+	('\u{1CD4}', '\u{1CE8}'), ('\u{1CED}', '\u{1CED}'), ('\u{1CF4}', '\u{1CF4}'), ('\u{1CF7}', '\u{1CF9}'),
+	('\u{1DC0}', '\u{1DFF}'), ('\u{200D}', '\u{200D}'), ('\u{20D0}', '\u{20F0}'), ('\u{2CEF}', '\u{2CF1}'),
+	('\u{2D7F}', '\u{2D7F}'), ('\u{2DE0}', '\u{2DFF}'), ('\u{302A}', '\u{302F}'), ('\u{3099}', '\u{309A}'),
+	('\u{A66F}', '\u{A672}'), ('\u{A674}', '\u{A67D}'), ('\u{A69E}', '\u{A69F}'), ('\u{A6F0}', '\u{A6F1}'),
+	('\u{A802}', '\u{A802}'), ('\u{A806}', '\u{A806}'), ('\u{A80B}', '\u{A80B}'), ('\u{A823}', '\u{A827}'),
+	('\u{A82C}', '\u{A82C}'), ('\u{A880}', '\u{A881}'), ('\u{A8B4}', '\u{A8C5}'), ('\u{A8E0}', '\u{A8F1}'),
+	('\u{A8FF}', '\u{A8FF}'), ('\u{A926}', '\u{A92D}'), ('\u{A947}', '\u{A953}'), ('\u{A980}', '\u{A983}'),
+	('\u{A9B3}', '\u{A9C0}'), ('\u{A9E5}', '\u{A9E5}'), ('\u{AA29}', '\u{AA36}'), ('\u{AA43}', '\u{AA43}'),
+	('\u{AA4C}', '\u{AA4D}'), ('\u{AA7B}', '\u{AA7D}'), ('\u{AAB0}', '\u{AAB0}'), ('\u{AAB2}', '\u{AAB4}'),
+	('\u{AAB7}', '\u{AAB8}'), ('\u{AABE}', '\u{AABF}'), ('\u{AAC1}', '\u{AAC1}'), ('\u{AAEB}', '\u{AAEF}'),
+	('\u{AAF5}', '\u{AAF6}'), ('\u{ABE3}', '\u{ABEA}'), ('\u{ABEC}', '\u{ABED}'), ('\u{FB1E}', '\u{FB1E}'),
+	('\u{FE00}', '\u{FE0F}'), ('\u{FE20}', '\u{FE2F}'), ('\u{101FD}', '\u{101FD}'), ('\u{102E0}', '\u{102E0}'),
+	('\u{10376}', '\u{1037A}'), ('\u{10A01}', '\u{10A03}'), ('\u{10A05}', '\u{10A06}'), ('\u{10A0C}', '\u{10A0F}'),
+	('\u{10A38}', '\u{10A3A}'), ('\u{10A3F}', '\u{10A3F}'), ('\u{10AE5}', '\u{10AE6}'), ('\u{10D24}', '\u{10D27}'),
+	('\u{10EAB}', '\u{10EAC}'), ('\u{10F46}', '\u{10F50}'), ('\u{10F82}', '\u{10F85}'), ('\u{11000}', '\u{11002}'),
+	('\u{11038}', '\u{11046}'), ('\u{11070}', '\u{11070}'), ('\u{11073}', '\u{11074}'), ('\u{1107F}', '\u{11082}'),
+	('\u{110B0}', '\u{110BA}'), ('\u{110C2}', '\u{110C2}'), ('\u{11100}', '\u{11102}'), ('\u{11127}', '\u{11134}'),
+	('\u{11145}', '\u{11146}'), ('\u{11173}', '\u{11173}'), ('\u{11180}', '\u{11182}'), ('\u{111B3}', '\u{111C0}'),
+	('\u{111C9}', '\u{111CC}'), ('\u{111CE}', '\u{111CF}'), ('\u{1122C}', '\u{11237}'), ('\u{1123E}', '\u{1123E}'),
+	('\u{112DF}', '\u{112EA}'), ('\u{11300}', '\u{11303}'), ('\u{1133B}', '\u{1133C}'), ('\u{1133E}', '\u{11344}'),
+	('\u{11347}', '\u{11348}'), ('\u{1134B}', '\u{1134D}'), ('\u{11357}', '\u{11357}'), ('\u{11362}', '\u{11363}'),
+	('\u{11366}', '\u{1136C}'), ('\u{11370}', '\u{11374}'), ('\u{11435}', '\u{11446}'), ('\u{1145E}', '\u{1145E}'),
+	('\u{114B0}', '\u{114C3}'), ('\u{115AF}', '\u{115B5}'), ('\u{115B8}', '\u{115C0}'), ('\u{115DC}', '\u{115DD}'),
+	('\u{11630}', '\u{11640}'), ('\u{116AB}', '\u{116B7}'), ('\u{1171D}', '\u{1172B}'), ('\u{1182C}', '\u{1183A}'),
+	('\u{11930}', '\u{11935}'), ('\u{11937}', '\u{11938}'), ('\u{1193B}', '\u{1193E}'), ('\u{11940}', '\u{11940}'),
+	('\u{11942}', '\u{11943}'), ('\u{119D1}', '\u{119D7}'), ('\u{119DA}', '\u{119E0}'), ('\u{119E4}', '\u{119E4}'),
+	('\u{11A01}', '\u{11A0A}'), ('\u{11A33}', '\u{11A39}'), ('\u{11A3B}', '\u{11A3E}'), ('\u{11A47}', '\u{11A47}'),
+	('\u{11A51}', '\u{11A5B}'), ('\u{11A8A}', '\u{11A99}'), ('\u{11C2F}', '\u{11C36}'), ('\u{11C38}', '\u{11C3F}'),
+	('\u{11C92}', '\u{11CA7}'), ('\u{11CA9}', '\u{11CB6}'), ('\u{11D31}', '\u{11D36}'), ('\u{11D3A}', '\u{11D3A}'),
+	('\u{11D3C}', '\u{11D3D}'), ('\u{11D3F}', '\u{11D45}'), ('\u{11D47}', '\u{11D47}'), ('\u{11D8A}', '\u{11D8E}'),
+	('\u{11D90}', '\u{11D91}'), ('\u{11D93}', '\u{11D97}'), ('\u{11EF3}', '\u{11EF6}'), ('\u{16AF0}', '\u{16AF4}'),
+	('\u{16B30}', '\u{16B36}'), ('\u{16F4F}', '\u{16F4F}'), ('\u{16F51}', '\u{16F87}'), ('\u{16F8F}', '\u{16F92}'),
+	('\u{16FE4}', '\u{16FE4}'), ('\u{16FF0}', '\u{16FF1}'), ('\u{1BC9D}', '\u{1BC9E}'), ('\u{1CF00}', '\u{1CF2D}'),
+	('\u{1CF30}', '\u{1CF46}'), ('\u{1D165}', '\u{1D169}'), ('\u{1D16D}', '\u{1D172}'), ('\u{1D17B}', '\u{1D182}'),
+	('\u{1D185}', '\u{1D18B}'), ('\u{1D1AA}', '\u{1D1AD}'), ('\u{1D242}', '\u{1D244}'), ('\u{1DA00}', '\u{1DA36}'),
+	('\u{1DA3B}', '\u{1DA6C}'), ('\u{1DA75}', '\u{1DA75}'), ('\u{1DA84}', '\u{1DA84}'), ('\u{1DA9B}', '\u{1DA9F}'),
+	('\u{1DAA1}', '\u{1DAAF}'), ('\u{1E000}', '\u{1E006}'), ('\u{1E008}', '\u{1E018}'), ('\u{1E01B}', '\u{1E021}'),
+	('\u{1E023}', '\u{1E024}'), ('\u{1E026}', '\u{1E02A}'), ('\u{1E130}', '\u{1E136}'), ('\u{1E2AE}', '\u{1E2AE}'),
+	('\u{1E2EC}', '\u{1E2EF}'), ('\u{1E8D0}', '\u{1E8D6}'), ('\u{1E944}', '\u{1E94A}'), ('\u{1F3FB}', '\u{1F3FF}'),
+	('\u{E0020}', '\u{E007F}'), ('\u{E0100}', '\u{E01EF}')
+];
+
+/// Characters with the XID_Start property.
+pub(crate) const XID_START:&[(char, char)] = &[
+	('\u{41}', '\u{5A}'), ('\u{61}', '\u{7A}'), ('\u{AA}', '\u{AA}'), ('\u{B5}', '\u{B5}'),
+	('\u{BA}', '\u{BA}'), ('\u{C0}', '\u{D6}'), ('\u{D8}', '\u{F6}'), ('\u{F8}', '\u{2C1}'),
+	('\u{2C6}', '\u{2D1}'), ('\u{2E0}', '\u{2E4}'), ('\u{2EC}', '\u{2EC}'), ('\u{2EE}', '\u{2EE}'),
+	('\u{370}', '\u{374}'), ('\u{376}', '\u{377}'), ('\u{37B}', '\u{37D}'), ('\u{37F}', '\u{37F}'),
+	('\u{386}', '\u{386}'), ('\u{388}', '\u{38A}'), ('\u{38C}', '\u{38C}'), ('\u{38E}', '\u{3A1}'),
+	('\u{3A3}', '\u{3F5}'), ('\u{3F7}', '\u{481}'), ('\u{48A}', '\u{52F}'), ('\u{531}', '\u{556}'),
+	('\u{559}', '\u{559}'), ('\u{560}', '\u{588}'), ('\u{5D0}', '\u{5EA}'), ('\u{5EF}', '\u{5F2}'),
+	('\u{620}', '\u{64A}'), ('\u{66E}', '\u{66F}'), ('\u{671}', '\u{6D3}'), ('\u{6D5}', '\u{6D5}'),
+	('\u{6E5}', '\u{6E6}'), ('\u{6EE}', '\u{6EF}'), ('\u{6FA}', '\u{6FC}'), ('\u{6FF}', '\u{6FF}'),
+	('\u{710}', '\u{710}'), ('\u{712}', '\u{72F}'), ('\u{74D}', '\u{7A5}'), ('\u{7B1}', '\u{7B1}'),
+	('\u{7CA}', '\u{7EA}'), ('\u{7F4}', '\u{7F5}'), ('\u{7FA}', '\u{7FA}'), ('\u{800}', '\u{815}'),
+	('\u{81A}', '\u{81A}'), ('\u{824}', '\u{824}'), ('\u{828}', '\u{828}'), ('\u{840}', '\u{858}'),
+	('\u{860}', '\u{86A}'), ('\u{870}', '\u{887}'), ('\u{889}', '\u{88E}'), ('\u{8A0}', '\u{8C9}'),
+	('\u{904}', '\u{939}'), ('\u{93D}', '\u{93D}'), ('\u{950}', '\u{950}'), ('\u{958}', '\u{961}'),
+	('\u{971}', '\u{980}'), ('\u{985}', '\u{98C}'), ('\u{98F}', '\u{990}'), ('\u{993}', '\u{9A8}'),
+	('\u{9AA}', '\u{9B0}'), ('\u{9B2}', '\u{9B2}'), ('\u{9B6}', '\u{9B9}'), ('\u{9BD}', '\u{9BD}'),
+	('\u{9CE}', '\u{9CE}'), ('\u{9DC}', '\u{9DD}'), ('\u{9DF}', '\u{9E1}'), ('\u{9F0}', '\u{9F1}'),
+	('\u{9FC}', '\u{9FC}'), ('\u{A05}', '\u{A0A}'), ('\u{A0F}', '\u{A10}'), ('\u{A13}', '\u{A28}'),
+	('\u{A2A}', '\u{A30}'), ('\u{A32}', '\u{A33}'), ('\u{A35}', '\u{A36}'), ('\u{A38}', '\u{A39}'),
+	('\u{A59}', '\u{A5C}'), ('\u{A5E}', '\u{A5E}'), ('\u{A72}', '\u{A74}'), ('\u{A85}', '\u{A8D}'),
+	('\u{A8F}', '\u{A91}'), ('\u{A93}', '\u{AA8}'), ('\u{AAA}', '\u{AB0}'), ('\u{AB2}', '\u{AB3}'),
+	('\u{AB5}', '\u{AB9}'), ('\u{ABD}', '\u{ABD}'), ('\u{AD0}', '\u{AD0}'), ('\u{AE0}', '\u{AE1}'),
+	('\u{AF9}', '\u{AF9}'), ('\u{B05}', '\u{B0C}'), ('\u{B0F}', '\u{B10}'), ('\u{B13}', '\u{B28}'),
+	('\u{B2A}', '\u{B30}'), ('\u{B32}', '\u{B33}'), ('\u{B35}', '\u{B39}'), ('\u{B3D}', '\u{B3D}'),
+	('\u{B5C}', '\u{B5D}'), ('\u{B5F}', '\u{B61}'), ('\u{B71}', '\u{B71}'), ('\u{B83}', '\u{B83}'),
+	('\u{B85}', '\u{B8A}'), ('\u{B8E}', '\u{B90}'), ('\u{B92}', '\u{B95}'), ('\u{B99}', '\u{B9A}'),
+	('\u{B9C}', '\u{B9C}'), ('\u{B9E}', '\u{B9F}'), ('\u{BA3}', '\u{BA4}'), ('\u{BA8}', '\u{BAA}'),
+	('\u{BAE}', '\u{BB9}'), ('\u{BD0}', '\u{BD0}'), ('\u{C05}', '\u{C0C}'), ('\u{C0E}', '\u{C10}'),
+	('\u{C12}', '\u{C28}'), ('\u{C2A}', '\u{C39}'), ('\u{C3D}', '\u{C3D}'), ('\u{C58}', '\u{C5A}'),
+	('\u{C5D}', '\u{C5D}'), ('\u{C60}', '\u{C61}'), ('\u{C80}', '\u{C80}'), ('\u{C85}', '\u{C8C}'),
+	('\u{C8E}', '\u{C90}'), ('\u{C92}', '\u{CA8}'), ('\u{CAA}', '\u{CB3}'), ('\u{CB5}', '\u{CB9}'),
+	('\u{CBD}', '\u{CBD}'), ('\u{CDD}', '\u{CDE}'), ('\u{CE0}', '\u{CE1}'), ('\u{CF1}', '\u{CF2}'),
+	('\u{D04}', '\u{D0C}'), ('\u{D0E}', '\u{D10}'), ('\u{D12}', '\u{D3A}'), ('\u{D3D}', '\u{D3D}'),
+	('\u{D4E}', '\u{D4E}'), ('\u{D54}', '\u{D56}'), ('\u{D5F}', '\u{D61}'), ('\u{D7A}', '\u{D7F}'),
+	('\u{D85}', '\u{D96}'), ('\u{D9A}', '\u{DB1}'), ('\u{DB3}', '\u{DBB}'), ('\u{DBD}', '\u{DBD}'),
+	('\u{DC0}', '\u{DC6}'), ('\u{E01}', '\u{E30}'), ('\u{E32}', '\u{E32}'), ('\u{E40}', '\u{E46}'),
+	('\u{E81}', '\u{E82}'), ('\u{E84}', '\u{E84}'), ('\u{E86}', '\u{E8A}'), ('\u{E8C}', '\u{EA3}'),
+	('\u{EA5}', '\u{EA5}'), ('\u{EA7}', '\u{EB0}'), ('\u{EB2}', '\u{EB2}'), ('\u{EBD}', '\u{EBD}'),
+	('\u{EC0}', '\u{EC4}'), ('\u{EC6}', '\u{EC6}'), ('\u{EDC}', '\u{EDF}'), ('\u{F00}', '\u{F00}'),
+	('\u{F40}', '\u{F47}'), ('\u{F49}', '\u{F6C}'), ('\u{F88}', '\u{F8C}'), ('\u{1000}', '\u{102A}'),
+	('\u{103F}', '\u{103F}'), ('\u{1050}', '\u{1055}'), ('\u{105A}', '\u{105D}'), ('\u{1061}', '\u{1061}'),
+	('\u{1065}', '\u{1066}'), ('\u{106E}', '\u{1070}'), ('\u{1075}', '\u{1081}'), ('\u{108E}', '\u{108E}'),
+	('\u{10A0}', '\u{10C5}'), ('\u{10C7}', '\u{10C7}'), ('\u{10CD}', '\u{10CD}'), ('\u{10D0}', '\u{10FA}'),
+	('\u{10FC}', '\u{1248}'), ('\u{124A}', '\u{124D}'), ('\u{1250}', '\u{1256}'), ('\u{1258}', '\u{1258}'),
+	('\u{125A}', '\u{125D}'), ('\u{1260}', '\u{1288}'), ('\u{128A}', '\u{128D}'), ('\u{1290}', '\u{12B0}'),
+	('\u{12B2}', '\u{12B5}'), ('\u{12B8}', '\u{12BE}'), ('\u{12C0}', '\u{12C0}'), ('\u{12C2}', '\u{12C5}'),
+	('\u{12C8}', '\u{12D6}'), ('\u{12D8}', '\u{1310}'), ('\u{1312}', '\u{1315}'), ('\u{1318}', '\u{135A}'),
+	('\u{1380}', '\u{138F}'), ('\u{13A0}', '\u{13F5}'), ('\u{13F8}', '\u{13FD}'), ('\u{1401}', '\u{166C}'),
+	('\u{166F}', '\u{167F}'), ('\u{1681}', '\u{169A}'), ('\u{16A0}', '\u{16EA}'), ('\u{16EE}', '\u{16F8}'),
+	('\u{1700}', '\u{1711}'), ('\u{171F}', '\u{1731}'), ('\u{1740}', '\u{1751}'), ('\u{1760}', '\u{176C}'),
+	('\u{176E}', '\u{1770}'), ('\u{1780}', '\u{17B3}'), ('\u{17D7}', '\u{17D7}'), ('\u{17DC}', '\u{17DC}'),
+	('\u{1820}', '\u{1878}'), ('\u{1880}', '\u{18A8}'), ('\u{18AA}', '\u{18AA}'), ('\u{18B0}', '\u{18F5}'),
+	('\u{1900}', '\u{191E}'), ('\u{1950}', '\u{196D}'), ('\u{1970}', '\u{1974}'), ('\u{1980}', '\u{19AB}'),
+	('\u{19B0}', '\u{19C9}'), ('\u{1A00}', '\u{1A16}'), ('\u{1A20}', '\u{1A54}'), ('\u{1AA7}', '\u{1AA7}'),
+	('\u{1B05}', '\u{1B33}'), ('\u{1B45}', '\u{1B4C}'), ('\u{1B83}', '\u{1BA0}'), ('\u{1BAE}', '\u{1BAF}'),
+	('\u{1BBA}', '\u{1BE5}'), ('\u{1C00}', '\u{1C23}'), ('\u{1C4D}', '\u{1C4F}'), ('\u{1C5A}', '\u{1C7D}'),
+	('\u{1C80}', '\u{1C88}'), ('\u{1C90}', '\u{1CBA}'), ('\u{1CBD}', '\u{1CBF}'), ('\u{1CE9}', '\u{1CEC}'),
+	('\u{1CEE}', '\u{1CF3}'), ('\u{1CF5}', '\u{1CF6}'), ('\u{1CFA}', '\u{1CFA}'), ('\u{1D00}', '\u{1DBF}'),
+	('\u{1E00}', '\u{1F15}'), ('\u{1F18}', '\u{1F1D}'), ('\u{1F20}', '\u{1F45}'), ('\u{1F48}', '\u{1F4D}'),
+	('\u{1F50}', '\u{1F57}'), ('\u{1F59}', '\u{1F59}'), ('\u{1F5B}', '\u{1F5B}'), ('\u{1F5D}', '\u{1F5D}'),
+	('\u{1F5F}', '\u{1F7D}'), ('\u{1F80}', '\u{1FB4}'), ('\u{1FB6}', '\u{1FBC}'), ('\u{1FBE}', '\u{1FBE}'),
+	('\u{1FC2}', '\u{1FC4}'), ('\u{1FC6}', '\u{1FCC}'), ('\u{1FD0}', '\u{1FD3}'), ('\u{1FD6}', '\u{1FDB}'),
+	('\u{1FE0}', '\u{1FEC}'), ('\u{1FF2}', '\u{1FF4}'), ('\u{1FF6}', '\u{1FFC}'), ('\u{2071}', '\u{2071}'),
+	('\u{207F}', '\u{207F}'), ('\u{2090}', '\u{209C}'), ('\u{2102}', '\u{2102}'), ('\u{2107}', '\u{2107}'),
+	('\u{210A}', '\u{2113}'), ('\u{2115}', '\u{2115}'), ('\u{2118}', '\u{211D}'), ('\u{2124}', '\u{2124}'),
+	('\u{2126}', '\u{2126}'), ('\u{2128}', '\u{2128}'), ('\u{212A}', '\u{2139}'), ('\u{213C}', '\u{213F}'),
+	('\u{2145}', '\u{2149}'), ('\u{214E}', '\u{214E}'), ('\u{2160}', '\u{2188}'), ('\u{2C00}', '\u{2CE4}'),
+	('\u{2CEB}', '\u{2CEE}'), ('\u{2CF2}', '\u{2CF3}'), ('\u{2D00}', '\u{2D25}'), ('\u{2D27}', '\u{2D27}'),
+	('\u{2D2D}', '\u{2D2D}'), ('\u{2D30}', '\u{2D67}'), ('\u{2D6F}', '\u{2D6F}'), ('\u{2D80}', '\u{2D96}'),
+	('\u{2DA0}', '\u{2DA6}'), ('\u{2DA8}', '\u{2DAE}'), ('\u{2DB0}', '\u{2DB6}'), ('\u{2DB8}', '\u{2DBE}'),
+	('\u{2DC0}', '\u{2DC6}'), ('\u{2DC8}', '\u{2DCE}'), ('\u{2DD0}', '\u{2DD6}'), ('\u{2DD8}', '\u{2DDE}'),
+	('\u{3005}', '\u{3007}'), ('\u{3021}', '\u{3029}'), ('\u{3031}', '\u{3035}'), ('\u{3038}', '\u{303C}'),
+	('\u{3041}', '\u{3096}'), ('\u{309D}', '\u{309F}'), ('\u{30A1}', '\u{30FA}'), ('\u{30FC}', '\u{30FF}'),
+	('\u{3105}', '\u{312F}'), ('\u{3131}', '\u{318E}'), ('\u{31A0}', '\u{31BF}'), ('\u{31F0}', '\u{31FF}'),
+	('\u{3400}', '\u{4DBF}'), ('\u{4E00}', '\u{A48C}'), ('\u{A4D0}', '\u{A4FD}'), ('\u{A500}', '\u{A60C}'),
+	('\u{A610}', '\u{A61F}'), ('\u{A62A}', '\u{A62B}'), ('\u{A640}', '\u{A66E}'), ('\u{A67F}', '\u{A69D}'),
+	('\u{A6A0}', '\u{A6EF}'), ('\u{A717}', '\u{A71F}'), ('\u{A722}', '\u{A788}'), ('\u{A78B}', '\u{A7CA}'),
+	('\u{A7D0}', '\u{A7D1}'), ('\u{A7D3}', '\u{A7D3}'), ('\u{A7D5}', '\u{A7D9}'), ('\u{A7F2}', '\u{A801}'),
+	('\u{A803}', '\u{A805}'), ('\u{A807}', '\u{A80A}'), ('\u{A80C}', '\u{A822}'), ('\u{A840}', '\u{A873}'),
+	('\u{A882}', '\u{A8B3}'), ('\u{A8F2}', '\u{A8F7}'), ('\u{A8FB}', '\u{A8FB}'), ('\u{A8FD}', '\u{A8FE}'),
+	('\u{A90A}', '\u{A925}'), ('\u{A930}', '\u{A946}'), ('\u{A960}', '\u{A97C}'), ('\u{A984}', '\u{A9B2}'),
+	('\u{A9CF}', '\u{A9CF}'), ('\u{A9E0}', '\u{A9E4}'), ('\u{A9E6}', '\u{A9EF}'), ('\u{A9FA}', '\u{A9FE}'),
+	('\u{AA00}', '\u{AA28}'), ('\u{AA40}', '\u{AA42}'), ('\u{AA44}', '\u{AA4B}'), ('\u{AA60}', '\u{AA76}'),
+	('\u{AA7A}', '\u{AA7A}'), ('\u{AA7E}', '\u{AAAF}'), ('\u{AAB1}', '\u{AAB1}'), ('\u{AAB5}', '\u{AAB6}'),
+	('\u{AAB9}', '\u{AABD}'), ('\u{AAC0}', '\u{AAC0}'), ('\u{AAC2}', '\u{AAC2}'), ('\u{AADB}', '\u{AADD}'),
+	('\u{AAE0}', '\u{AAEA}'), ('\u{AAF2}', '\u{AAF4}'), ('\u{AB01}', '\u{AB06}'), ('\u{AB09}', '\u{AB0E}'),
+	('\u{AB11}', '\u{AB16}'), ('\u{AB20}', '\u{AB26}'), ('\u{AB28}', '\u{AB2E}'), ('\u{AB30}', '\u{AB5A}'),
+	('\u{AB5C}', '\u{AB69}'), ('\u{AB70}', '\u{ABE2}'), ('\u{AC00}', '\u{D7A3}'), ('\u{D7B0}', '\u{D7C6}'),
+	('\u{D7CB}', '\u{D7FB}'), ('\u{F900}', '\u{FA6D}'), ('\u{FA70}', '\u{FAD9}'), ('\u{FB00}', '\u{FB06}'),
+	('\u{FB13}', '\u{FB17}'), ('\u{FB1D}', '\u{FB1D}'), ('\u{FB1F}', '\u{FB28}'), ('\u{FB2A}', '\u{FB36}'),
+	('\u{FB38}', '\u{FB3C}'), ('\u{FB3E}', '\u{FB3E}'), ('\u{FB40}', '\u{FB41}'), ('\u{FB43}', '\u{FB44}'),
+	('\u{FB46}', '\u{FBB1}'), ('\u{FBD3}', '\u{FC5D}'), ('\u{FC64}', '\u{FD3D}'), ('\u{FD50}', '\u{FD8F}'),
+	('\u{FD92}', '\u{FDC7}'), ('\u{FDF0}', '\u{FDF9}'), ('\u{FE71}', '\u{FE71}'), ('\u{FE73}', '\u{FE73}'),
+	('\u{FE77}', '\u{FE77}'), ('\u{FE79}', '\u{FE79}'), ('\u{FE7B}', '\u{FE7B}'), ('\u{FE7D}', '\u{FE7D}'),
+	('\u{FE7F}', '\u{FEFC}'), ('\u{FF21}', '\u{FF3A}'), ('\u{FF41}', '\u{FF5A}'), ('\u{FF66}', '\u{FF9D}'),
+	('\u{FFA0}', '\u{FFBE}'), ('\u{FFC2}', '\u{FFC7}'), ('\u{FFCA}', '\u{FFCF}'), ('\u{FFD2}', '\u{FFD7}'),
+	('\u{FFDA}', '\u{FFDC}'), ('\u{10000}', '\u{1000B}'), ('\u{1000D}', '\u{10026}'), ('\u{10028}', '\u{1003A}'),
+	('\u{1003C}', '\u{1003D}'), ('\u{1003F}', '\u{1004D}'), ('\u{10050}', '\u{1005D}'), ('\u{10080}', '\u{100FA}'),
+	('\u{10140}', '\u{10174}'), ('\u{10280}', '\u{1029C}'), ('\u{102A0}', '\u{102D0}'), ('\u{10300}', '\u{1031F}'),
+	('\u{1032D}', '\u{1034A}'), ('\u{10350}', '\u{10375}'), ('\u{10380}', '\u{1039D}'), ('\u{103A0}', '\u{103C3}'),
+	('\u{103C8}', '\u{103CF}'), ('\u{103D1}', '\u{103D5}'), ('\u{10400}', '\u{1049D}'), ('\u{104B0}', '\u{104D3}'),
+	('\u{104D8}', '\u{104FB}'), ('\u{10500}', '\u{10527}'), ('\u{10530}', '\u{10563}'), ('\u{10570}', '\u{1057A}'),
+	('\u{1057C}', '\u{1058A}'), ('\u{1058C}', '\u{10592}'), ('\u{10594}', '\u{10595}'), ('\u{10597}', '\u{105A1}'),
+	('\u{105A3}', '\u{105B1}'), ('\u{105B3}', '\u{105B9}'), ('\u{105BB}', '\u{105BC}'), ('\u{10600}', '\u{10736}'),
+	('\u{10740}', '\u{10755}'), ('\u{10760}', '\u{10767}'), ('\u{10780}', '\u{10785}'), ('\u{10787}', '\u{107B0}'),
+	('\u{107B2}', '\u{107BA}'), ('\u{10800}', '\u{10805}'), ('\u{10808}', '\u{10808}'), ('\u{1080A}', '\u{10835}'),
+	('\u{10837}', '\u{10838}'), ('\u{1083C}', '\u{1083C}'), ('\u{1083F}', '\u{10855}'), ('\u{10860}', '\u{10876}'),
+	('\u{10880}', '\u{1089E}'), ('\u{108E0}', '\u{108F2}'), ('\u{108F4}', '\u{108F5}'), ('\u{10900}', '\u{10915}'),
+	('\u{10920}', '\u{10939}'), ('\u{10980}', '\u{109B7}'), ('\u{109BE}', '\u{109BF}'), ('\u{10A00}', '\u{10A00}'),
+	('\u{10A10}', '\u{10A13}'), ('\u{10A15}', '\u{10A17}'), ('\u{10A19}', '\u{10A35}'), ('\u{10A60}', '\u{10A7C}'),
+	('\u{10A80}', '\u{10A9C}'), ('\u{10AC0}', '\u{10AC7}'), ('\u{10AC9}', '\u{10AE4}'), ('\u{10B00}', '\u{10B35}'),
+	('\u{10B40}', '\u{10B55}'), ('\u{10B60}', '\u{10B72}'), ('\u{10B80}', '\u{10B91}'), ('\u{10C00}', '\u{10C48}'),
+	('\u{10C80}', '\u{10CB2}'), ('\u{10CC0}', '\u{10CF2}'), ('\u{10D00}', '\u{10D23}'), ('\u{10E80}', '\u{10EA9}'),
+	('\u{10EB0}', '\u{10EB1}'), ('\u{10F00}', '\u{10F1C}'), ('\u{10F27}', '\u{10F27}'), ('\u{10F30}', '\u{10F45}'),
+	('\u{10F70}', '\u{10F81}'), ('\u{10FB0}', '\u{10FC4}'), ('\u{10FE0}', '\u{10FF6}'), ('\u{11003}', '\u{11037}'),
+	('\u{11071}', '\u{11072}'), ('\u{11075}', '\u{11075}'), ('\u{11083}', '\u{110AF}'), ('\u{110D0}', '\u{110E8}'),
+	('\u{11103}', '\u{11126}'), ('\u{11144}', '\u{11144}'), ('\u{11147}', '\u{11147}'), ('\u{11150}', '\u{11172}'),
+	('\u{11176}', '\u{11176}'), ('\u{11183}', '\u{111B2}'), ('\u{111C1}', '\u{111C4}'), ('\u{111DA}', '\u{111DA}'),
+	('\u{111DC}', '\u{111DC}'), ('\u{11200}', '\u{11211}'), ('\u{11213}', '\u{1122B}'), ('\u{11280}', '\u{11286}'),
+	('\u{11288}', '\u{11288}'), ('\u{1128A}', '\u{1128D}'), ('\u{1128F}', '\u{1129D}'), ('\u{1129F}', '\u{112A8}'),
+	('\u{112B0}', '\u{112DE}'), ('\u{11305}', '\u{1130C}'), ('\u{1130F}', '\u{11310}'), ('\u{11313}', '\u{11328}'),
+	('\u{1132A}', '\u{11330}'), ('\u{11332}', '\u{11333}'), ('\u{11335}', '\u{11339}'), ('\u{1133D}', '\u{1133D}'),
+	('\u{11350}', '\u{11350}'), ('\u{1135D}', '\u{11361}'), ('\u{11400}', '\u{11434}'), ('\u{11447}', '\u{1144A}'),
+	('\u{1145F}', '\u{11461}'), ('\u{11480}', '\u{114AF}'), ('\u{114C4}', '\u{114C5}'), ('\u{114C7}', '\u{114C7}'),
+	('\u{11580}', '\u{115AE}'), ('\u{115D8}', '\u{115DB}'), ('\u{11600}', '\u{1162F}'), ('\u{11644}', '\u{11644}'),
+	('\u{11680}', '\u{116AA}'), ('\u{116B8}', '\u{116B8}'), ('\u{11700}', '\u{1171A}'), ('\u{11740}', '\u{11746}'),
+	('\u{11800}', '\u{1182B}'), ('\u{118A0}', '\u{118DF}'), ('\u{118FF}', '\u{11906}'), ('\u{11909}', '\u{11909}'),
+	('\u{1190C}', '\u{11913}'), ('\u{11915}', '\u{11916}'), ('\u{11918}', '\u{1192F}'), ('\u{1193F}', '\u{1193F}'),
+	('\u{11941}', '\u{11941}'), ('\u{119A0}', '\u{119A7}'), ('\u{119AA}', '\u{119D0}'), ('\u{119E1}', '\u{119E1}'),
+	('\u{119E3}', '\u{119E3}'), ('\u{11A00}', '\u{11A00}'), ('\u{11A0B}', '\u{11A32}'), ('\u{11A3A}', '\u{11A3A}'),
+	('\u{11A50}', '\u{11A50}'), ('\u{11A5C}', '\u{11A89}'), ('\u{11A9D}', '\u{11A9D}'), ('\u{11AB0}', '\u{11AF8}'),
+	('\u{11C00}', '\u{11C08}'), ('\u{11C0A}', '\u{11C2E}'), ('\u{11C40}', '\u{11C40}'), ('\u{11C72}', '\u{11C8F}'),
+	('\u{11D00}', '\u{11D06}'), ('\u{11D08}', '\u{11D09}'), ('\u{11D0B}', '\u{11D30}'), ('\u{11D46}', '\u{11D46}'),
+	('\u{11D60}', '\u{11D65}'), ('\u{11D67}', '\u{11D68}'), ('\u{11D6A}', '\u{11D89}'), ('\u{11D98}', '\u{11D98}'),
+	('\u{11EE0}', '\u{11EF2}'), ('\u{11FB0}', '\u{11FB0}'), ('\u{12000}', '\u{12399}'), ('\u{12400}', '\u{1246E}'),
+	('\u{12480}', '\u{12543}'), ('\u{12F90}', '\u{12FF0}'), ('\u{13000}', '\u{1342E}'), ('\u{14400}', '\u{14646}'),
+	('\u{16800}', '\u{16A38}'), ('\u{16A40}', '\u{16A5E}'), ('\u{16A70}', '\u{16ABE}'), ('\u{16AD0}', '\u{16AED}'),
+	('\u{16B00}', '\u{16B2F}'), ('\u{16B40}', '\u{16B43}'), ('\u{16B63}', '\u{16B77}'), ('\u{16B7D}', '\u{16B8F}'),
+	('\u{16E40}', '\u{16E7F}'), ('\u{16F00}', '\u{16F4A}'), ('\u{16F50}', '\u{16F50}'), ('\u{16F93}', '\u{16F9F}'),
+	('\u{16FE0}', '\u{16FE1}'), ('\u{16FE3}', '\u{16FE3}'), ('\u{17000}', '\u{187F7}'), ('\u{18800}', '\u{18CD5}'),
+	('\u{18D00}', '\u{18D08}'), ('\u{1AFF0}', '\u{1AFF3}'), ('\u{1AFF5}', '\u{1AFFB}'), ('\u{1AFFD}', '\u{1AFFE}'),
+	('\u{1B000}', '\u{1B122}'), ('\u{1B150}', '\u{1B152}'), ('\u{1B164}', '\u{1B167}'), ('\u{1B170}', '\u{1B2FB}'),
+	('\u{1BC00}', '\u{1BC6A}'), ('\u{1BC70}', '\u{1BC7C}'), ('\u{1BC80}', '\u{1BC88}'), ('\u{1BC90}', '\u{1BC99}'),
+	('\u{1D400}', '\u{1D454}'), ('\u{1D456}', '\u{1D49C}'), ('\u{1D49E}', '\u{1D49F}'), ('\u{1D4A2}', '\u{1D4A2}'),
+	('\u{1D4A5}', '\u{1D4A6}'), ('\u{1D4A9}', '\u{1D4AC}'), ('\u{1D4AE}', '\u{1D4B9}'), ('\u{1D4BB}', '\u{1D4BB}'),
+	('\u{1D4BD}', '\u{1D4C3}'), ('\u{1D4C5}', '\u{1D505}'), ('\u{1D507}', '\u{1D50A}'), ('\u{1D50D}', '\u{1D514}'),
+	('\u{1D516}', '\u{1D51C}'), ('\u{1D51E}', '\u{1D539}'), ('\u{1D53B}', '\u{1D53E}'), ('\u{1D540}', '\u{1D544}'),
+	('\u{1D546}', '\u{1D546}'), ('\u{1D54A}', '\u{1D550}'), ('\u{1D552}', '\u{1D6A5}'), ('\u{1D6A8}', '\u{1D6C0}'),
+	('\u{1D6C2}', '\u{1D6DA}'), ('\u{1D6DC}', '\u{1D6FA}'), ('\u{1D6FC}', '\u{1D714}'), ('\u{1D716}', '\u{1D734}'),
+	('\u{1D736}', '\u{1D74E}'), ('\u{1D750}', '\u{1D76E}'), ('\u{1D770}', '\u{1D788}'), ('\u{1D78A}', '\u{1D7A8}'),
+	('\u{1D7AA}', '\u{1D7C2}'), ('\u{1D7C4}', '\u{1D7CB}'), ('\u{1DF00}', '\u{1DF1E}'), ('\u{1E100}', '\u{1E12C}'),
+	('\u{1E137}', '\u{1E13D}'), ('\u{1E14E}', '\u{1E14E}'), ('\u{1E290}', '\u{1E2AD}'), ('\u{1E2C0}', '\u{1E2EB}'),
+	('\u{1E7E0}', '\u{1E7E6}'), ('\u{1E7E8}', '\u{1E7EB}'), ('\u{1E7ED}', '\u{1E7EE}'), ('\u{1E7F0}', '\u{1E7FE}'),
+	('\u{1E800}', '\u{1E8C4}'), ('\u{1E900}', '\u{1E943}'), ('\u{1E94B}', '\u{1E94B}'), ('\u{1EE00}', '\u{1EE03}'),
+	('\u{1EE05}', '\u{1EE1F}'), ('\u{1EE21}', '\u{1EE22}'), ('\u{1EE24}', '\u{1EE24}'), ('\u{1EE27}', '\u{1EE27}'),
+	('\u{1EE29}', '\u{1EE32}'), ('\u{1EE34}', '\u{1EE37}'), ('\u{1EE39}', '\u{1EE39}'), ('\u{1EE3B}', '\u{1EE3B}'),
+	('\u{1EE42}', '\u{1EE42}'), ('\u{1EE47}', '\u{1EE47}'), ('\u{1EE49}', '\u{1EE49}'), ('\u{1EE4B}', '\u{1EE4B}'),
+	('\u{1EE4D}', '\u{1EE4F}'), ('\u{1EE51}', '\u{1EE52}'), ('\u{1EE54}', '\u{1EE54}'), ('\u{1EE57}', '\u{1EE57}'),
+	('\u{1EE59}', '\u{1EE59}'), ('\u{1EE5B}', '\u{1EE5B}'), ('\u{1EE5D}', '\u{1EE5D}'), ('\u{1EE5F}', '\u{1EE5F}'),
+	('\u{1EE61}', '\u{1EE62}'), ('\u{1EE64}', '\u{1EE64}'), ('\u{1EE67}', '\u{1EE6A}'), ('\u{1EE6C}', '\u{1EE72}'),
+	('\u{1EE74}', '\u{1EE77}'), ('\u{1EE79}', '\u{1EE7C}'), ('\u{1EE7E}', '\u{1EE7E}'), ('\u{1EE80}', '\u{1EE89}'),
+	('\u{1EE8B}', '\u{1EE9B}'), ('\u{1EEA1}', '\u{1EEA3}'), ('\u{1EEA5}', '\u{1EEA9}'), ('\u{1EEAB}', '\u{1EEBB}'),
+	('\u{20000}', '\u{2A6DF}'), ('\u{2A700}', '\u{2B738}'), ('\u{2B740}', '\u{2B81D}'), ('\u{2B820}', '\u{2CEA1}'),
+	('\u{2CEB0}', '\u{2EBE0}'), ('\u{2F800}', '\u{2FA1D}'), ('\u{30000}', '\u{3134A}')
+];
+
+/// Characters with the XID_Continue property.
+pub(crate) const XID_CONTINUE:&[(char, char)] = &[
+	('\u{30}', '\u{39}'), ('\u{41}', '\u{5A}'), ('\u{5F}', '\u{5F}'), ('\u{61}', '\u{7A}'),
+	('\u{AA}', '\u{AA}'), ('\u{B5}', '\u{B5}'), ('\u{B7}', '\u{B7}'), ('\u{BA}', '\u{BA}'),
+	('\u{C0}', '\u{D6}'), ('\u{D8}', '\u{F6}'), ('\u{F8}', '\u{2C1}'), ('\u{2C6}', '\u{2D1}'),
+	('\u{2E0}', '\u{2E4}'), ('\u{2EC}', '\u{2EC}'), ('\u{2EE}', '\u{2EE}'), ('\u{300}', '\u{374}'),
+	('\u{376}', '\u{377}'), ('\u{37B}', '\u{37D}'), ('\u{37F}', '\u{37F}'), ('\u{386}', '\u{38A}'),
+	('\u{38C}', '\u{38C}'), ('\u{38E}', '\u{3A1}'), ('\u{3A3}', '\u{3F5}'), ('\u{3F7}', '\u{481}'),
+	('\u{483}', '\u{487}'), ('\u{48A}', '\u{52F}'), ('\u{531}', '\u{556}'), ('\u{559}', '\u{559}'),
+	('\u{560}', '\u{588}'), ('\u{591}', '\u{5BD}'), ('\u{5BF}', '\u{5BF}'), ('\u{5C1}', '\u{5C2}'),
+	('\u{5C4}', '\u{5C5}'), ('\u{5C7}', '\u{5C7}'), ('\u{5D0}', '\u{5EA}'), ('\u{5EF}', '\u{5F2}'),
+	('\u{610}', '\u{61A}'), ('\u{620}', '\u{669}'), ('\u{66E}', '\u{6D3}'), ('\u{6D5}', '\u{6DC}'),
+	('\u{6DF}', '\u{6E8}'), ('\u{6EA}', '\u{6FC}'), ('\u{6FF}', '\u{6FF}'), ('\u{710}', '\u{74A}'),
+	('\u{74D}', '\u{7B1}'), ('\u{7C0}', '\u{7F5}'), ('\u{7FA}', '\u{7FA}'), ('\u{7FD}', '\u{7FD}'),
+	('\u{800}', '\u{82D}'), ('\u{840}', '\u{85B}'), ('\u{860}', '\u{86A}'), ('\u{870}', '\u{887}'),
+	('\u{889}', '\u{88E}'), ('\u{898}', '\u{8E1}'), ('\u{8E3}', '\u{963}'), ('\u{966}', '\u{96F}'),
+	('\u{971}', '\u{983}'), ('\u{985}', '\u{98C}'), ('\u{98F}', '\u{990}'), ('\u{993}', '\u{9A8}'),
+	('\u{9AA}', '\u{9B0}'), ('\u{9B2}', '\u{9B2}'), ('\u{9B6}', '\u{9B9}'), ('\u{9BC}', '\u{9C4}'),
+	('\u{9C7}', '\u{9C8}'), ('\u{9CB}', '\u{9CE}'), ('\u{9D7}', '\u{9D7}'), ('\u{9DC}', '\u{9DD}'),
+	('\u{9DF}', '\u{9E3}'), ('\u{9E6}', '\u{9F1}'), ('\u{9FC}', '\u{9FC}'), ('\u{9FE}', '\u{9FE}'),
+	('\u{A01}', '\u{A03}'), ('\u{A05}', '\u{A0A}'), ('\u{A0F}', '\u{A10}'), ('\u{A13}', '\u{A28}'),
+	('\u{A2A}', '\u{A30}'), ('\u{A32}', '\u{A33}'), ('\u{A35}', '\u{A36}'), ('\u{A38}', '\u{A39}'),
+	('\u{A3C}', '\u{A3C}'), ('\u{A3E}', '\u{A42}'), ('\u{A47}', '\u{A48}'), ('\u{A4B}', '\u{A4D}'),
+	('\u{A51}', '\u{A51}'), ('\u{A59}', '\u{A5C}'), ('\u{A5E}', '\u{A5E}'), ('\u{A66}', '\u{A75}'),
+	('\u{A81}', '\u{A83}'), ('\u{A85}', '\u{A8D}'), ('\u{A8F}', '\u{A91}'), ('\u{A93}', '\u{AA8}'),
+	('\u{AAA}', '\u{AB0}'), ('\u{AB2}', '\u{AB3}'), ('\u{AB5}', '\u{AB9}'), ('\u{ABC}', '\u{AC5}'),
+	('\u{AC7}', '\u{AC9}'), ('\u{ACB}', '\u{ACD}'), ('\u{AD0}', '\u{AD0}'), ('\u{AE0}', '\u{AE3}'),
+	('\u{AE6}', '\u{AEF}'), ('\u{AF9}', '\u{AFF}'), ('\u{B01}', '\u{B03}'), ('\u{B05}', '\u{B0C}'),
+	('\u{B0F}', '\u{B10}'), ('\u{B13}', '\u{B28}'), ('\u{B2A}', '\u{B30}'), ('\u{B32}', '\u{B33}'),
+	('\u{B35}', '\u{B39}'), ('\u{B3C}', '\u{B44}'), ('\u{B47}', '\u{B48}'), ('\u{B4B}', '\u{B4D}'),
+	('\u{B55}', '\u{B57}'), ('\u{B5C}', '\u{B5D}'), ('\u{B5F}', '\u{B63}'), ('\u{B66}', '\u{B6F}'),
+	('\u{B71}', '\u{B71}'), ('\u{B82}', '\u{B83}'), ('\u{B85}', '\u{B8A}'), ('\u{B8E}', '\u{B90}'),
+	('\u{B92}', '\u{B95}'), ('\u{B99}', '\u{B9A}'), ('\u{B9C}', '\u{B9C}'), ('\u{B9E}', '\u{B9F}'),
+	('\u{BA3}', '\u{BA4}'), ('\u{BA8}', '\u{BAA}'), ('\u{BAE}', '\u{BB9}'), ('\u{BBE}', '\u{BC2}'),
+	('\u{BC6}', '\u{BC8}'), ('\u{BCA}', '\u{BCD}'), ('\u{BD0}', '\u{BD0}'), ('\u{BD7}', '\u{BD7}'),
+	('\u{BE6}', '\u{BEF}'), ('\u{C00}', '\u{C0C}'), ('\u{C0E}', '\u{C10}'), ('\u{C12}', '\u{C28}'),
+	('\u{C2A}', '\u{C39}'), ('\u{C3C}', '\u{C44}'), ('\u{C46}', '\u{C48}'), ('\u{C4A}', '\u{C4D}'),
+	('\u{C55}', '\u{C56}'), ('\u{C58}', '\u{C5A}'), ('\u{C5D}', '\u{C5D}'), ('\u{C60}', '\u{C63}'),
+	('\u{C66}', '\u{C6F}'), ('\u{C80}', '\u{C83}'), ('\u{C85}', '\u{C8C}'), ('\u{C8E}', '\u{C90}'),
+	('\u{C92}', '\u{CA8}'), ('\u{CAA}', '\u{CB3}'), ('\u{CB5}', '\u{CB9}'), ('\u{CBC}', '\u{CC4}'),
+	('\u{CC6}', '\u{CC8}'), ('\u{CCA}', '\u{CCD}'), ('\u{CD5}', '\u{CD6}'), ('\u{CDD}', '\u{CDE}'),
+	('\u{CE0}', '\u{CE3}'), ('\u{CE6}', '\u{CEF}'), ('\u{CF1}', '\u{CF2}'), ('\u{D00}', '\u{D0C}'),
+	('\u{D0E}', '\u{D10}'), ('\u{D12}', '\u{D44}'), ('\u{D46}', '\u{D48}'), ('\u{D4A}', '\u{D4E}'),
+	('\u{D54}', '\u{D57}'), ('\u{D5F}', '\u{D63}'), ('\u{D66}', '\u{D6F}'), ('\u{D7A}', '\u{D7F}'),
+	('\u{D81}', '\u{D83}'), ('\u{D85}', '\u{D96}'), ('\u{D9A}', '\u{DB1}'), ('\u{DB3}', '\u{DBB}'),
+	('\u{DBD}', '\u{DBD}'), ('\u{DC0}', '\u{DC6}'), ('\u{DCA}', '\u{DCA}'), ('\u{DCF}', '\u{DD4}'),
+	('\u{DD6}', '\u{DD6}'), ('\u{DD8}', '\u{DDF}'), ('\u{DE6}', '\u{DEF}'), ('\u{DF2}', '\u{DF3}'),
+	('\u{E01}', '\u{E3A}'), ('\u{E40}', '\u{E4E}'), ('\u{E50}', '\u{E59}'), ('\u{E81}', '\u{E82}'),
+	('\u{E84}', '\u{E84}'), ('\u{E86}', '\u{E8A}'), ('\u{E8C}', '\u{EA3}'), ('\u{EA5}', '\u{EA5}'),
+	('\u{EA7}', '\u{EBD}'), ('\u{EC0}', '\u{EC4}'), ('\u{EC6}', '\u{EC6}'), ('\u{EC8}', '\u{ECD}'),
+	('\u{ED0}', '\u{ED9}'), ('\u{EDC}', '\u{EDF}'), ('\u{F00}', '\u{F00}'), ('\u{F18}', '\u{F19}'),
+	('\u{F20}', '\u{F29}'), ('\u{F35}', '\u{F35}'), ('\u{F37}', '\u{F37}'), ('\u{F39}', '\u{F39}'),
+	('\u{F3E}', '\u{F47}'), ('\u{F49}', '\u{F6C}'), ('\u{F71}', '\u{F84}'), ('\u{F86}', '\u{F97}'),
+	('\u{F99}', '\u{FBC}'), ('\u{FC6}', '\u{FC6}'), ('\u{1000}', '\u{1049}'), ('\u{1050}', '\u{109D}'),
+	('\u{10A0}', '\u{10C5}'), ('\u{10C7}', '\u{10C7}'), ('\u{10CD}', '\u{10CD}'), ('\u{10D0}', '\u{10FA}'),
+	('\u{10FC}', '\u{1248}'), ('\u{124A}', '\u{124D}'), ('\u{1250}', '\u{1256}'), ('\u{1258}', '\u{1258}'),
+	('\u{125A}', '\u{125D}'), ('\u{1260}', '\u{1288}'), ('\u{128A}', '\u{128D}'), ('\u{1290}', '\u{12B0}'),
+	('\u{12B2}', '\u{12B5}'), ('\u{12B8}', '\u{12BE}'), ('\u{12C0}', '\u{12C0}'), ('\u{12C2}', '\u{12C5}'),
+	('\u{12C8}', '\u{12D6}'), ('\u{12D8}', '\u{1310}'), ('\u{1312}', '\u{1315}'), ('\u{1318}', '\u{135A}'),
+	('\u{135D}', '\u{135F}'), ('\u{1369}', '\u{1371}'), ('\u{1380}', '\u{138F}'), ('\u{13A0}', '\u{13F5}'),
+	('\u{13F8}', '\u{13FD}'), ('\u{1401}', '\u{166C}'), ('\u{166F}', '\u{167F}'), ('\u{1681}', '\u{169A}'),
+	('\u{16A0}', '\u{16EA}'), ('\u{16EE}', '\u{16F8}'), ('\u{1700}', '\u{1715}'), ('\u{171F}', '\u{1734}'),
+	('\u{1740}', '\u{1753}'), ('\u{1760}', '\u{176C}'), ('\u{176E}', '\u{1770}'), ('\u{1772}', '\u{1773}'),
+	('\u{1780}', '\u{17D3}'), ('\u{17D7}', '\u{17D7}'), ('\u{17DC}', '\u{17DD}'), ('\u{17E0}', '\u{17E9}'),
+	('\u{180B}', '\u{180D}'), ('\u{180F}', '\u{1819}'), ('\u{1820}', '\u{1878}'), ('\u{1880}', '\u{18AA}'),
+	('\u{18B0}', '\u{18F5}'), ('\u{1900}', '\u{191E}'), ('\u{1920}', '\u{192B}'), ('\u{1930}', '\u{193B}'),
+	('\u{1946}', '\u{196D}'), ('\u{1970}', '\u{1974}'), ('\u{1980}', '\u{19AB}'), ('\u{19B0}', '\u{19C9}'),
+	('\u{19D0}', '\u{19DA}'), ('\u{1A00}', '\u{1A1B}'), ('\u{1A20}', '\u{1A5E}'), ('\u{1A60}', '\u{1A7C}'),
+	('\u{1A7F}', '\u{1A89}'), ('\u{1A90}', '\u{1A99}'), ('\u{1AA7}', '\u{1AA7}'), ('\u{1AB0}', '\u{1ABD}'),
+	('\u{1ABF}', '\u{1ACE}'), ('\u{1B00}', '\u{1B4C}'), ('\u{1B50}', '\u{1B59}'), ('\u{1B6B}', '\u{1B73}'),
+	('\u{1B80}', '\u{1BF3}'), ('\u{1C00}', '\u{1C37}'), ('\u{1C40}', '\u{1C49}'), ('\u{1C4D}', '\u{1C7D}'),
+	('\u{1C80}', '\u{1C88}'), ('\u{1C90}', '\u{1CBA}'), ('\u{1CBD}', '\u{1CBF}'), ('\u{1CD0}', '\u{1CD2}'),
+	('\u{1CD4}', '\u{1CFA}'), ('\u{1D00}', '\u{1F15}'), ('\u{1F18}', '\u{1F1D}'), ('\u{1F20}', '\u{1F45}'),
+	('\u{1F48}', '\u{1F4D}'), ('\u{1F50}', '\u{1F57}'), ('\u{1F59}', '\u{1F59}'), ('\u{1F5B}', '\u{1F5B}'),
+	('\u{1F5D}', '\u{1F5D}'), ('\u{1F5F}', '\u{1F7D}'), ('\u{1F80}', '\u{1FB4}'), ('\u{1FB6}', '\u{1FBC}'),
+	('\u{1FBE}', '\u{1FBE}'), ('\u{1FC2}', '\u{1FC4}'), ('\u{1FC6}', '\u{1FCC}'), ('\u{1FD0}', '\u{1FD3}'),
+	('\u{1FD6}', '\u{1FDB}'), ('\u{1FE0}', '\u{1FEC}'), ('\u{1FF2}', '\u{1FF4}'), ('\u{1FF6}', '\u{1FFC}'),
+	('\u{203F}', '\u{2040}'), ('\u{2054}', '\u{2054}'), ('\u{2071}', '\u{2071}'), ('\u{207F}', '\u{207F}'),
+	('\u{2090}', '\u{209C}'), ('\u{20D0}', '\u{20DC}'), ('\u{20E1}', '\u{20E1}'), ('\u{20E5}', '\u{20F0}'),
+	('\u{2102}', '\u{2102}'), ('\u{2107}', '\u{2107}'), ('\u{210A}', '\u{2113}'), ('\u{2115}', '\u{2115}'),
+	('\u{2118}', '\u{211D}'), ('\u{2124}', '\u{2124}'), ('\u{2126}', '\u{2126}'), ('\u{2128}', '\u{2128}'),
+	('\u{212A}', '\u{2139}'), ('\u{213C}', '\u{213F}'), ('\u{2145}', '\u{2149}'), ('\u{214E}', '\u{214E}'),
+	('\u{2160}', '\u{2188}'), ('\u{2C00}', '\u{2CE4}'), ('\u{2CEB}', '\u{2CF3}'), ('\u{2D00}', '\u{2D25}'),
+	('\u{2D27}', '\u{2D27}'), ('\u{2D2D}', '\u{2D2D}'), ('\u{2D30}', '\u{2D67}'), ('\u{2D6F}', '\u{2D6F}'),
+	('\u{2D7F}', '\u{2D96}'), ('\u{2DA0}', '\u{2DA6}'), ('\u{2DA8}', '\u{2DAE}'), ('\u{2DB0}', '\u{2DB6}'),
+	('\u{2DB8}', '\u{2DBE}'), ('\u{2DC0}', '\u{2DC6}'), ('\u{2DC8}', '\u{2DCE}'), ('\u{2DD0}', '\u{2DD6}'),
+	('\u{2DD8}', '\u{2DDE}'), ('\u{2DE0}', '\u{2DFF}'), ('\u{3005}', '\u{3007}'), ('\u{3021}', '\u{302F}'),
+	('\u{3031}', '\u{3035}'), ('\u{3038}', '\u{303C}'), ('\u{3041}', '\u{3096}'), ('\u{3099}', '\u{309A}'),
+	('\u{309D}', '\u{309F}'), ('\u{30A1}', '\u{30FA}'), ('\u{30FC}', '\u{30FF}'), ('\u{3105}', '\u{312F}'),
+	('\u{3131}', '\u{318E}'), ('\u{31A0}', '\u{31BF}'), ('\u{31F0}', '\u{31FF}'), ('\u{3400}', '\u{4DBF}'),
+	('\u{4E00}', '\u{A48C}'), ('\u{A4D0}', '\u{A4FD}'), ('\u{A500}', '\u{A60C}'), ('\u{A610}', '\u{A62B}'),
+	('\u{A640}', '\u{A66F}'), ('\u{A674}', '\u{A67D}'), ('\u{A67F}', '\u{A6F1}'), ('\u{A717}', '\u{A71F}'),
+	('\u{A722}', '\u{A788}'), ('\u{A78B}', '\u{A7CA}'), ('\u{A7D0}', '\u{A7D1}'), ('\u{A7D3}', '\u{A7D3}'),
+	('\u{A7D5}', '\u{A7D9}'), ('\u{A7F2}', '\u{A827}'), ('\u{A82C}', '\u{A82C}'), ('\u{A840}', '\u{A873}'),
+	('\u{A880}', '\u{A8C5}'), ('\u{A8D0}', '\u{A8D9}'), ('\u{A8E0}', '\u{A8F7}'), ('\u{A8FB}', '\u{A8FB}'),
+	('\u{A8FD}', '\u{A92D}'), ('\u{A930}', '\u{A953}'), ('\u{A960}', '\u{A97C}'), ('\u{A980}', '\u{A9C0}'),
+	('\u{A9CF}', '\u{A9D9}'), ('\u{A9E0}', '\u{A9FE}'), ('\u{AA00}', '\u{AA36}'), ('\u{AA40}', '\u{AA4D}'),
+	('\u{AA50}', '\u{AA59}'), ('\u{AA60}', '\u{AA76}'), ('\u{AA7A}', '\u{AAC2}'), ('\u{AADB}', '\u{AADD}'),
+	('\u{AAE0}', '\u{AAEF}'), ('\u{AAF2}', '\u{AAF6}'), ('\u{AB01}', '\u{AB06}'), ('\u{AB09}', '\u{AB0E}'),
+	('\u{AB11}', '\u{AB16}'), ('\u{AB20}', '\u{AB26}'), ('\u{AB28}', '\u{AB2E}'), ('\u{AB30}', '\u{AB5A}'),
+	('\u{AB5C}', '\u{AB69}'), ('\u{AB70}', '\u{ABEA}'), ('\u{ABEC}', '\u{ABED}'), ('\u{ABF0}', '\u{ABF9}'),
+	('\u{AC00}', '\u{D7A3}'), ('\u{D7B0}', '\u{D7C6}'), ('\u{D7CB}', '\u{D7FB}'), ('\u{F900}', '\u{FA6D}'),
+	('\u{FA70}', '\u{FAD9}'), ('\u{FB00}', '\u{FB06}'), ('\u{FB13}', '\u{FB17}'), ('\u{FB1D}', '\u{FB28}'),
+	('\u{FB2A}', '\u{FB36}'), ('\u{FB38}', '\u{FB3C}'), ('\u{FB3E}', '\u{FB3E}'), ('\u{FB40}', '\u{FB41}'),
+	('\u{FB43}', '\u{FB44}'), ('\u{FB46}', '\u{FBB1}'), ('\u{FBD3}', '\u{FC5D}'), ('\u{FC64}', '\u{FD3D}'),
+	('\u{FD50}', '\u{FD8F}'), ('\u{FD92}', '\u{FDC7}'), ('\u{FDF0}', '\u{FDF9}'), ('\u{FE00}', '\u{FE0F}'),
+	('\u{FE20}', '\u{FE2F}'), ('\u{FE33}', '\u{FE34}'), ('\u{FE4D}', '\u{FE4F}'), ('\u{FE71}', '\u{FE71}'),
+	('\u{FE73}', '\u{FE73}'), ('\u{FE77}', '\u{FE77}'), ('\u{FE79}', '\u{FE79}'), ('\u{FE7B}', '\u{FE7B}'),
+	('\u{FE7D}', '\u{FE7D}'), ('\u{FE7F}', '\u{FEFC}'), ('\u{FF10}', '\u{FF19}'), ('\u{FF21}', '\u{FF3A}'),
+	('\u{FF3F}', '\u{FF3F}'), ('\u{FF41}', '\u{FF5A}'), ('\u{FF66}', '\u{FFBE}'), ('\u{FFC2}', '\u{FFC7}'),
+	('\u{FFCA}', '\u{FFCF}'), ('\u{FFD2}', '\u{FFD7}'), ('\u{FFDA}', '\u{FFDC}'), ('\u{10000}', '\u{1000B}'),
+	('\u{1000D}', '\u{10026}'), ('\u{10028}', '\u{1003A}'), ('\u{1003C}', '\u{1003D}'), ('\u{1003F}', '\u{1004D}'),
+	('\u{10050}', '\u{1005D}'), ('\u{10080}', '\u{100FA}'), ('\u{10140}', '\u{10174}'), ('\u{101FD}', '\u{101FD}'),
+	('\u{10280}', '\u{1029C}'), ('\u{102A0}', '\u{102D0}'), ('\u{102E0}', '\u{102E0}'), ('\u{10300}', '\u{1031F}'),
+	('\u{1032D}', '\u{1034A}'), ('\u{10350}', '\u{1037A}'), ('\u{10380}', '\u{1039D}'), ('\u{103A0}', '\u{103C3}'),
+	('\u{103C8}', '\u{103CF}'), ('\u{103D1}', '\u{103D5}'), ('\u{10400}', '\u{1049D}'), ('\u{104A0}', '\u{104A9}'),
+	('\u{104B0}', '\u{104D3}'), ('\u{104D8}', '\u{104FB}'), ('\u{10500}', '\u{10527}'), ('\u{10530}', '\u{10563}'),
+	('\u{10570}', '\u{1057A}'), ('\u{1057C}', '\u{1058A}'), ('\u{1058C}', '\u{10592}'), ('\u{10594}', '\u{10595}'),
+	('\u{10597}', '\u{105A1}'), ('\u{105A3}', '\u{105B1}'), ('\u{105B3}', '\u{105B9}'), ('\u{105BB}', '\u{105BC}'),
+	('\u{10600}', '\u{10736}'), ('\u{10740}', '\u{10755}'), ('\u{10760}', '\u{10767}'), ('\u{10780}', '\u{10785}'),
+	('\u{10787}', '\u{107B0}'), ('\u{107B2}', '\u{107BA}'), ('\u{10800}', '\u{10805}'), ('\u{10808}', '\u{10808}'),
+	('\u{1080A}', '\u{10835}'), ('\u{10837}', '\u{10838}'), ('\u{1083C}', '\u{1083C}'), ('\u{1083F}', '\u{10855}'),
+	('\u{10860}', '\u{10876}'), ('\u{10880}', '\u{1089E}'), ('\u{108E0}', '\u{108F2}'), ('\u{108F4}', '\u{108F5}'),
+	('\u{10900}', '\u{10915}'), ('\u{10920}', '\u{10939}'), ('\u{10980}', '\u{109B7}'), ('\u{109BE}', '\u{109BF}'),
+	('\u{10A00}', '\u{10A03}'), ('\u{10A05}', '\u{10A06}'), ('\u{10A0C}', '\u{10A13}'), ('\u{10A15}', '\u{10A17}'),
+	('\u{10A19}', '\u{10A35}'), ('\u{10A38}', '\u{10A3A}'), ('\u{10A3F}', '\u{10A3F}'), ('\u{10A60}', '\u{10A7C}'),
+	('\u{10A80}', '\u{10A9C}'), ('\u{10AC0}', '\u{10AC7}'), ('\u{10AC9}', '\u{10AE6}'), ('\u{10B00}', '\u{10B35}'),
+	('\u{10B40}', '\u{10B55}'), ('\u{10B60}', '\u{10B72}'), ('\u{10B80}', '\u{10B91}'), ('\u{10C00}', '\u{10C48}'),
+	('\u{10C80}', '\u{10CB2}'), ('\u{10CC0}', '\u{10CF2}'), ('\u{10D00}', '\u{10D27}'), ('\u{10D30}', '\u{10D39}'),
+	('\u{10E80}', '\u{10EA9}'), ('\u{10EAB}', '\u{10EAC}'), ('\u{10EB0}', '\u{10EB1}'), ('\u{10F00}', '\u{10F1C}'),
+	('\u{10F27}', '\u{10F27}'), ('\u{10F30}', '\u{10F50}'), ('\u{10F70}', '\u{10F85}'), ('\u{10FB0}', '\u{10FC4}'),
+	('\u{10FE0}', '\u{10FF6}'), ('\u{11000}', '\u{11046}'), ('\u{11066}', '\u{11075}'), ('\u{1107F}', '\u{110BA}'),
+	('\u{110C2}', '\u{110C2}'), ('\u{110D0}', '\u{110E8}'), ('\u{110F0}', '\u{110F9}'), ('\u{11100}', '\u{11134}'),
+	('\u{11136}', '\u{1113F}'), ('\u{11144}', '\u{11147}'), ('\u{11150}', '\u{11173}'), ('\u{11176}', '\u{11176}'),
+	('\u{11180}', '\u{111C4}'), ('\u{111C9}', '\u{111CC}'), ('\u{111CE}', '\u{111DA}'), ('\u{111DC}', '\u{111DC}'),
+	('\u{11200}', '\u{11211}'), ('\u{11213}', '\u{11237}'), ('\u{1123E}', '\u{1123E}'), ('\u{11280}', '\u{11286}'),
+	('\u{11288}', '\u{11288}'), ('\u{1128A}', '\u{1128D}'), ('\u{1128F}', '\u{1129D}'), ('\u{1129F}', '\u{112A8}'),
+	('\u{112B0}', '\u{112EA}'), ('\u{112F0}', '\u{112F9}'), ('\u{11300}', '\u{11303}'), ('\u{11305}', '\u{1130C}'),
+	('\u{1130F}', '\u{11310}'), ('\u{11313}', '\u{11328}'), ('\u{1132A}', '\u{11330}'), ('\u{11332}', '\u{11333}'),
+	('\u{11335}', '\u{11339}'), ('\u{1133B}', '\u{11344}'), ('\u{11347}', '\u{11348}'), ('\u{1134B}', '\u{1134D}'),
+	('\u{11350}', '\u{11350}'), ('\u{11357}', '\u{11357}'), ('\u{1135D}', '\u{11363}'), ('\u{11366}', '\u{1136C}'),
+	('\u{11370}', '\u{11374}'), ('\u{11400}', '\u{1144A}'), ('\u{11450}', '\u{11459}'), ('\u{1145E}', '\u{11461}'),
+	('\u{11480}', '\u{114C5}'), ('\u{114C7}', '\u{114C7}'), ('\u{114D0}', '\u{114D9}'), ('\u{11580}', '\u{115B5}'),
+	('\u{115B8}', '\u{115C0}'), ('\u{115D8}', '\u{115DD}'), ('\u{11600}', '\u{11640}'), ('\u{11644}', '\u{11644}'),
+	('\u{11650}', '\u{11659}'), ('\u{11680}', '\u{116B8}'), ('\u{116C0}', '\u{116C9}'), ('\u{11700}', '\u{1171A}'),
+	('\u{1171D}', '\u{1172B}'), ('\u{11730}', '\u{11739}'), ('\u{11740}', '\u{11746}'), ('\u{11800}', '\u{1183A}'),
+	('\u{118A0}', '\u{118E9}'), ('\u{118FF}', '\u{11906}'), ('\u{11909}', '\u{11909}'), ('\u{1190C}', '\u{11913}'),
+	('\u{11915}', '\u{11916}'), ('\u{11918}', '\u{11935}'), ('\u{11937}', '\u{11938}'), ('\u{1193B}', '\u{11943}'),
+	('\u{11950}', '\u{11959}'), ('\u{119A0}', '\u{119A7}'), ('\u{119AA}', '\u{119D7}'), ('\u{119DA}', '\u{119E1}'),
+	('\u{119E3}', '\u{119E4}'), ('\u{11A00}', '\u{11A3E}'), ('\u{11A47}', '\u{11A47}'), ('\u{11A50}', '\u{11A99}'),
+	('\u{11A9D}', '\u{11A9D}'), ('\u{11AB0}', '\u{11AF8}'), ('\u{11C00}', '\u{11C08}'), ('\u{11C0A}', '\u{11C36}'),
+	('\u{11C38}', '\u{11C40}'), ('\u{11C50}', '\u{11C59}'), ('\u{11C72}', '\u{11C8F}'), ('\u{11C92}', '\u{11CA7}'),
+	('\u{11CA9}', '\u{11CB6}'), ('\u{11D00}', '\u{11D06}'), ('\u{11D08}', '\u{11D09}'), ('\u{11D0B}', '\u{11D36}'),
+	('\u{11D3A}', '\u{11D3A}'), ('\u{11D3C}', '\u{11D3D}'), ('\u{11D3F}', '\u{11D47}'), ('\u{11D50}', '\u{11D59}'),
+	('\u{11D60}', '\u{11D65}'), ('\u{11D67}', '\u{11D68}'), ('\u{11D6A}', '\u{11D8E}'), ('\u{11D90}', '\u{11D91}'),
+	('\u{11D93}', '\u{11D98}'), ('\u{11DA0}', '\u{11DA9}'), ('\u{11EE0}', '\u{11EF6}'), ('\u{11FB0}', '\u{11FB0}'),
+	('\u{12000}', '\u{12399}'), ('\u{12400}', '\u{1246E}'), ('\u{12480}', '\u{12543}'), ('\u{12F90}', '\u{12FF0}'),
+	('\u{13000}', '\u{1342E}'), ('\u{14400}', '\u{14646}'), ('\u{16800}', '\u{16A38}'), ('\u{16A40}', '\u{16A5E}'),
+	('\u{16A60}', '\u{16A69}'), ('\u{16A70}', '\u{16ABE}'), ('\u{16AC0}', '\u{16AC9}'), ('\u{16AD0}', '\u{16AED}'),
+	('\u{16AF0}', '\u{16AF4}'), ('\u{16B00}', '\u{16B36}'), ('\u{16B40}', '\u{16B43}'), ('\u{16B50}', '\u{16B59}'),
+	('\u{16B63}', '\u{16B77}'), ('\u{16B7D}', '\u{16B8F}'), ('\u{16E40}', '\u{16E7F}'), ('\u{16F00}', '\u{16F4A}'),
+	('\u{16F4F}', '\u{16F87}'), ('\u{16F8F}', '\u{16F9F}'), ('\u{16FE0}', '\u{16FE1}'), ('\u{16FE3}', '\u{16FE4}'),
+	('\u{16FF0}', '\u{16FF1}'), ('\u{17000}', '\u{187F7}'), ('\u{18800}', '\u{18CD5}'), ('\u{18D00}', '\u{18D08}'),
+	('\u{1AFF0}', '\u{1AFF3}'), ('\u{1AFF5}', '\u{1AFFB}'), ('\u{1AFFD}', '\u{1AFFE}'), ('\u{1B000}', '\u{1B122}'),
+	('\u{1B150}', '\u{1B152}'), ('\u{1B164}', '\u{1B167}'), ('\u{1B170}', '\u{1B2FB}'), ('\u{1BC00}', '\u{1BC6A}'),
+	('\u{1BC70}', '\u{1BC7C}'), ('\u{1BC80}', '\u{1BC88}'), ('\u{1BC90}', '\u{1BC99}'), ('\u{1BC9D}', '\u{1BC9E}'),
+	('\u{1CF00}', '\u{1CF2D}'), ('\u{1CF30}', '\u{1CF46}'), ('\u{1D165}', '\u{1D169}'), ('\u{1D16D}', '\u{1D172}'),
+	('\u{1D17B}', '\u{1D182}'), ('\u{1D185}', '\u{1D18B}'), ('\u{1D1AA}', '\u{1D1AD}'), ('\u{1D242}', '\u{1D244}'),
+	('\u{1D400}', '\u{1D454}'), ('\u{1D456}', '\u{1D49C}'), ('\u{1D49E}', '\u{1D49F}'), ('\u{1D4A2}', '\u{1D4A2}'),
+	('\u{1D4A5}', '\u{1D4A6}'), ('\u{1D4A9}', '\u{1D4AC}'), ('\u{1D4AE}', '\u{1D4B9}'), ('\u{1D4BB}', '\u{1D4BB}'),
+	('\u{1D4BD}', '\u{1D4C3}'), ('\u{1D4C5}', '\u{1D505}'), ('\u{1D507}', '\u{1D50A}'), ('\u{1D50D}', '\u{1D514}'),
+	('\u{1D516}', '\u{1D51C}'), ('\u{1D51E}', '\u{1D539}'), ('\u{1D53B}', '\u{1D53E}'), ('\u{1D540}', '\u{1D544}'),
+	('\u{1D546}', '\u{1D546}'), ('\u{1D54A}', '\u{1D550}'), ('\u{1D552}', '\u{1D6A5}'), ('\u{1D6A8}', '\u{1D6C0}'),
+	('\u{1D6C2}', '\u{1D6DA}'), ('\u{1D6DC}', '\u{1D6FA}'), ('\u{1D6FC}', '\u{1D714}'), ('\u{1D716}', '\u{1D734}'),
+	('\u{1D736}', '\u{1D74E}'), ('\u{1D750}', '\u{1D76E}'), ('\u{1D770}', '\u{1D788}'), ('\u{1D78A}', '\u{1D7A8}'),
+	('\u{1D7AA}', '\u{1D7C2}'), ('\u{1D7C4}', '\u{1D7CB}'), ('\u{1D7CE}', '\u{1D7FF}'), ('\u{1DA00}', '\u{1DA36}'),
+	('\u{1DA3B}', '\u{1DA6C}'), ('\u{1DA75}', '\u{1DA75}'), ('\u{1DA84}', '\u{1DA84}'), ('\u{1DA9B}', '\u{1DA9F}'),
+	('\u{1DAA1}', '\u{1DAAF}'), ('\u{1DF00}', '\u{1DF1E}'), ('\u{1E000}', '\u{1E006}'), ('\u{1E008}', '\u{1E018}'),
+	('\u{1E01B}', '\u{1E021}'), ('\u{1E023}', '\u{1E024}'), ('\u{1E026}', '\u{1E02A}'), ('\u{1E100}', '\u{1E12C}'),
+	('\u{1E130}', '\u{1E13D}'), ('\u{1E140}', '\u{1E149}'), ('\u{1E14E}', '\u{1E14E}'), ('\u{1E290}', '\u{1E2AE}'),
+	('\u{1E2C0}', '\u{1E2F9}'), ('\u{1E7E0}', '\u{1E7E6}'), ('\u{1E7E8}', '\u{1E7EB}'), ('\u{1E7ED}', '\u{1E7EE}'),
+	('\u{1E7F0}', '\u{1E7FE}'), ('\u{1E800}', '\u{1E8C4}'), ('\u{1E8D0}', '\u{1E8D6}'), ('\u{1E900}', '\u{1E94B}'),
+	('\u{1E950}', '\u{1E959}'), ('\u{1EE00}', '\u{1EE03}'), ('\u{1EE05}', '\u{1EE1F}'), ('\u{1EE21}', '\u{1EE22}'),
+	('\u{1EE24}', '\u{1EE24}'), ('\u{1EE27}', '\u{1EE27}'), ('\u{1EE29}', '\u{1EE32}'), ('\u{1EE34}', '\u{1EE37}'),
+	('\u{1EE39}', '\u{1EE39}'), ('\u{1EE3B}', '\u{1EE3B}'), ('\u{1EE42}', '\u{1EE42}'), ('\u{1EE47}', '\u{1EE47}'),
+	('\u{1EE49}', '\u{1EE49}'), ('\u{1EE4B}', '\u{1EE4B}'), ('\u{1EE4D}', '\u{1EE4F}'), ('\u{1EE51}', '\u{1EE52}'),
+	('\u{1EE54}', '\u{1EE54}'), ('\u{1EE57}', '\u{1EE57}'), ('\u{1EE59}', '\u{1EE59}'), ('\u{1EE5B}', '\u{1EE5B}'),
+	('\u{1EE5D}', '\u{1EE5D}'), ('\u{1EE5F}', '\u{1EE5F}'), ('\u{1EE61}', '\u{1EE62}'), ('\u{1EE64}', '\u{1EE64}'),
+	('\u{1EE67}', '\u{1EE6A}'), ('\u{1EE6C}', '\u{1EE72}'), ('\u{1EE74}', '\u{1EE77}'), ('\u{1EE79}', '\u{1EE7C}'),
+	('\u{1EE7E}', '\u{1EE7E}'), ('\u{1EE80}', '\u{1EE89}'), ('\u{1EE8B}', '\u{1EE9B}'), ('\u{1EEA1}', '\u{1EEA3}'),
+	('\u{1EEA5}', '\u{1EEA9}'), ('\u{1EEAB}', '\u{1EEBB}'), ('\u{1FBF0}', '\u{1FBF9}'), ('\u{20000}', '\u{2A6DF}'),
+	('\u{2A700}', '\u{2B738}'), ('\u{2B740}', '\u{2B81D}'), ('\u{2B820}', '\u{2CEA1}'), ('\u{2CEB0}', '\u{2EBE0}'),
+	('\u{2F800}', '\u{2FA1D}'), ('\u{30000}', '\u{3134A}'), ('\u{E0100}', '\u{E01EF}')
+];
+
+/// The general category of all assigned characters. Characters not in this table are unassigned.
+pub(crate) const GENERAL_CATEGORIES:&[(char, char, GeneralCategory)] = &[
+	('\u{0}', '\u{1F}', Control), ('\u{20}', '\u{20}', SpaceSeparator), ('\u{21}', '\u{23}', OtherPunctuation),
+	('\u{24}', '\u{24}', CurrencySymbol), ('\u{25}', '\u{27}', OtherPunctuation), ('\u{28}', '\u{28}', OpenPunctuation),
+	('\u{29}', '\u{29}', ClosePunctuation), ('\u{2A}', '\u{2A}', OtherPunctuation), ('\u{2B}', '\u{2B}', MathSymbol),
+	('\u{2C}', '\u{2C}', OtherPunctuation), ('\u{2D}', '\u{2D}', DashPunctuation), ('\u{2E}', '\u{2F}', OtherPunctuation),
+	('\u{30}', '\u{39}', DecimalNumber), ('\u{3A}', '\u{3B}', OtherPunctuation), ('\u{3C}', '\u{3E}', MathSymbol),
+	('\u{3F}', '\u{40}', OtherPunctuation), ('\u{41}', '\u{5A}', UppercaseLetter), ('\u{5B}', '\u{5B}', OpenPunctuation),
+	('\u{5C}', '\u{5C}', OtherPunctuation), ('\u{5D}', '\u{5D}', ClosePunctuation), ('\u{5E}', '\u{5E}', ModifierSymbol),
+	('\u{5F}', '\u{5F}', ConnectorPunctuation), ('\u{60}', '\u{60}', ModifierSymbol), ('\u{61}', '\u{7A}', LowercaseLetter),
+	('\u{7B}', '\u{7B}', OpenPunctuation), ('\u{7C}', '\u{7C}', MathSymbol), ('\u{7D}', '\u{7D}', ClosePunctuation),
+	('\u{7E}', '\u{7E}', MathSymbol), ('\u{7F}', '\u{9F}', Control), ('\u{A0}', '\u{A0}', SpaceSeparator),
+	('\u{A1}', '\u{A1}', OtherPunctuation), ('\u{A2}', '\u{A5}', CurrencySymbol), ('\u{A6}', '\u{A6}', OtherSymbol),
+	('\u{A7}', '\u{A7}', OtherPunctuation), ('\u{A8}', '\u{A8}', ModifierSymbol), ('\u{A9}', '\u{A9}', OtherSymbol),
+	('\u{AA}', '\u{AA}', OtherLetter), ('\u{AB}', '\u{AB}', InitialPunctuation), ('\u{AC}', '\u{AC}', MathSymbol),
+	('\u{AD}', '\u{AD}', Format), ('\u{AE}', '\u{AE}', OtherSymbol), ('\u{AF}', '\u{AF}', ModifierSymbol),
+	('\u{B0}', '\u{B0}', OtherSymbol), ('\u{B1}', '\u{B1}', MathSymbol), ('\u{B2}', '\u{B3}', OtherNumber),
+	('\u{B4}', '\u{B4}', ModifierSymbol), ('\u{B5}', '\u{B5}', LowercaseLetter), ('\u{B6}', '\u{B7}', OtherPunctuation),
+	('\u{B8}', '\u{B8}', ModifierSymbol), ('\u{B9}', '\u{B9}', OtherNumber), ('\u{BA}', '\u{BA}', OtherLetter),
+	('\u{BB}', '\u{BB}', FinalPunctuation), ('\u{BC}', '\u{BE}', OtherNumber), ('\u{BF}', '\u{BF}', OtherPunctuation),
+	('\u{C0}', '\u{D6}', UppercaseLetter), ('\u{D7}', '\u{D7}', MathSymbol), ('\u{D8}', '\u{DE}', UppercaseLetter),
+	('\u{DF}', '\u{F6}', LowercaseLetter), ('\u{F7}', '\u{F7}', MathSymbol), ('\u{F8}', '\u{FF}', LowercaseLetter),
+	('\u{100}', '\u{100}', UppercaseLetter), ('\u{101}', '\u{101}', LowercaseLetter), ('\u{102}', '\u{102}', UppercaseLetter),
+	('\u{103}', '\u{103}', LowercaseLetter), ('\u{104}', '\u{104}', UppercaseLetter), ('\u{105}', '\u{105}', LowercaseLetter),
+	('\u{106}', '\u{106}', UppercaseLetter), ('\u{107}', '\u{107}', LowercaseLetter), ('\u{108}', '\u{108}', UppercaseLetter),
+	('\u{109}', '\u{109}', LowercaseLetter), ('\u{10A}', '\u{10A}', UppercaseLetter), ('\u{10B}', '\u{10B}', LowercaseLetter),
+	('\u{10C}', '\u{10C}', UppercaseLetter), ('\u{10D}', '\u{10D}', LowercaseLetter), ('\u{10E}', '\u{10E}', UppercaseLetter),
+	('\u{10F}', '\u{10F}', LowercaseLetter), ('\u{110}', '\u{110}', UppercaseLetter), ('\u{111}', '\u{111}', LowercaseLetter),
+	('\u{112}', '\u{112}', UppercaseLetter), ('\u{113}', '\u{113}', LowercaseLetter), ('\u{114}', '\u{114}', UppercaseLetter),
+	('\u{115}', '\u{115}', LowercaseLetter), ('\u{116}', '\u{116}', UppercaseLetter), ('\u{117}', '\u{117}', LowercaseLetter),
+	('\u{118}', '\u{118}', UppercaseLetter), ('\u{119}', '\u{119}', LowercaseLetter), ('\u{11A}', '\u{11A}', UppercaseLetter),
+	('\u{11B}', '\u{11B}', LowercaseLetter), ('\u{11C}', '\u{11C}', UppercaseLetter), ('\u{11D}', '\u{11D}', LowercaseLetter),
+	('\u{11E}', '\u{11E}', UppercaseLetter), ('\u{11F}', '\u{11F}', LowercaseLetter), ('\u{120}', '\u{120}', UppercaseLetter),
+	('\u{121}', '\u{121}', LowercaseLetter), ('\u{122}', '\u{122}', UppercaseLetter), ('\u{123}', '\u{123}', LowercaseLetter),
+	('\u{124}', '\u{124}', UppercaseLetter), ('\u{125}', '\u{125}', LowercaseLetter), ('\u{126}', '\u{126}', UppercaseLetter),
+	('\u{127}', '\u{127}', LowercaseLetter), ('\u{128}', '\u{128}', UppercaseLetter), ('\u{129}', '\u{129}', LowercaseLetter),
+	('\u{12A}', '\u{12A}', UppercaseLetter), ('\u{12B}', '\u{12B}', LowercaseLetter), ('\u{12C}', '\u{12C}', UppercaseLetter),
+	('\u{12D}', '\u{12D}', LowercaseLetter), ('\u{12E}', '\u{12E}', UppercaseLetter), ('\u{12F}', '\u{12F}', LowercaseLetter),
+	('\u{130}', '\u{130}', UppercaseLetter), ('\u{131}', '\u{131}', LowercaseLetter), ('\u{132}', '\u{132}', UppercaseLetter),
+	('\u{133}', '\u{133}', LowercaseLetter), ('\u{134}', '\u{134}', UppercaseLetter), ('\u{135}', '\u{135}', LowercaseLetter),
+	('\u{136}', '\u{136}', UppercaseLetter), ('\u{137}', '\u{138}', LowercaseLetter), ('\u{139}', '\u{139}', UppercaseLetter),
+	('\u{13A}', '\u{13A}', LowercaseLetter), ('\u{13B}', '\u{13B}', UppercaseLetter), ('\u{13C}', '\u{13C}', LowercaseLetter),
+	('\u{13D}', '\u{13D}', UppercaseLetter), ('\u{13E}', '\u{13E}', LowercaseLetter), ('\u{13F}', '\u{13F}', UppercaseLetter),
+	('\u{140}', '\u{140}', LowercaseLetter), ('\u{141}', '\u{141}', UppercaseLetter), ('\u{142}', '\u{142}', LowercaseLetter),
+	('\u{143}', '\u{143}', UppercaseLetter), ('\u{144}', '\u{144}', LowercaseLetter), ('\u{145}', '\u{145}', UppercaseLetter),
+	('\u{146}', '\u{146}', LowercaseLetter), ('\u{147}', '\u{147}', UppercaseLetter), ('\u{148}', '\u{149}', LowercaseLetter),
+	('\u{14A}', '\u{14A}', UppercaseLetter), ('\u{14B}', '\u{14B}', LowercaseLetter), ('\u{14C}', '\u{14C}', UppercaseLetter),
+	('\u{14D}', '\u{14D}', LowercaseLetter), ('\u{14E}', '\u{14E}', UppercaseLetter), ('\u{14F}', '\u{14F}', LowercaseLetter),
+	('\u{150}', '\u{150}', UppercaseLetter), ('\u{151}', '\u{151}', LowercaseLetter), ('\u{152}', '\u{152}', UppercaseLetter),
+	('\u{153}', '\u{153}', LowercaseLetter), ('\u{154}', '\u{154}', UppercaseLetter), ('\u{155}', '\u{155}', LowercaseLetter),
+	('\u{156}', '\u{156}', UppercaseLetter), ('\u{157}', '\u{157}', LowercaseLetter), ('\u{158}', '\u{158}', UppercaseLetter),
+	('\u{159}', '\u{159}', LowercaseLetter), ('\u{15A}', '\u{15A}', UppercaseLetter), ('\u{15B}', '\u{15B}', LowercaseLetter),
+	('\u{15C}', '\u{15C}', UppercaseLetter), ('\u{15D}', '\u{15D}', LowercaseLetter), ('\u{15E}', '\u{15E}', UppercaseLetter),
+	('\u{15F}', '\u{15F}', LowercaseLetter), ('\u{160}', '\u{160}', UppercaseLetter), ('\u{161}', '\u{161}', LowercaseLetter),
+	('\u{162}', '\u{162}', UppercaseLetter), ('\u{163}', '\u{163}', LowercaseLetter), ('\u{164}', '\u{164}', UppercaseLetter),
+	('\u{165}', '\u{165}', LowercaseLetter), ('\u{166}', '\u{166}', UppercaseLetter), ('\u{167}', '\u{167}', LowercaseLetter),
+	('\u{168}', '\u{168}', UppercaseLetter), ('\u{169}', '\u{169}', LowercaseLetter), ('\u{16A}', '\u{16A}', UppercaseLetter),
+	('\u{16B}', '\u{16B}', LowercaseLetter), ('\u{16C}', '\u{16C}', UppercaseLetter), ('\u{16D}', '\u{16D}', LowercaseLetter),
+	('\u{16E}', '\u{16E}', UppercaseLetter), ('\u{16F}', '\u{16F}', LowercaseLetter), ('\u{170}', '\u{170}', UppercaseLetter),
+	('\u{171}', '\u{171}', LowercaseLetter), ('\u{172}', '\u{172}', UppercaseLetter), ('\u{173}', '\u{173}', LowercaseLetter),
+	('\u{174}', '\u{174}', UppercaseLetter), ('\u{175}', '\u{175}', LowercaseLetter), ('\u{176}', '\u{176}', UppercaseLetter),
+	('\u{177}', '\u{177}', LowercaseLetter), ('\u{178}', '\u{179}', UppercaseLetter), ('\u{17A}', '\u{17A}', LowercaseLetter),
+	('\u{17B}', '\u{17B}', UppercaseLetter), ('\u{17C}', '\u{17C}', LowercaseLetter), ('\u{17D}', '\u{17D}', UppercaseLetter),
+	('\u{17E}', '\u{180}', LowercaseLetter), ('\u{181}', '\u{182}', UppercaseLetter), ('\u{183}', '\u{183}', LowercaseLetter),
+	('\u{184}', '\u{184}', UppercaseLetter), ('\u{185}', '\u{185}', LowercaseLetter), ('\u{186}', '\u{187}', UppercaseLetter),
+	('\u{188}', '\u{188}', LowercaseLetter), ('\u{189}', '\u{18B}', UppercaseLetter), ('\u{18C}', '\u{18D}', LowercaseLetter),
+	('\u{18E}', '\u{191}', UppercaseLetter), ('\u{192}', '\u{192}', LowercaseLetter), ('\u{193}', '\u{194}', UppercaseLetter),
+	('\u{195}', '\u{195}', LowercaseLetter), ('\u{196}', '\u{198}', UppercaseLetter), ('\u{199}', '\u{19B}', LowercaseLetter),
+	('\u{19C}', '\u{19D}', UppercaseLetter), ('\u{19E}', '\u{19E}', LowercaseLetter), ('\u{19F}', '\u{1A0}', UppercaseLetter),
+	('\u{1A1}', '\u{1A1}', LowercaseLetter), ('\u{1A2}', '\u{1A2}', UppercaseLetter), ('\u{1A3}', '\u{1A3}', LowercaseLetter),
+	('\u{1A4}', '\u{1A4}', UppercaseLetter), ('\u{1A5}', '\u{1A5}', LowercaseLetter), ('\u{1A6}', '\u{1A7}', UppercaseLetter),
+	('\u{1A8}', '\u{1A8}', LowercaseLetter), ('\u{1A9}', '\u{1A9}', UppercaseLetter), ('\u{1AA}', '\u{1AB}', LowercaseLetter),
+	('\u{1AC}', '\u{1AC}', UppercaseLetter), ('\u{1AD}', '\u{1AD}', LowercaseLetter), ('\u{1AE}', '\u{1AF}', UppercaseLetter),
+	('\u{1B0}', '\u{1B0}', LowercaseLetter), ('\u{1B1}', '\u{1B3}', UppercaseLetter), ('\u{1B4}', '\u{1B4}', LowercaseLetter),
+	('\u{1B5}', '\u{1B5}', UppercaseLetter), ('\u{1B6}', '\u{1B6}', LowercaseLetter), ('\u{1B7}', '\u{1B8}', UppercaseLetter),
+	('\u{1B9}', '\u{1BA}', LowercaseLetter), ('\u{1BB}', '\u{1BB}', OtherLetter), ('\u{1BC}', '\u{1BC}', UppercaseLetter),
+	('\u{1BD}', '\u{1BF}', LowercaseLetter), ('\u{1C0}', '\u{1C3}', OtherLetter), ('\u{1C4}', '\u{1C4}', UppercaseLetter),
+	('\u{1C5}', '\u{1C5}', TitlecaseLetter), ('\u{1C6}', '\u{1C6}', LowercaseLetter), ('\u{1C7}', '\u{1C7}', UppercaseLetter),
+	('\u{1C8}', '\u{1C8}', TitlecaseLetter), ('\u{1C9}', '\u{1C9}', LowercaseLetter), ('\u{1CA}', '\u{1CA}', UppercaseLetter),
+	('\u{1CB}', '\u{1CB}', TitlecaseLetter), ('\u{1CC}', '\u{1CC}', LowercaseLetter), ('\u{1CD}', '\u{1CD}', UppercaseLetter),
+	('\u{1CE}', '\u{1CE}', LowercaseLetter), ('\u{1CF}', '\u{1CF}', UppercaseLetter), ('\u{1D0}', '\u{1D0}', LowercaseLetter),
+	('\u{1D1}', '\u{1D1}', UppercaseLetter), ('\u{1D2}', '\u{1D2}', LowercaseLetter), ('\u{1D3}', '\u{1D3}', UppercaseLetter),
+	('\u{1D4}', '\u{1D4}', LowercaseLetter), ('\u{1D5}', '\u{1D5}', UppercaseLetter), ('\u{1D6}', '\u{1D6}', LowercaseLetter),
+	('\u{1D7}', '\u{1D7}', UppercaseLetter), ('\u{1D8}', '\u{1D8}', LowercaseLetter), ('\u{1D9}', '\u{1D9}', UppercaseLetter),
+	('\u{1DA}', '\u{1DA}', LowercaseLetter), ('\u{1DB}', '\u{1DB}', UppercaseLetter), ('\u{1DC}', '\u{1DD}', LowercaseLetter),
+	('\u{1DE}', '\u{1DE}', UppercaseLetter), ('\u{1DF}', '\u{1DF}', LowercaseLetter), ('\u{1E0}', '\u{1E0}', UppercaseLetter),
+	('\u{1E1}', '\u{1E1}', LowercaseLetter), ('\u{1E2}', '\u{1E2}', UppercaseLetter), ('\u{1E3}', '\u{1E3}', LowercaseLetter),
+	('\u{1E4}', '\u{1E4}', UppercaseLetter), ('\u{1E5}', '\u{1E5}', LowercaseLetter), ('\u{1E6}', '\u{1E6}', UppercaseLetter),
+	('\u{1E7}', '\u{1E7}', LowercaseLetter), ('\u{1E8}', '\u{1E8}', UppercaseLetter), ('\u{1E9}', '\u{1E9}', LowercaseLetter),
+	('\u{1EA}', '\u{1EA}', UppercaseLetter), ('\u{1EB}', '\u{1EB}', LowercaseLetter), ('\u{1EC}', '\u{1EC}', UppercaseLetter),
+	('\u{1ED}', '\u{1ED}', LowercaseLetter), ('\u{1EE}', '\u{1EE}', UppercaseLetter), ('\u{1EF}', '\u{1F0}', LowercaseLetter),
+	('\u{1F1}', '\u{1F1}', UppercaseLetter), ('\u{1F2}', '\u{1F2}', TitlecaseLetter), ('\u{1F3}', '\u{1F3}', LowercaseLetter),
+	('\u{1F4}', '\u{1F4}', UppercaseLetter), ('\u{1F5}', '\u{1F5}', LowercaseLetter), ('\u{1F6}', '\u{1F8}', UppercaseLetter),
+	('\u{1F9}', '\u{1F9}', LowercaseLetter), ('\u{1FA}', '\u{1FA}', UppercaseLetter), ('\u{1FB}', '\u{1FB}', LowercaseLetter),
+	('\u{1FC}', '\u{1FC}', UppercaseLetter), ('\u{1FD}', '\u{1FD}', LowercaseLetter), ('\u{1FE}', '\u{1FE}', UppercaseLetter),
+	('\u{1FF}', '\u{1FF}', LowercaseLetter), ('\u{200}', '\u{200}', UppercaseLetter), ('\u{201}', '\u{201}', LowercaseLetter),
+	('\u{202}', '\u{202}', UppercaseLetter), ('\u{203}', '\u{203}', LowercaseLetter), ('\u{204}', '\u{204}', UppercaseLetter),
+	('\u{205}', '\u{205}', LowercaseLetter), ('\u{206}', '\u{206}', UppercaseLetter), ('\u{207}', '\u{207}', LowercaseLetter),
+	('\u{208}', '\u{208}', UppercaseLetter), ('\u{209}', '\u{209}', LowercaseLetter), ('\u{20A}', '\u{20A}', UppercaseLetter),
+	('\u{20B}', '\u{20B}', LowercaseLetter), ('\u{20C}', '\u{20C}', UppercaseLetter), ('\u{20D}', '\u{20D}', LowercaseLetter),
+	('\u{20E}', '\u{20E}', UppercaseLetter), ('\u{20F}', '\u{20F}', LowercaseLetter), ('\u{210}', '\u{210}', UppercaseLetter),
+	('\u{211}', '\u{211}', LowercaseLetter), ('\u{212}', '\u{212}', UppercaseLetter), ('\u{213}', '\u{213}', LowercaseLetter),
+	('\u{214}', '\u{214}', UppercaseLetter), ('\u{215}', '\u{215}', LowercaseLetter), ('\u{216}', '\u{216}', UppercaseLetter),
+	('\u{217}', '\u{217}', LowercaseLetter), ('\u{218}', '\u{218}', UppercaseLetter), ('\u{219}', '\u{219}', LowercaseLetter),
+	('\u{21A}', '\u{21A}', UppercaseLetter), ('\u{21B}', '\u{21B}', LowercaseLetter), ('\u{21C}', '\u{21C}', UppercaseLetter),
+	('\u{21D}', '\u{21D}', LowercaseLetter), ('\u{21E}', '\u{21E}', UppercaseLetter), ('\u{21F}', '\u{21F}', LowercaseLetter),
+	('\u{220}', '\u{220}', UppercaseLetter), ('\u{221}', '\u{221}', LowercaseLetter), ('\u{222}', '\u{222}', UppercaseLetter),
+	('\u{223}', '\u{223}', LowercaseLetter), ('\u{224}', '\u{224}', UppercaseLetter), ('\u{225}', '\u{225}', LowercaseLetter),
+	('\u{226}', '\u{226}', UppercaseLetter), ('\u{227}', '\u{227}', LowercaseLetter), ('\u{228}', '\u{228}', UppercaseLetter),
+	('\u{229}', '\u{229}', LowercaseLetter), ('\u{22A}', '\u{22A}', UppercaseLetter), ('\u{22B}', '\u{22B}', LowercaseLetter),
+	('\u{22C}', '\u{22C}', UppercaseLetter), ('\u{22D}', '\u{22D}', LowercaseLetter), ('\u{22E}', '\u{22E}', UppercaseLetter),
+	('\u{22F}', '\u{22F}', LowercaseLetter), ('\u{230}', '\u{230}', UppercaseLetter), ('\u{231}', '\u{231}', LowercaseLetter),
+	('\u{232}', '\u{232}', UppercaseLetter), ('\u{233}', '\u{239}', LowercaseLetter), ('\u{23A}', '\u{23B}', UppercaseLetter),
+	('\u{23C}', '\u{23C}', LowercaseLetter), ('\u{23D}', '\u{23E}', UppercaseLetter), ('\u{23F}', '\u{240}', LowercaseLetter),
+	('\u{241}', '\u{241}', UppercaseLetter), ('\u{242}', '\u{242}', LowercaseLetter), ('\u{243}', '\u{246}', UppercaseLetter),
+	('\u{247}', '\u{247}', LowercaseLetter), ('\u{248}', '\u{248}', UppercaseLetter), ('\u{249}', '\u{249}', LowercaseLetter),
+	('\u{24A}', '\u{24A}', UppercaseLetter), ('\u{24B}', '\u{24B}', LowercaseLetter), ('\u{24C}', '\u{24C}', UppercaseLetter),
+	('\u{24D}', '\u{24D}', LowercaseLetter), ('\u{24E}', '\u{24E}', UppercaseLetter), ('\u{24F}', '\u{293}', LowercaseLetter),
+	('\u{294}', '\u{294}', OtherLetter), ('\u{295}', '\u{2AF}', LowercaseLetter), ('\u{2B0}', '\u{2C1}', ModifierLetter),
+	('\u{2C2}', '\u{2C5}', ModifierSymbol), ('\u{2C6}', '\u{2D1}', ModifierLetter), ('\u{2D2}', '\u{2DF}', ModifierSymbol),
+	('\u{2E0}', '\u{2E4}', ModifierLetter), ('\u{2E5}', '\u{2EB}', ModifierSymbol), ('\u{2EC}', '\u{2EC}', ModifierLetter),
+	('\u{2ED}', '\u{2ED}', ModifierSymbol), ('\u{2EE}', '\u{2EE}', ModifierLetter), ('\u{2EF}', '\u{2FF}', ModifierSymbol),
+	('\u{300}', '\u{36F}', NonspacingMark), ('\u{370}', '\u{370}', UppercaseLetter), ('\u{371}', '\u{371}', LowercaseLetter),
+	('\u{372}', '\u{372}', UppercaseLetter), ('\u{373}', '\u{373}', LowercaseLetter), ('\u{374}', '\u{374}', ModifierLetter),
+	('\u{375}', '\u{375}', ModifierSymbol), ('\u{376}', '\u{376}', UppercaseLetter), ('\u{377}', '\u{377}', LowercaseLetter),
+	('\u{37A}', '\u{37A}', ModifierLetter), ('\u{37B}', '\u{37D}', LowercaseLetter), ('\u{37E}', '\u{37E}', OtherPunctuation),
+	('\u{37F}', '\u{37F}', UppercaseLetter), ('\u{384}', '\u{385}', ModifierSymbol), ('\u{386}', '\u{386}', UppercaseLetter),
+	('\u{387}', '\u{387}', OtherPunctuation), ('\u{388}', '\u{38A}', UppercaseLetter), ('\u{38C}', '\u{38C}', UppercaseLetter),
+	('\u{38E}', '\u{38F}', UppercaseLetter), ('\u{390}', '\u{390}', LowercaseLetter), ('\u{391}', '\u{3A1}', UppercaseLetter),
+	('\u{3A3}', '\u{3AB}', UppercaseLetter), ('\u{3AC}', '\u{3CE}', LowercaseLetter), ('\u{3CF}', '\u{3CF}', UppercaseLetter),
+	('\u{3D0}', '\u{3D1}', LowercaseLetter), ('\u{3D2}', '\u{3D4}', UppercaseLetter), ('\u{3D5}', '\u{3D7}', LowercaseLetter),
+	('\u{3D8}', '\u{3D8}', UppercaseLetter), ('\u{3D9}', '\u{3D9}', LowercaseLetter), ('\u{3DA}', '\u{3DA}', UppercaseLetter),
+	('\u{3DB}', '\u{3DB}', LowercaseLetter), ('\u{3DC}', '\u{3DC}', UppercaseLetter), ('\u{3DD}', '\u{3DD}', LowercaseLetter),
+	('\u{3DE}', '\u{3DE}', UppercaseLetter), ('\u{3DF}', '\u{3DF}', LowercaseLetter), ('\u{3E0}', '\u{3E0}', UppercaseLetter),
+	('\u{3E1}', '\u{3E1}', LowercaseLetter), ('\u{3E2}', '\u{3E2}', UppercaseLetter), ('\u{3E3}', '\u{3E3}', LowercaseLetter),
+	('\u{3E4}', '\u{3E4}', UppercaseLetter), ('\u{3E5}', '\u{3E5}', LowercaseLetter), ('\u{3E6}', '\u{3E6}', UppercaseLetter),
+	('\u{3E7}', '\u{3E7}', LowercaseLetter), ('\u{3E8}', '\u{3E8}', UppercaseLetter), ('\u{3E9}', '\u{3E9}', LowercaseLetter),
+	('\u{3EA}', '\u{3EA}', UppercaseLetter), ('\u{3EB}', '\u{3EB}', LowercaseLetter), ('\u{3EC}', '\u{3EC}', UppercaseLetter),
+	('\u{3ED}', '\u{3ED}', LowercaseLetter), ('\u{3EE}', '\u{3EE}', UppercaseLetter), ('\u{3EF}', '\u{3F3}', LowercaseLetter),
+	('\u{3F4}', '\u{3F4}', UppercaseLetter), ('\u{3F5}', '\u{3F5}', LowercaseLetter), ('\u{3F6}', '\u{3F6}', MathSymbol),
+	('\u{3F7}', '\u{3F7}', UppercaseLetter), ('\u{3F8}', '\u{3F8}', LowercaseLetter), ('\u{3F9}', '\u{3FA}', UppercaseLetter),
+	('\u{3FB}', '\u{3FC}', LowercaseLetter), ('\u{3FD}', '\u{42F}', UppercaseLetter), ('\u{430}', '\u{45F}', LowercaseLetter),
+	('\u{460}', '\u{460}', UppercaseLetter), ('\u{461}', '\u{461}', LowercaseLetter), ('\u{462}', '\u{462}', UppercaseLetter),
+	('\u{463}', '\u{463}', LowercaseLetter), ('\u{464}', '\u{464}', UppercaseLetter), ('\u{465}', '\u{465}', LowercaseLetter),
+	('\u{466}', '\u{466}', UppercaseLetter), ('\u{467}', '\u{467}', LowercaseLetter), ('\u{468}', '\u{468}', UppercaseLetter),
+	('\u{469}', '\u{469}', LowercaseLetter), ('\u{46A}', '\u{46A}', UppercaseLetter), ('\u{46B}', '\u{46B}', LowercaseLetter),
+	('\u{46C}', '\u{46C}', UppercaseLetter), ('\u{46D}', '\u{46D}', LowercaseLetter), ('\u{46E}', '\u{46E}', UppercaseLetter),
+	('\u{46F}', '\u{46F}', LowercaseLetter), ('\u{470}', '\u{470}', UppercaseLetter), ('\u{471}', '\u{471}', LowercaseLetter),
+	('\u{472}', '\u{472}', UppercaseLetter), ('\u{473}', '\u{473}', LowercaseLetter), ('\u{474}', '\u{474}', UppercaseLetter),
+	('\u{475}', '\u{475}', LowercaseLetter), ('\u{476}', '\u{476}', UppercaseLetter), ('\u{477}', '\u{477}', LowercaseLetter),
+	('\u{478}', '\u{478}', UppercaseLetter), ('\u{479}', '\u{479}', LowercaseLetter), ('\u{47A}', '\u{47A}', UppercaseLetter),
+	('\u{47B}', '\u{47B}', LowercaseLetter), ('\u{47C}', '\u{47C}', UppercaseLetter), ('\u{47D}', '\u{47D}', LowercaseLetter),
+	('\u{47E}', '\u{47E}', UppercaseLetter), ('\u{47F}', '\u{47F}', LowercaseLetter), ('\u{480}', '\u{480}', UppercaseLetter),
+	('\u{481}', '\u{481}', LowercaseLetter), ('\u{482}', '\u{482}', OtherSymbol), ('\u{483}', '\u{487}', NonspacingMark),
+	('\u{488}', '\u{489}', EnclosingMark), ('\u{48A}', '\u{48A}', UppercaseLetter), ('\u{48B}', '\u{48B}', LowercaseLetter),
+	('\u{48C}', '\u{48C}', UppercaseLetter), ('\u{48D}', '\u{48D}', LowercaseLetter), ('\u{48E}', '\u{48E}', UppercaseLetter),
+	('\u{48F}', '\u{48F}', LowercaseLetter), ('\u{490}', '\u{490}', UppercaseLetter), ('\u{491}', '\u{491}', LowercaseLetter),
+	('\u{492}', '\u{492}', UppercaseLetter), ('\u{493}', '\u{493}', LowercaseLetter), ('\u{494}', '\u{494}', UppercaseLetter),
+	('\u{495}', '\u{495}', LowercaseLetter), ('\u{496}', '\u{496}', UppercaseLetter), ('\u{497}', '\u{497}', LowercaseLetter),
+	('\u{498}', '\u{498}', UppercaseLetter), ('\u{499}', '\u{499}', LowercaseLetter), ('\u{49A}', '\u{49A}', UppercaseLetter),
+	('\u{49B}', '\u{49B}', LowercaseLetter), ('\u{49C}', '\u{49C}', UppercaseLetter), ('\u{49D}', '\u{49D}', LowercaseLetter),
+	('\u{49E}', '\u{49E}', UppercaseLetter), ('\u{49F}', '\u{49F}', LowercaseLetter), ('\u{4A0}', '\u{4A0}', UppercaseLetter),
+	('\u{4A1}', '\u{4A1}', LowercaseLetter), ('\u{4A2}', '\u{4A2}', UppercaseLetter), ('\u{4A3}', '\u{4A3}', LowercaseLetter),
+	('\u{4A4}', '\u{4A4}', UppercaseLetter), ('\u{4A5}', '\u{4A5}', LowercaseLetter), ('\u{4A6}', '\u{4A6}', UppercaseLetter),
+	('\u{4A7}', '\u{4A7}', LowercaseLetter), ('\u{4A8}', '\u{4A8}', UppercaseLetter), ('\u{4A9}', '\u{4A9}', LowercaseLetter),
+	('\u{4AA}', '\u{4AA}', UppercaseLetter), ('\u{4AB}', '\u{4AB}', LowercaseLetter), ('\u{4AC}', '\u{4AC}', UppercaseLetter),
+	('\u{4AD}', '\u{4AD}', LowercaseLetter), ('\u{4AE}', '\u{4AE}', UppercaseLetter), ('\u{4AF}', '\u{4AF}', LowercaseLetter),
+	('\u{4B0}', '\u{4B0}', UppercaseLetter), ('\u{4B1}', '\u{4B1}', LowercaseLetter), ('\u{4B2}', '\u{4B2}', UppercaseLetter),
+	('\u{4B3}', '\u{4B3}', LowercaseLetter), ('\u{4B4}', '\u{4B4}', UppercaseLetter), ('\u{4B5}', '\u{4B5}', LowercaseLetter),
+	('\u{4B6}', '\u{4B6}', UppercaseLetter), ('\u{4B7}', '\u{4B7}', LowercaseLetter), ('\u{4B8}', '\u{4B8}', UppercaseLetter),
+	('\u{4B9}', '\u{4B9}', LowercaseLetter), ('\u{4BA}', '\u{4BA}', UppercaseLetter), ('\u{4BB}', '\u{4BB}', LowercaseLetter),
+	('\u{4BC}', '\u{4BC}', UppercaseLetter), ('\u{4BD}', '\u{4BD}', LowercaseLetter), ('\u{4BE}', '\u{4BE}', UppercaseLetter),
+	('\u{4BF}', '\u{4BF}', LowercaseLetter), ('\u{4C0}', '\u{4C1}', UppercaseLetter), ('\u{4C2}', '\u{4C2}', LowercaseLetter),
+	('\u{4C3}', '\u{4C3}', UppercaseLetter), ('\u{4C4}', '\u{4C4}', LowercaseLetter), ('\u{4C5}', '\u{4C5}', UppercaseLetter),
+	('\u{4C6}', '\u{4C6}', LowercaseLetter), ('\u{4C7}', '\u{4C7}', UppercaseLetter), ('\u{4C8}', '\u{4C8}', LowercaseLetter),
+	('\u{4C9}', '\u{4C9}', UppercaseLetter), ('\u{4CA}', '\u{4CA}', LowercaseLetter), ('\u{4CB}', '\u{4CB}', UppercaseLetter),
+	('\u{4CC}', '\u{4CC}', LowercaseLetter), ('\u{4CD}', '\u{4CD}', UppercaseLetter), ('\u{4CE}', '\u{4CF}', LowercaseLetter),
+	('\u{4D0}', '\u{4D0}', UppercaseLetter), ('\u{4D1}', '\u{4D1}', LowercaseLetter), ('\u{4D2}', '\u{4D2}', UppercaseLetter),
+	('\u{4D3}', '\u{4D3}', LowercaseLetter), ('\u{4D4}', '\u{4D4}', UppercaseLetter), ('\u{4D5}', '\u{4D5}', LowercaseLetter),
+	('\u{4D6}', '\u{4D6}', UppercaseLetter), ('\u{4D7}', '\u{4D7}', LowercaseLetter), ('\u{4D8}', '\u{4D8}', UppercaseLetter),
+	('\u{4D9}', '\u{4D9}', LowercaseLetter), ('\u{4DA}', '\u{4DA}', UppercaseLetter), ('\u{4DB}', '\u{4DB}', LowercaseLetter),
+	('\u{4DC}', '\u{4DC}', UppercaseLetter), ('\u{4DD}', '\u{4DD}', LowercaseLetter), ('\u{4DE}', '\u{4DE}', UppercaseLetter),
+	('\u{4DF}', '\u{4DF}', LowercaseLetter), ('\u{4E0}', '\u{4E0}', UppercaseLetter), ('\u{4E1}', '\u{4E1}', LowercaseLetter),
+	('\u{4E2}', '\u{4E2}', UppercaseLetter), ('\u{4E3}', '\u{4E3}', LowercaseLetter), ('\u{4E4}', '\u{4E4}', UppercaseLetter),
+	('\u{4E5}', '\u{4E5}', LowercaseLetter), ('\u{4E6}', '\u{4E6}', UppercaseLetter), ('\u{4E7}', '\u{4E7}', LowercaseLetter),
+	('\u{4E8}', '\u{4E8}', UppercaseLetter), ('\u{4E9}', '\u{4E9}', LowercaseLetter), ('\u{4EA}', '\u{4EA}', UppercaseLetter),
+	('\u{4EB}', '\u{4EB}', LowercaseLetter), ('\u{4EC}', '\u{4EC}', UppercaseLetter), ('\u{4ED}', '\u{4ED}', LowercaseLetter),
+	('\u{4EE}', '\u{4EE}', UppercaseLetter), ('\u{4EF}', '\u{4EF}', LowercaseLetter), ('\u{4F0}', '\u{4F0}', UppercaseLetter),
+	('\u{4F1}', '\u{4F1}', LowercaseLetter), ('\u{4F2}', '\u{4F2}', UppercaseLetter), ('\u{4F3}', '\u{4F3}', LowercaseLetter),
+	('\u{4F4}', '\u{4F4}', UppercaseLetter), ('\u{4F5}', '\u{4F5}', LowercaseLetter), ('\u{4F6}', '\u{4F6}', UppercaseLetter),
+	('\u{4F7}', '\u{4F7}', LowercaseLetter), ('\u{4F8}', '\u{4F8}', UppercaseLetter), ('\u{4F9}', '\u{4F9}', LowercaseLetter),
+	('\u{4FA}', '\u{4FA}', UppercaseLetter), ('\u{4FB}', '\u{4FB}', LowercaseLetter), ('\u{4FC}', '\u{4FC}', UppercaseLetter),
+	('\u{4FD}', '\u{4FD}', LowercaseLetter), ('\u{4FE}', '\u{4FE}', UppercaseLetter), ('\u{4FF}', '\u{4FF}', LowercaseLetter),
+	('\u{500}', '\u{500}', UppercaseLetter), ('\u{501}', '\u{501}', LowercaseLetter), ('\u{502}', '\u{502}', UppercaseLetter),
+	('\u{503}', '\u{503}', LowercaseLetter), ('\u{504}', '\u{504}', UppercaseLetter), ('\u{505}', '\u{505}', LowercaseLetter),
+	('\u{506}', '\u{506}', UppercaseLetter), ('\u{507}', '\u{507}', LowercaseLetter), ('\u{508}', '\u{508}', UppercaseLetter),
+	('\u{509}', '\u{509}', LowercaseLetter), ('\u{50A}', '\u{50A}', UppercaseLetter), ('\u{50B}', '\u{50B}', LowercaseLetter),
+	('\u{50C}', '\u{50C}', UppercaseLetter), ('\u{50D}', '\u{50D}', LowercaseLetter), ('\u{50E}', '\u{50E}', UppercaseLetter),
+	('\u{50F}', '\u{50F}', LowercaseLetter), ('\u{510}', '\u{510}', UppercaseLetter), ('\u{511}', '\u{511}', LowercaseLetter),
+	('\u{512}', '\u{512}', UppercaseLetter), ('\u{513}', '\u{513}', LowercaseLetter), ('\u{514}', '\u{514}', UppercaseLetter),
+	('\u{515}', '\u{515}', LowercaseLetter), ('\u{516}', '\u{516}', UppercaseLetter), ('\u{517}', '\u{517}', LowercaseLetter),
+	('\u{518}', '\u{518}', UppercaseLetter), ('\u{519}', '\u{519}', LowercaseLetter), ('\u{51A}', '\u{51A}', UppercaseLetter),
+	('\u{51B}', '\u{51B}', LowercaseLetter), ('\u{51C}', '\u{51C}', UppercaseLetter), ('\u{51D}', '\u{51D}', LowercaseLetter),
+	('\u{51E}', '\u{51E}', UppercaseLetter), ('\u{51F}', '\u{51F}', LowercaseLetter), ('\u{520}', '\u{520}', UppercaseLetter),
+	('\u{521}', '\u{521}', LowercaseLetter), ('\u{522}', '\u{522}', UppercaseLetter), ('\u{523}', '\u{523}', LowercaseLetter),
+	('\u{524}', '\u{524}', UppercaseLetter), ('\u{525}', '\u{525}', LowercaseLetter), ('\u{526}', '\u{526}', UppercaseLetter),
+	('\u{527}', '\u{527}', LowercaseLetter), ('\u{528}', '\u{528}', UppercaseLetter), ('\u{529}', '\u{529}', LowercaseLetter),
+	('\u{52A}', '\u{52A}', UppercaseLetter), ('\u{52B}', '\u{52B}', LowercaseLetter), ('\u{52C}', '\u{52C}', UppercaseLetter),
+	('\u{52D}', '\u{52D}', LowercaseLetter), ('\u{52E}', '\u{52E}', UppercaseLetter), ('\u{52F}', '\u{52F}', LowercaseLetter),
+	('\u{531}', '\u{556}', UppercaseLetter), ('\u{559}', '\u{559}', ModifierLetter), ('\u{55A}', '\u{55F}', OtherPunctuation),
+	('\u{560}', '\u{588}', LowercaseLetter), ('\u{589}', '\u{589}', OtherPunctuation), ('\u{58A}', '\u{58A}', DashPunctuation),
+	('\u{58D}', '\u{58E}', OtherSymbol), ('\u{58F}', '\u{58F}', CurrencySymbol), ('\u{591}', '\u{5BD}', NonspacingMark),
+	('\u{5BE}', '\u{5BE}', DashPunctuation), ('\u{5BF}', '\u{5BF}', NonspacingMark), ('\u{5C0}', '\u{5C0}', OtherPunctuation),
+	('\u{5C1}', '\u{5C2}', NonspacingMark), ('\u{5C3}', '\u{5C3}', OtherPunctuation), ('\u{5C4}', '\u{5C5}', NonspacingMark),
+	('\u{5C6}', '\u{5C6}', OtherPunctuation), ('\u{5C7}', '\u{5C7}', NonspacingMark), ('\u{5D0}', '\u{5EA}', OtherLetter),
+	('\u{5EF}', '\u{5F2}', OtherLetter), ('\u{5F3}', '\u{5F4}', OtherPunctuation), ('\u{600}', '\u{605}', Format),
+	('\u{606}', '\u{608}', MathSymbol), ('\u{609}', '\u{60A}', OtherPunctuation), ('\u{60B}', '\u{60B}', CurrencySymbol),
+	('\u{60C}', '\u{60D}', OtherPunctuation), ('\u{60E}', '\u{60F}', OtherSymbol), ('\u{610}', '\u{61A}', NonspacingMark),
+	('\u{61B}', '\u{61B}', OtherPunctuation), ('\u{61C}', '\u{61C}', Format), ('\u{61D}', '\u{61F}', OtherPunctuation),
+	('\u{620}', '\u{63F}', OtherLetter), ('\u{640}', '\u{640}', ModifierLetter), ('\u{641}', '\u{64A}', OtherLetter),
+	('\u{64B}', '\u{65F}', NonspacingMark), ('\u{660}', '\u{669}', DecimalNumber), ('\u{66A}', '\u{66D}', OtherPunctuation),
+	('\u{66E}', '\u{66F}', OtherLetter), ('\u{670}', '\u{670}', NonspacingMark), ('\u{671}', '\u{6D3}', OtherLetter),
+	('\u{6D4}', '\u{6D4}', OtherPunctuation), ('\u{6D5}', '\u{6D5}', OtherLetter), ('\u{6D6}', '\u{6DC}', NonspacingMark),
+	('\u{6DD}', '\u{6DD}', Format), ('\u{6DE}', '\u{6DE}', OtherSymbol), ('\u{6DF}', '\u{6E4}', NonspacingMark),
+	('\u{6E5}', '\u{6E6}', ModifierLetter), ('\u{6E7}', '\u{6E8}', NonspacingMark), ('\u{6E9}', '\u{6E9}', OtherSymbol),
+	('\u{6EA}', '\u{6ED}', NonspacingMark), ('\u{6EE}', '\u{6EF}', OtherLetter), ('\u{6F0}', '\u{6F9}', DecimalNumber),
+	('\u{6FA}', '\u{6FC}', OtherLetter), ('\u{6FD}', '\u{6FE}', OtherSymbol), ('\u{6FF}', '\u{6FF}', OtherLetter),
+	('\u{700}', '\u{70D}', OtherPunctuation), ('\u{70F}', '\u{70F}', Format), ('\u{710}', '\u{710}', OtherLetter),
+	('\u{711}', '\u{711}', NonspacingMark), ('\u{712}', '\u{72F}', OtherLetter), ('\u{730}', '\u{74A}', NonspacingMark),
+	('\u{74D}', '\u{7A5}', OtherLetter), ('\u{7A6}', '\u{7B0}', NonspacingMark), ('\u{7B1}', '\u{7B1}', OtherLetter),
+	('\u{7C0}', '\u{7C9}', DecimalNumber), ('\u{7CA}', '\u{7EA}', OtherLetter), ('\u{7EB}', '\u{7F3}', NonspacingMark),
+	('\u{7F4}', '\u{7F5}', ModifierLetter), ('\u{7F6}', '\u{7F6}', OtherSymbol), ('\u{7F7}', '\u{7F9}', OtherPunctuation),
+	('\u{7FA}', '\u{7FA}', ModifierLetter), ('\u{7FD}', '\u{7FD}', NonspacingMark), ('\u{7FE}', '\u{7FF}', CurrencySymbol),
+	('\u{800}', '\u{815}', OtherLetter), ('\u{816}', '\u{819}', NonspacingMark), ('\u{81A}', '\u{81A}', ModifierLetter),
+	('\u{81B}', '\u{823}', NonspacingMark), ('\u{824}', '\u{824}', ModifierLetter), ('\u{825}', '\u{827}', NonspacingMark),
+	('\u{828}', '\u{828}', ModifierLetter), ('\u{829}', '\u{82D}', NonspacingMark), ('\u{830}', '\u{83E}', OtherPunctuation),
+	('\u{840}', '\u{858}', OtherLetter), ('\u{859}', '\u{85B}', NonspacingMark), ('\u{85E}', '\u{85E}', OtherPunctuation),
+	('\u{860}', '\u{86A}', OtherLetter), ('\u{870}', '\u{887}', OtherLetter), ('\u{888}', '\u{888}', ModifierSymbol),
+	('\u{889}', '\u{88E}', OtherLetter), ('\u{890}', '\u{891}', Format), ('\u{898}', '\u{89F}', NonspacingMark),
+	('\u{8A0}', '\u{8C8}', OtherLetter), ('\u{8C9}', '\u{8C9}', ModifierLetter), ('\u{8CA}', '\u{8E1}', NonspacingMark),
+	('\u{8E2}', '\u{8E2}', Format), ('\u{8E3}', '\u{902}', NonspacingMark), ('\u{903}', '\u{903}', SpacingMark),
+	('\u{904}', '\u{939}', OtherLetter), ('\u{93A}', '\u{93A}', NonspacingMark), ('\u{93B}', '\u{93B}', SpacingMark),
+	('\u{93C}', '\u{93C}', NonspacingMark), ('\u{93D}', '\u{93D}', OtherLetter), ('\u{93E}', '\u{940}', SpacingMark),
+	('\u{941}', '\u{948}', NonspacingMark), ('\u{949}', '\u{94C}', SpacingMark), ('\u{94D}', '\u{94D}', NonspacingMark),
+	('\u{94E}', '\u{94F}', SpacingMark), ('\u{950}', '\u{950}', OtherLetter), ('\u{951}', '\u{957}', NonspacingMark),
+	('\u{958}', '\u{961}', OtherLetter), ('\u{962}', '\u{963}', NonspacingMark), ('\u{964}', '\u{965}', OtherPunctuation),
+	('\u{966}', '\u{96F}', DecimalNumber), ('\u{970}', '\u{970}', OtherPunctuation), ('\u{971}', '\u{971}', ModifierLetter),
+	('\u{972}', '\u{980}', OtherLetter), ('\u{981}', '\u{981}', NonspacingMark), ('\u{982}', '\u{983}', SpacingMark),
+	('\u{985}', '\u{98C}', OtherLetter), ('\u{98F}', '\u{990}', OtherLetter), ('\u{993}', '\u{9A8}', OtherLetter),
+	('\u{9AA}', '\u{9B0}', OtherLetter), ('\u{9B2}', '\u{9B2}', OtherLetter), ('\u{9B6}', '\u{9B9}', OtherLetter),
+	('\u{9BC}', '\u{9BC}', NonspacingMark), ('\u{9BD}', '\u{9BD}', OtherLetter), ('\u{9BE}', '\u{9C0}', SpacingMark),
+	('\u{9C1}', '\u{9C4}', NonspacingMark), ('\u{9C7}', '\u{9C8}', SpacingMark), ('\u{9CB}', '\u{9CC}', SpacingMark),
+	('\u{9CD}', '\u{9CD}', NonspacingMark), ('\u{9CE}', '\u{9CE}', OtherLetter), ('\u{9D7}', '\u{9D7}', SpacingMark),
+	('\u{9DC}', '\u{9DD}', OtherLetter), ('\u{9DF}', '\u{9E1}', OtherLetter), ('\u{9E2}', '\u{9E3}', NonspacingMark),
+	('\u{9E6}', '\u{9EF}', DecimalNumber), ('\u{9F0}', '\u{9F1}', OtherLetter), ('\u{9F2}', '\u{9F3}', CurrencySymbol),
+	('\u{9F4}', '\u{9F9}', OtherNumber), ('\u{9FA}', '\u{9FA}', OtherSymbol), ('\u{9FB}', '\u{9FB}', CurrencySymbol),
+	('\u{9FC}', '\u{9FC}', OtherLetter), ('\u{9FD}', '\u{9FD}', OtherPunctuation), ('\u{9FE}', '\u{9FE}', NonspacingMark),
+	('\u{A01}', '\u{A02}', NonspacingMark), ('\u{A03}', '\u{A03}', SpacingMark), ('\u{A05}', '\u{A0A}', OtherLetter),
+	('\u{A0F}', '\u{A10}', OtherLetter), ('\u{A13}', '\u{A28}', OtherLetter), ('\u{A2A}', '\u{A30}', OtherLetter),
+	('\u{A32}', '\u{A33}', OtherLetter), ('\u{A35}', '\u{A36}', OtherLetter), ('\u{A38}', '\u{A39}', OtherLetter),
+	('\u{A3C}', '\u{A3C}', NonspacingMark), ('\u{A3E}', '\u{A40}', SpacingMark), ('\u{A41}', '\u{A42}', NonspacingMark),
+	('\u{A47}', '\u{A48}', NonspacingMark), ('\u{A4B}', '\u{A4D}', NonspacingMark), ('\u{A51}', '\u{A51}', NonspacingMark),
+	('\u{A59}', '\u{A5C}', OtherLetter), ('\u{A5E}', '\u{A5E}', OtherLetter), ('\u{A66}', '\u{A6F}', DecimalNumber),
+	('\u{A70}', '\u{A71}', NonspacingMark), ('\u{A72}', '\u{A74}', OtherLetter), ('\u{A75}', '\u{A75}', NonspacingMark),
+	('\u{A76}', '\u{A76}', OtherPunctuation), ('\u{A81}', '\u{A82}', NonspacingMark), ('\u{A83}', '\u{A83}', SpacingMark),
+	('\u{A85}', '\u{A8D}', OtherLetter), ('\u{A8F}', '\u{A91}', OtherLetter), ('\u{A93}', '\u{AA8}', OtherLetter),
+	('\u{AAA}', '\u{AB0}', OtherLetter), ('\u{AB2}', '\u{AB3}', OtherLetter), ('\u{AB5}', '\u{AB9}', OtherLetter),
+	('\u{ABC}', '\u{ABC}', NonspacingMark), ('\u{ABD}', '\u{ABD}', OtherLetter), ('\u{ABE}', '\u{AC0}', SpacingMark),
+	('\u{AC1}', '\u{AC5}', NonspacingMark), ('\u{AC7}', '\u{AC8}', NonspacingMark), ('\u{AC9}', '\u{AC9}', SpacingMark),
+	('\u{ACB}', '\u{ACC}', SpacingMark), ('\u{ACD}', '\u{ACD}', NonspacingMark), ('\u{AD0}', '\u{AD0}', OtherLetter),
+	('\u{AE0}', '\u{AE1}', OtherLetter), ('\u{AE2}', '\u{AE3}', NonspacingMark), ('\u{AE6}', '\u{AEF}', DecimalNumber),
+	('\u{AF0}', '\u{AF0}', OtherPunctuation), ('\u{AF1}', '\u{AF1}', CurrencySymbol), ('\u{AF9}', '\u{AF9}', OtherLetter),
+	('\u{AFA}', '\u{AFF}', NonspacingMark), ('\u{B01}', '\u{B01}', NonspacingMark), ('\u{B02}', '\u{B03}', SpacingMark),
+	('\u{B05}', '\u{B0C}', OtherLetter), ('\u{B0F}', '\u{B10}', OtherLetter), ('\u{B13}', '\u{B28}', OtherLetter),
+	('\u{B2A}', '\u{B30}', OtherLetter), ('\u{B32}', '\u{B33}', OtherLetter), ('\u{B35}', '\u{B39}', OtherLetter),
+	('\u{B3C}', '\u{B3C}', NonspacingMark), ('\u{B3D}', '\u{B3D}', OtherLetter), ('\u{B3E}', '\u{B3E}', SpacingMark),
+	('\u{B3F}', '\u{B3F}', NonspacingMark), ('\u{B40}', '\u{B40}', SpacingMark), ('\u{B41}', '\u{B44}', NonspacingMark),
+	('\u{B47}', '\u{B48}', SpacingMark), ('\u{B4B}', '\u{B4C}', SpacingMark), ('\u{B4D}', '\u{B4D}', NonspacingMark),
+	('\u{B55}', '\u{B56}', NonspacingMark), ('\u{B57}', '\u{B57}', SpacingMark), ('\u{B5C}', '\u{B5D}', OtherLetter),
+	('\u{B5F}', '\u{B61}', OtherLetter), ('\u{B62}', '\u{B63}', NonspacingMark), ('\u{B66}', '\u{B6F}', DecimalNumber),
+	('\u{B70}', '\u{B70}', OtherSymbol), ('\u{B71}', '\u{B71}', OtherLetter), ('\u{B72}', '\u{B77}', OtherNumber),
+	('\u{B82}', '\u{B82}', NonspacingMark), ('\u{B83}', '\u{B83}', OtherLetter), ('\u{B85}', '\u{B8A}', OtherLetter),
+	('\u{B8E}', '\u{B90}', OtherLetter), ('\u{B92}', '\u{B95}', OtherLetter), ('\u{B99}', '\u{B9A}', OtherLetter),
+	('\u{B9C}', '\u{B9C}', OtherLetter), ('\u{B9E}', '\u{B9F}', OtherLetter), ('\u{BA3}', '\u{BA4}', OtherLetter),
+	('\u{BA8}', '\u{BAA}', OtherLetter), ('\u{BAE}', '\u{BB9}', OtherLetter), ('\u{BBE}', '\u{BBF}', SpacingMark),
+	('\u{BC0}', '\u{BC0}', NonspacingMark), ('\u{BC1}', '\u{BC2}', SpacingMark), ('\u{BC6}', '\u{BC8}', SpacingMark),
+	('\u{BCA}', '\u{BCC}', SpacingMark), ('\u{BCD}', '\u{BCD}', NonspacingMark), ('\u{BD0}', '\u{BD0}', OtherLetter),
+	('\u{BD7}', '\u{BD7}', SpacingMark), ('\u{BE6}', '\u{BEF}', DecimalNumber), ('\u{BF0}', '\u{BF2}', OtherNumber),
+	('\u{BF3}', '\u{BF8}', OtherSymbol), ('\u{BF9}', '\u{BF9}', CurrencySymbol), ('\u{BFA}', '\u{BFA}', OtherSymbol),
+	('\u{C00}', '\u{C00}', NonspacingMark), ('\u{C01}', '\u{C03}', SpacingMark), ('\u{C04}', '\u{C04}', NonspacingMark),
+	('\u{C05}', '\u{C0C}', OtherLetter), ('\u{C0E}', '\u{C10}', OtherLetter), ('\u{C12}', '\u{C28}', OtherLetter),
+	('\u{C2A}', '\u{C39}', OtherLetter), ('\u{C3C}', '\u{C3C}', NonspacingMark), ('\u{C3D}', '\u{C3D}', OtherLetter),
+	('\u{C3E}', '\u{C40}', NonspacingMark), ('\u{C41}', '\u{C44}', SpacingMark), ('\u{C46}', '\u{C48}', NonspacingMark),
+	('\u{C4A}', '\u{C4D}', NonspacingMark), ('\u{C55}', '\u{C56}', NonspacingMark), ('\u{C58}', '\u{C5A}', OtherLetter),
+	('\u{C5D}', '\u{C5D}', OtherLetter), ('\u{C60}', '\u{C61}', OtherLetter), ('\u{C62}', '\u{C63}', NonspacingMark),
+	('\u{C66}', '\u{C6F}', DecimalNumber), ('\u{C77}', '\u{C77}', OtherPunctuation), ('\u{C78}', '\u{C7E}', OtherNumber),
+	('\u{C7F}', '\u{C7F}', OtherSymbol), ('\u{C80}', '\u{C80}', OtherLetter), ('\u{C81}', '\u{C81}', NonspacingMark),
+	('\u{C82}', '\u{C83}', SpacingMark), ('\u{C84}', '\u{C84}', OtherPunctuation), ('\u{C85}', '\u{C8C}', OtherLetter),
+	('\u{C8E}', '\u{C90}', OtherLetter), ('\u{C92}', '\u{CA8}', OtherLetter), ('\u{CAA}', '\u{CB3}', OtherLetter),
+	('\u{CB5}', '\u{CB9}', OtherLetter), ('\u{CBC}', '\u{CBC}', NonspacingMark), ('\u{CBD}', '\u{CBD}', OtherLetter),
+	('\u{CBE}', '\u{CBE}', SpacingMark), ('\u{CBF}', '\u{CBF}', NonspacingMark), ('\u{CC0}', '\u{CC4}', SpacingMark),
+	('\u{CC6}', '\u{CC6}', NonspacingMark), ('\u{CC7}', '\u{CC8}', SpacingMark), ('\u{CCA}', '\u{CCB}', SpacingMark),
+	('\u{CCC}', '\u{CCD}', NonspacingMark), ('\u{CD5}', '\u{CD6}', SpacingMark), ('\u{CDD}', '\u{CDE}', OtherLetter),
+	('\u{CE0}', '\u{CE1}', OtherLetter), ('\u{CE2}', '\u{CE3}', NonspacingMark), ('\u{CE6}', '\u{CEF}', DecimalNumber),
+	('\u{CF1}', '\u{CF2}', OtherLetter), ('\u{D00}', '\u{D01}', NonspacingMark), ('\u{D02}', '\u{D03}', SpacingMark),
+	('\u{D04}', '\u{D0C}', OtherLetter), ('\u{D0E}', '\u{D10}', OtherLetter), ('\u{D12}', '\u{D3A}', OtherLetter),
+	('\u{D3B}', '\u{D3C}', NonspacingMark), ('\u{D3D}', '\u{D3D}', OtherLetter), ('\u{D3E}', '\u{D40}', SpacingMark),
+	('\u{D41}', '\u{D44}', NonspacingMark), ('\u{D46}', '\u{D48}', SpacingMark), ('\u{D4A}', '\u{D4C}', SpacingMark),
+	('\u{D4D}', '\u{D4D}', NonspacingMark), ('\u{D4E}', '\u{D4E}', OtherLetter), ('\u{D4F}', '\u{D4F}', OtherSymbol),
+	('\u{D54}', '\u{D56}', OtherLetter), ('\u{D57}', '\u{D57}', SpacingMark), ('\u{D58}', '\u{D5E}', OtherNumber),
+	('\u{D5F}', '\u{D61}', OtherLetter), ('\u{D62}', '\u{D63}', NonspacingMark), ('\u{D66}', '\u{D6F}', DecimalNumber),
+	('\u{D70}', '\u{D78}', OtherNumber), ('\u{D79}', '\u{D79}', OtherSymbol), ('\u{D7A}', '\u{D7F}', OtherLetter),
+	('\u{D81}', '\u{D81}', NonspacingMark), ('\u{D82}', '\u{D83}', SpacingMark), ('\u{D85}', '\u{D96}', OtherLetter),
+	('\u{D9A}', '\u{DB1}', OtherLetter), ('\u{DB3}', '\u{DBB}', OtherLetter), ('\u{DBD}', '\u{DBD}', OtherLetter),
+	('\u{DC0}', '\u{DC6}', OtherLetter), ('\u{DCA}', '\u{DCA}', NonspacingMark), ('\u{DCF}', '\u{DD1}', SpacingMark),
+	('\u{DD2}', '\u{DD4}', NonspacingMark), ('\u{DD6}', '\u{DD6}', NonspacingMark), ('\u{DD8}', '\u{DDF}', SpacingMark),
+	('\u{DE6}', '\u{DEF}', DecimalNumber), ('\u{DF2}', '\u{DF3}', SpacingMark), ('\u{DF4}', '\u{DF4}', OtherPunctuation),
+	('\u{E01}', '\u{E30}', OtherLetter), ('\u{E31}', '\u{E31}', NonspacingMark), ('\u{E32}', '\u{E33}', OtherLetter),
+	('\u{E34}', '\u{E3A}', NonspacingMark), ('\u{E3F}', '\u{E3F}', CurrencySymbol), ('\u{E40}', '\u{E45}', OtherLetter),
+	('\u{E46}', '\u{E46}', ModifierLetter), ('\u{E47}', '\u{E4E}', NonspacingMark), ('\u{E4F}', '\u{E4F}', OtherPunctuation),
+	('\u{E50}', '\u{E59}', DecimalNumber), ('\u{E5A}', '\u{E5B}', OtherPunctuation), ('\u{E81}', '\u{E82}', OtherLetter),
+	('\u{E84}', '\u{E84}', OtherLetter), ('\u{E86}', '\u{E8A}', OtherLetter), ('\u{E8C}', '\u{EA3}', OtherLetter),
+	('\u{EA5}', '\u{EA5}', OtherLetter), ('\u{EA7}', '\u{EB0}', OtherLetter), ('\u{EB1}', '\u{EB1}', NonspacingMark),
+	('\u{EB2}', '\u{EB3}', OtherLetter), ('\u{EB4}', '\u{EBC}', NonspacingMark), ('\u{EBD}', '\u{EBD}', OtherLetter),
+	('\u{EC0}', '\u{EC4}', OtherLetter), ('\u{EC6}', '\u{EC6}', ModifierLetter), ('\u{EC8}', '\u{ECD}', NonspacingMark),
+	('\u{ED0}', '\u{ED9}', DecimalNumber), ('\u{EDC}', '\u{EDF}', OtherLetter), ('\u{F00}', '\u{F00}', OtherLetter),
+	('\u{F01}', '\u{F03}', OtherSymbol), ('\u{F04}', '\u{F12}', OtherPunctuation), ('\u{F13}', '\u{F13}', OtherSymbol),
+	('\u{F14}', '\u{F14}', OtherPunctuation), ('\u{F15}', '\u{F17}', OtherSymbol), ('\u{F18}', '\u{F19}', NonspacingMark),
+	('\u{F1A}', '\u{F1F}', OtherSymbol), ('\u{F20}', '\u{F29}', DecimalNumber), ('\u{F2A}', '\u{F33}', OtherNumber),
+	('\u{F34}', '\u{F34}', OtherSymbol), ('\u{F35}', '\u{F35}', NonspacingMark), ('\u{F36}', '\u{F36}', OtherSymbol),
+	('\u{F37}', '\u{F37}', NonspacingMark), ('\u{F38}', '\u{F38}', OtherSymbol), ('\u{F39}', '\u{F39}', NonspacingMark),
+	('\u{F3A}', '\u{F3A}', OpenPunctuation), ('\u{F3B}', '\u{F3B}', ClosePunctuation), ('\u{F3C}', '\u{F3C}', OpenPunctuation),
+	('\u{F3D}', '\u{F3D}', ClosePunctuation), ('\u{F3E}', '\u{F3F}', SpacingMark), ('\u{F40}', '\u{F47}', OtherLetter),
+	('\u{F49}', '\u{F6C}', OtherLetter), ('\u{F71}', '\u{F7E}', NonspacingMark), ('\u{F7F}', '\u{F7F}', SpacingMark),
+	('\u{F80}', '\u{F84}', NonspacingMark), ('\u{F85}', '\u{F85}', OtherPunctuation), ('\u{F86}', '\u{F87}', NonspacingMark),
+	('\u{F88}', '\u{F8C}', OtherLetter), ('\u{F8D}', '\u{F97}', NonspacingMark), ('\u{F99}', '\u{FBC}', NonspacingMark),
+	('\u{FBE}', '\u{FC5}', OtherSymbol), ('\u{FC6}', '\u{FC6}', NonspacingMark), ('\u{FC7}', '\u{FCC}', OtherSymbol),
+	('\u{FCE}', '\u{FCF}', OtherSymbol), ('\u{FD0}', '\u{FD4}', OtherPunctuation), ('\u{FD5}', '\u{FD8}', OtherSymbol),
+	('\u{FD9}', '\u{FDA}', OtherPunctuation), ('\u{1000}', '\u{102A}', OtherLetter), ('\u{102B}', '\u{102C}', SpacingMark),
+	('\u{102D}', '\u{1030}', NonspacingMark), ('\u{1031}', '\u{1031}', SpacingMark), ('\u{1032}', '\u{1037}', NonspacingMark),
+	('\u{1038}', '\u{1038}', SpacingMark), ('\u{1039}', '\u{103A}', NonspacingMark), ('\u{103B}', '\u{103C}', SpacingMark),
+	('\u{103D}', '\u{103E}', NonspacingMark), ('\u{103F}', '\u{103F}', OtherLetter), ('\u{1040}', '\u{1049}', DecimalNumber),
+	('\u{104A}', '\u{104F}', OtherPunctuation), ('\u{1050}', '\u{1055}', OtherLetter), ('\u{1056}', '\u{1057}', SpacingMark),
+	('\u{1058}', '\u{1059}', NonspacingMark), ('\u{105A}', '\u{105D}', OtherLetter), ('\u{105E}', '\u{1060}', NonspacingMark),
+	('\u{1061}', '\u{1061}', OtherLetter), ('\u{1062}', '\u{1064}', SpacingMark), ('\u{1065}', '\u{1066}', OtherLetter),
+	('\u{1067}', '\u{106D}', SpacingMark), ('\u{106E}', '\u{1070}', OtherLetter), ('\u{1071}', '\u{1074}', NonspacingMark),
+	('\u{1075}', '\u{1081}', OtherLetter), ('\u{1082}', '\u{1082}', NonspacingMark), ('\u{1083}', '\u{1084}', SpacingMark),
+	('\u{1085}', '\u{1086}', NonspacingMark), ('\u{1087}', '\u{108C}', SpacingMark), ('\u{108D}', '\u{108D}', NonspacingMark),
+	('\u{108E}', '\u{108E}', OtherLetter), ('\u{108F}', '\u{108F}', SpacingMark), ('\u{1090}', '\u{1099}', DecimalNumber),
+	('\u{109A}', '\u{109C}', SpacingMark), ('\u{109D}', '\u{109D}', NonspacingMark), ('\u{109E}', '\u{109F}', OtherSymbol),
+	('\u{10A0}', '\u{10C5}', UppercaseLetter), ('\u{10C7}', '\u{10C7}', UppercaseLetter), ('\u{10CD}', '\u{10CD}', UppercaseLetter),
+	('\u{10D0}', '\u{10FA}', LowercaseLetter), ('\u{10FB}', '\u{10FB}', OtherPunctuation), ('\u{10FC}', '\u{10FC}', ModifierLetter),
+	('\u{10FD}', '\u{10FF}', LowercaseLetter), ('\u{1100}', '\u{1248}', OtherLetter), ('\u{124A}', '\u{124D}', OtherLetter),
+	('\u{1250}', '\u{1256}', OtherLetter), ('\u{1258}', '\u{1258}', OtherLetter), ('\u{125A}', '\u{125D}', OtherLetter),
+	('\u{1260}', '\u{1288}', OtherLetter), ('\u{128A}', '\u{128D}', OtherLetter), ('\u{1290}', '\u{12B0}', OtherLetter),
+	('\u{12B2}', '\u{12B5}', OtherLetter), ('\u{12B8}', '\u{12BE}', OtherLetter), ('\u{12C0}', '\u{12C0}', OtherLetter),
+	('\u{12C2}', '\u{12C5}', OtherLetter), ('\u{12C8}', '\u{12D6}', OtherLetter), ('\u{12D8}', '\u{1310}', OtherLetter),
+	('\u{1312}', '\u{1315}', OtherLetter), ('\u{1318}', '\u{135A}', OtherLetter), ('\u{135D}', '\u{135F}', NonspacingMark),
+	('\u{1360}', '\u{1368}', OtherPunctuation), ('\u{1369}', '\u{137C}', OtherNumber), ('\u{1380}', '\u{138F}', OtherLetter),
+	('\u{1390}', '\u{1399}', OtherSymbol), ('\u{13A0}', '\u{13F5}', UppercaseLetter), ('\u{13F8}', '\u{13FD}', LowercaseLetter),
+	('\u{1400}', '\u{1400}', DashPunctuation), ('\u{1401}', '\u{166C}', OtherLetter), ('\u{166D}', '\u{166D}', OtherSymbol),
+	('\u{166E}', '\u{166E}', OtherPunctuation), ('\u{166F}', '\u{167F}', OtherLetter), ('\u{1680}', '\u{1680}', SpaceSeparator),
+	('\u{1681}', '\u{169A}', OtherLetter), ('\u{169B}', '\u{169B}', OpenPunctuation), ('\u{169C}', '\u{169C}', ClosePunctuation),
+	('\u{16A0}', '\u{16EA}', OtherLetter), ('\u{16EB}', '\u{16ED}', OtherPunctuation), ('\u{16EE}', '\u{16F0}', LetterNumber),
+	('\u{16F1}', '\u{16F8}', OtherLetter), ('\u{1700}', '\u{1711}', OtherLetter), ('\u{1712}', '\u{1714}', NonspacingMark),
+	('\u{1715}', '\u{1715}', SpacingMark), ('\u{171F}', '\u{1731}', OtherLetter), ('\u{1732}', '\u{1733}', NonspacingMark),
+	('\u{1734}', '\u{1734}', SpacingMark), ('\u{1735}', '\u{1736}', OtherPunctuation), ('\u{1740}', '\u{1751}', OtherLetter),
+	('\u{1752}', '\u{1753}', NonspacingMark), ('\u{1760}', '\u{176C}', OtherLetter), ('\u{176E}', '\u{1770}', OtherLetter),
+	('\u{1772}', '\u{1773}', NonspacingMark), ('\u{1780}', '\u{17B3}', OtherLetter), ('\u{17B4}', '\u{17B5}', NonspacingMark),
+	('\u{17B6}', '\u{17B6}', SpacingMark), ('\u{17B7}', '\u{17BD}', NonspacingMark), ('\u{17BE}', '\u{17C5}', SpacingMark),
+	('\u{17C6}', '\u{17C6}', NonspacingMark), ('\u{17C7}', '\u{17C8}', SpacingMark), ('\u{17C9}', '\u{17D3}', NonspacingMark),
+	('\u{17D4}', '\u{17D6}', OtherPunctuation), ('\u{17D7}', '\u{17D7}', ModifierLetter), ('\u{17D8}', '\u{17DA}', OtherPunctuation),
+	('\u{17DB}', '\u{17DB}', CurrencySymbol), ('\u{17DC}', '\u{17DC}', OtherLetter), ('\u{17DD}', '\u{17DD}', NonspacingMark),
+	('\u{17E0}', '\u{17E9}', DecimalNumber), ('\u{17F0}', '\u{17F9}', OtherNumber), ('\u{1800}', '\u{1805}', OtherPunctuation),
+	('\u{1806}', '\u{1806}', DashPunctuation), ('\u{1807}', '\u{180A}', OtherPunctuation), ('\u{180B}', '\u{180D}', NonspacingMark),
+	('\u{180E}', '\u{180E}', Format), ('\u{180F}', '\u{180F}', NonspacingMark), ('\u{1810}', '\u{1819}', DecimalNumber),
+	('\u{1820}', '\u{1842}', OtherLetter), ('\u{1843}', '\u{1843}', ModifierLetter), ('\u{1844}', '\u{1878}', OtherLetter),
+	('\u{1880}', '\u{1884}', OtherLetter), ('\u{1885}', '\u{1886}', NonspacingMark), ('\u{1887}', '\u{18A8}', OtherLetter),
+	('\u{18A9}', '\u{18A9}', NonspacingMark), ('\u{18AA}', '\u{18AA}', OtherLetter), ('\u{18B0}', '\u{18F5}', OtherLetter),
+	('\u{1900}', '\u{191E}', OtherLetter), ('\u{1920}', '\u{1922}', NonspacingMark), ('\u{1923}', '\u{1926}', SpacingMark),
+	('\u{1927}', '\u{1928}', NonspacingMark), ('\u{1929}', '\u{192B}', SpacingMark), ('\u{1930}', '\u{1931}', SpacingMark),
+	('\u{1932}', '\u{1932}', NonspacingMark), ('\u{1933}', '\u{1938}', SpacingMark), ('\u{1939}', '\u{193B}', NonspacingMark),
+	('\u{1940}', '\u{1940}', OtherSymbol), ('\u{1944}', '\u{1945}', OtherPunctuation), ('\u{1946}', '\u{194F}', DecimalNumber),
+	('\u{1950}', '\u{196D}', OtherLetter), ('\u{1970}', '\u{1974}', OtherLetter), ('\u{1980}', '\u{19AB}', OtherLetter),
+	('\u{19B0}', '\u{19C9}', OtherLetter), ('\u{19D0}', '\u{19D9}', DecimalNumber), ('\u{19DA}', '\u{19DA}', OtherNumber),
+	('\u{19DE}', '\u{19FF}', OtherSymbol), ('\u{1A00}', '\u{1A16}', OtherLetter), ('\u{1A17}', '\u{1A18}', NonspacingMark),
+	('\u{1A19}', '\u{1A1A}', SpacingMark), ('\u{1A1B}', '\u{1A1B}', NonspacingMark), ('\u{1A1E}', '\u{1A1F}', OtherPunctuation),
+	('\u{1A20}', '\u{1A54}', OtherLetter), ('\u{1A55}', '\u{1A55}', SpacingMark), ('\u{1A56}', '\u{1A56}', NonspacingMark),
+	('\u{1A57}', '\u{1A57}', SpacingMark), ('\u{1A58}', '\u{1A5E}', NonspacingMark), ('\u{1A60}', '\u{1A60}', NonspacingMark),
+	('\u{1A61}', '\u{1A61}', SpacingMark), ('\u{1A62}', '\u{1A62}', NonspacingMark), ('\u{1A63}', '\u{1A64}', SpacingMark),
+	('\u{1A65}', '\u{1A6C}', NonspacingMark), ('\u{1A6D}', '\u{1A72}', SpacingMark), ('\u{1A73}', '\u{1A7C}', NonspacingMark),
+	('\u{1A7F}', '\u{1A7F}', NonspacingMark), ('\u{1A80}', '\u{1A89}', DecimalNumber), ('\u{1A90}', '\u{1A99}', DecimalNumber),
+	('\u{1AA0}', '\u{1AA6}', OtherPunctuation), ('\u{1AA7}', '\u{1AA7}', ModifierLetter), ('\u{1AA8}', '\u{1AAD}', OtherPunctuation),
+	('\u{1AB0}', '\u{1ABD}', NonspacingMark), ('\u{1ABE}', '\u{1ABE}', EnclosingMark), ('\u{1ABF}', '\u{1ACE}', NonspacingMark),
+	('\u{1B00}', '\u{1B03}', NonspacingMark), ('\u{1B04}', '\u{1B04}', SpacingMark), ('\u{1B05}', '\u{1B33}', OtherLetter),
+	('\u{1B34}', '\u{1B34}', NonspacingMark), ('\u{1B35}', '\u{1B35}', SpacingMark), ('\u{1B36}', '\u{1B3A}', NonspacingMark),
+	('\u{1B3B}', '\u{1B3B}', SpacingMark), ('\u{1B3C}', '\u{1B3C}', NonspacingMark), ('\u{1B3D}', '\u{1B41}', SpacingMark),
+	('\u{1B42}', '\u{1B42}', NonspacingMark), ('\u{1B43}', '\u{1B44}', SpacingMark), ('\u{1B45}', '\u{1B4C}', OtherLetter),
+	('\u{1B50}', '\u{1B59}', DecimalNumber), ('\u{1B5A}', '\u{1B60}', OtherPunctuation), ('\u{1B61}', '\u{1B6A}', OtherSymbol),
+	('\u{1B6B}', '\u{1B73}', NonspacingMark), ('\u{1B74}', '\u{1B7C}', OtherSymbol), ('\u{1B7D}', '\u{1B7E}', OtherPunctuation),
+	('\u{1B80}', '\u{1B81}', NonspacingMark), ('\u{1B82}', '\u{1B82}', SpacingMark), ('\u{1B83}', '\u{1BA0}', OtherLetter),
+	('\u{1BA1}', '\u{1BA1}', SpacingMark), ('\u{1BA2}', '\u{1BA5}', NonspacingMark), ('\u{1BA6}', '\u{1BA7}', SpacingMark),
+	('\u{1BA8}', '\u{1BA9}', NonspacingMark), ('\u{1BAA}', '\u{1BAA}', SpacingMark), ('\u{1BAB}', '\u{1BAD}', NonspacingMark),
+	('\u{1BAE}', '\u{1BAF}', OtherLetter), ('\u{1BB0}', '\u{1BB9}', DecimalNumber), ('\u{1BBA}', '\u{1BE5}', OtherLetter),
+	('\u{1BE6}', '\u{1BE6}', NonspacingMark), ('\u{1BE7}', '\u{1BE7}', SpacingMark), ('\u{1BE8}', '\u{1BE9}', NonspacingMark),
+	('\u{1BEA}', '\u{1BEC}', SpacingMark), ('\u{1BED}', '\u{1BED}', NonspacingMark), ('\u{1BEE}', '\u{1BEE}', SpacingMark),
+	('\u{1BEF}', '\u{1BF1}', NonspacingMark), ('\u{1BF2}', '\u{1BF3}', SpacingMark), ('\u{1BFC}', '\u{1BFF}', OtherPunctuation),
+	('\u{1C00}', '\u{1C23}', OtherLetter), ('\u{1C24}', '\u{1C2B}', SpacingMark), ('\u{1C2C}', '\u{1C33}', NonspacingMark),
+	('\u{1C34}', '\u{1C35}', SpacingMark), ('\u{1C36}', '\u{1C37}', NonspacingMark), ('\u{1C3B}', '\u{1C3F}', OtherPunctuation),
+	('\u{1C40}', '\u{1C49}', DecimalNumber), ('\u{1C4D}', '\u{1C4F}', OtherLetter), ('\u{1C50}', '\u{1C59}', DecimalNumber),
+	('\u{1C5A}', '\u{1C77}', OtherLetter), ('\u{1C78}', '\u{1C7D}', ModifierLetter), ('\u{1C7E}', '\u{1C7F}', OtherPunctuation),
+	('\u{1C80}', '\u{1C88}', LowercaseLetter), ('\u{1C90}', '\u{1CBA}', UppercaseLetter), ('\u{1CBD}', '\u{1CBF}', UppercaseLetter),
+	('\u{1CC0}', '\u{1CC7}', OtherPunctuation), ('\u{1CD0}', '\u{1CD2}', NonspacingMark), ('\u{1CD3}', '\u{1CD3}', OtherPunctuation),
+	('\u{1CD4}', '\u{1CE0}', NonspacingMark), ('\u{1CE1}', '\u{1CE1}', SpacingMark), ('\u{1CE2}', '\u{1CE8}', NonspacingMark),
+	('\u{1CE9}', '\u{1CEC}', OtherLetter), ('\u{1CED}', '\u{1CED}', NonspacingMark), ('\u{1CEE}', '\u{1CF3}', OtherLetter),
+	('\u{1CF4}', '\u{1CF4}', NonspacingMark), ('\u{1CF5}', '\u{1CF6}', OtherLetter), ('\u{1CF7}', '\u{1CF7}', SpacingMark),
+	('\u{1CF8}', '\u{1CF9}', NonspacingMark), ('\u{1CFA}', '\u{1CFA}', OtherLetter), ('\u{1D00}', '\u{1D2B}', LowercaseLetter),
+	('\u{1D2C}', '\u{1D6A}', ModifierLetter), ('\u{1D6B}', '\u{1D77}', LowercaseLetter), ('\u{1D78}', '\u{1D78}', ModifierLetter),
+	('\u{1D79}', '\u{1D9A}', LowercaseLetter), ('\u{1D9B}', '\u{1DBF}', ModifierLetter), ('\u{1DC0}', '\u{1DFF}', NonspacingMark),
+	('\u{1E00}', '\u{1E00}', UppercaseLetter), ('\u{1E01}', '\u{1E01}', LowercaseLetter), ('\u{1E02}', '\u{1E02}', UppercaseLetter),
+	('\u{1E03}', '\u{1E03}', LowercaseLetter), ('\u{1E04}', '\u{1E04}', UppercaseLetter), ('\u{1E05}', '\u{1E05}', LowercaseLetter),
+	('\u{1E06}', '\u{1E06}', UppercaseLetter), ('\u{1E07}', '\u{1E07}', LowercaseLetter), ('\u{1E08}', '\u{1E08}', UppercaseLetter),
+	('\u{1E09}', '\u{1E09}', LowercaseLetter), ('\u{1E0A}', '\u{1E0A}', UppercaseLetter), ('\u{1E0B}', '\u{1E0B}', LowercaseLetter),
+	('\u{1E0C}', '\u{1E0C}', UppercaseLetter), ('\u{1E0D}', '\u{1E0D}', LowercaseLetter), ('\u{1E0E}', '\u{1E0E}', UppercaseLetter),
+	('\u{1E0F}', '\u{1E0F}', LowercaseLetter), ('\u{1E10}', '\u{1E10}', UppercaseLetter), ('\u{1E11}', '\u{1E11}', LowercaseLetter),
+	('\u{1E12}', '\u{1E12}', UppercaseLetter), ('\u{1E13}', '\u{1E13}', LowercaseLetter), ('\u{1E14}', '\u{1E14}', UppercaseLetter),
+	('\u{1E15}', '\u{1E15}', LowercaseLetter), ('\u{1E16}', '\u{1E16}', UppercaseLetter), ('\u{1E17}', '\u{1E17}', LowercaseLetter),
+	('\u{1E18}', '\u{1E18}', UppercaseLetter), ('\u{1E19}', '\u{1E19}', LowercaseLetter), ('\u{1E1A}', '\u{1E1A}', UppercaseLetter),
+	('\u{1E1B}', '\u{1E1B}', LowercaseLetter), ('\u{1E1C}', '\u{1E1C}', UppercaseLetter), ('\u{1E1D}', '\u{1E1D}', LowercaseLetter),
+	('\u{1E1E}', '\u{1E1E}', UppercaseLetter), ('\u{1E1F}', '\u{1E1F}', LowercaseLetter), ('\u{1E20}', '\u{1E20}', UppercaseLetter),
+	('\u{1E21}', '\u{1E21}', LowercaseLetter), ('\u{1E22}', '\u{1E22}', UppercaseLetter), ('\u{1E23}', '\u{1E23}', LowercaseLetter),
+	('\u{1E24}', '\u{1E24}', UppercaseLetter), ('\u{1E25}', '\u{1E25}', LowercaseLetter), ('\u{1E26}', '\u{1E26}', UppercaseLetter),
+	('\u{1E27}', '\u{1E27}', LowercaseLetter), ('\u{1E28}', '\u{1E28}', UppercaseLetter), ('\u{1E29}', '\u{1E29}', LowercaseLetter),
+	('\u{1E2A}', '\u{1E2A}', UppercaseLetter), ('\u{1E2B}', '\u{1E2B}', LowercaseLetter), ('\u{1E2C}', '\u{1E2C}', UppercaseLetter),
+	('\u{1E2D}', '\u{1E2D}', LowercaseLetter), ('\u{1E2E}', '\u{1E2E}', UppercaseLetter), ('\u{1E2F}', '\u{1E2F}', LowercaseLetter),
+	('\u{1E30}', '\u{1E30}', UppercaseLetter), ('\u{1E31}', '\u{1E31}', LowercaseLetter), ('\u{1E32}', '\u{1E32}', UppercaseLetter),
+	('\u{1E33}', '\u{1E33}', LowercaseLetter), ('\u{1E34}', '\u{1E34}', UppercaseLetter), ('\u{1E35}', '\u{1E35}', LowercaseLetter),
+	('\u{1E36}', '\u{1E36}', UppercaseLetter), ('\u{1E37}', '\u{1E37}', LowercaseLetter), ('\u{1E38}', '\u{1E38}', UppercaseLetter),
+	('\u{1E39}', '\u{1E39}', LowercaseLetter), ('\u{1E3A}', '\u{1E3A}', UppercaseLetter), ('\u{1E3B}', '\u{1E3B}', LowercaseLetter),
+	('\u{1E3C}', '\u{1E3C}', UppercaseLetter), ('\u{1E3D}', '\u{1E3D}', LowercaseLetter), ('\u{1E3E}', '\u{1E3E}', UppercaseLetter),
+	('\u{1E3F}', '\u{1E3F}', LowercaseLetter), ('\u{1E40}', '\u{1E40}', UppercaseLetter), ('\u{1E41}', '\u{1E41}', LowercaseLetter),
+	('\u{1E42}', '\u{1E42}', UppercaseLetter), ('\u{1E43}', '\u{1E43}', LowercaseLetter), ('\u{1E44}', '\u{1E44}', UppercaseLetter),
+	('\u{1E45}', '\u{1E45}', LowercaseLetter), ('\u{1E46}', '\u{1E46}', UppercaseLetter), ('\u{1E47}', '\u{1E47}', LowercaseLetter),
+	('\u{1E48}', '\u{1E48}', UppercaseLetter), ('\u{1E49}', '\u{1E49}', LowercaseLetter), ('\u{1E4A}', '\u{1E4A}', UppercaseLetter),
+	('\u{1E4B}', '\u{1E4B}', LowercaseLetter), ('\u{1E4C}', '\u{1E4C}', UppercaseLetter), ('\u{1E4D}', '\u{1E4D}', LowercaseLetter),
+	('\u{1E4E}', '\u{1E4E}', UppercaseLetter), ('\u{1E4F}', '\u{1E4F}', LowercaseLetter), ('\u{1E50}', '\u{1E50}', UppercaseLetter),
+	('\u{1E51}', '\u{1E51}', LowercaseLetter), ('\u{1E52}', '\u{1E52}', UppercaseLetter), ('\u{1E53}', '\u{1E53}', LowercaseLetter),
+	('\u{1E54}', '\u{1E54}', UppercaseLetter), ('\u{1E55}', '\u{1E55}', LowercaseLetter), ('\u{1E56}', '\u{1E56}', UppercaseLetter),
+	('\u{1E57}', '\u{1E57}', LowercaseLetter), ('\u{1E58}', '\u{1E58}', UppercaseLetter), ('\u{1E59}', '\u{1E59}', LowercaseLetter),
+	('\u{1E5A}', '\u{1E5A}', UppercaseLetter), ('\u{1E5B}', '\u{1E5B}', LowercaseLetter), ('\u{1E5C}', '\u{1E5C}', UppercaseLetter),
+	('\u{1E5D}', '\u{1E5D}', LowercaseLetter), ('\u{1E5E}', '\u{1E5E}', UppercaseLetter), ('\u{1E5F}', '\u{1E5F}', LowercaseLetter),
+	('\u{1E60}', '\u{1E60}', UppercaseLetter), ('\u{1E61}', '\u{1E61}', LowercaseLetter), ('\u{1E62}', '\u{1E62}', UppercaseLetter),
+	('\u{1E63}', '\u{1E63}', LowercaseLetter), ('\u{1E64}', '\u{1E64}', UppercaseLetter), ('\u{1E65}', '\u{1E65}', LowercaseLetter),
+	('\u{1E66}', '\u{1E66}', UppercaseLetter), ('\u{1E67}', '\u{1E67}', LowercaseLetter), ('\u{1E68}', '\u{1E68}', UppercaseLetter),
+	('\u{1E69}', '\u{1E69}', LowercaseLetter), ('\u{1E6A}', '\u{1E6A}', UppercaseLetter), ('\u{1E6B}', '\u{1E6B}', LowercaseLetter),
+	('\u{1E6C}', '\u{1E6C}', UppercaseLetter), ('\u{1E6D}', '\u{1E6D}', LowercaseLetter), ('\u{1E6E}', '\u{1E6E}', UppercaseLetter),
+	('\u{1E6F}', '\u{1E6F}', LowercaseLetter), ('\u{1E70}', '\u{1E70}', UppercaseLetter), ('\u{1E71}', '\u{1E71}', LowercaseLetter),
+	('\u{1E72}', '\u{1E72}', UppercaseLetter), ('\u{1E73}', '\u{1E73}', LowercaseLetter), ('\u{1E74}', '\u{1E74}', UppercaseLetter),
+	('\u{1E75}', '\u{1E75}', LowercaseLetter), ('\u{1E76}', '\u{1E76}', UppercaseLetter), ('\u{1E77}', '\u{1E77}', LowercaseLetter),
+	('\u{1E78}', '\u{1E78}', UppercaseLetter), ('\u{1E79}', '\u{1E79}', LowercaseLetter), ('\u{1E7A}', '\u{1E7A}', UppercaseLetter),
+	('\u{1E7B}', '\u{1E7B}', LowercaseLetter), ('\u{1E7C}', '\u{1E7C}', UppercaseLetter), ('\u{1E7D}', '\u{1E7D}', LowercaseLetter),
+	('\u{1E7E}', '\u{1E7E}', UppercaseLetter), ('\u{1E7F}', '\u{1E7F}', LowercaseLetter), ('\u{1E80}', '\u{1E80}', UppercaseLetter),
+	('\u{1E81}', '\u{1E81}', LowercaseLetter), ('\u{1E82}', '\u{1E82}', UppercaseLetter), ('\u{1E83}', '\u{1E83}', LowercaseLetter),
+	('\u{1E84}', '\u{1E84}', UppercaseLetter), ('\u{1E85}', '\u{1E85}', LowercaseLetter), ('\u{1E86}', '\u{1E86}', UppercaseLetter),
+	('\u{1E87}', '\u{1E87}', LowercaseLetter), ('\u{1E88}', '\u{1E88}', UppercaseLetter), ('\u{1E89}', '\u{1E89}', LowercaseLetter),
+	('\u{1E8A}', '\u{1E8A}', UppercaseLetter), ('\u{1E8B}', '\u{1E8B}', LowercaseLetter), ('\u{1E8C}', '\u{1E8C}', UppercaseLetter),
+	('\u{1E8D}', '\u{1E8D}', LowercaseLetter), ('\u{1E8E}', '\u{1E8E}', UppercaseLetter), ('\u{1E8F}', '\u{1E8F}', LowercaseLetter),
+	('\u{1E90}', '\u{1E90}', UppercaseLetter), ('\u{1E91}', '\u{1E91}', LowercaseLetter), ('\u{1E92}', '\u{1E92}', UppercaseLetter),
+	('\u{1E93}', '\u{1E93}', LowercaseLetter), ('\u{1E94}', '\u{1E94}', UppercaseLetter), ('\u{1E95}', '\u{1E9D}', LowercaseLetter),
+	('\u{1E9E}', '\u{1E9E}', UppercaseLetter), ('\u{1E9F}', '\u{1E9F}', LowercaseLetter), ('\u{1EA0}', '\u{1EA0}', UppercaseLetter),
+	('\u{1EA1}', '\u{1EA1}', LowercaseLetter), ('\u{1EA2}', '\u{1EA2}', UppercaseLetter), ('\u{1EA3}', '\u{1EA3}', LowercaseLetter),
+	('\u{1EA4}', '\u{1EA4}', UppercaseLetter), ('\u{1EA5}', '\u{1EA5}', LowercaseLetter), ('\u{1EA6}', '\u{1EA6}', UppercaseLetter),
+	('\u{1EA7}', '\u{1EA7}', LowercaseLetter), ('\u{1EA8}', '\u{1EA8}', UppercaseLetter), ('\u{1EA9}', '\u{1EA9}', LowercaseLetter),
+	('\u{1EAA}', '\u{1EAA}', UppercaseLetter), ('\u{1EAB}', '\u{1EAB}', LowercaseLetter), ('\u{1EAC}', '\u{1EAC}', UppercaseLetter),
+	('\u{1EAD}', '\u{1EAD}', LowercaseLetter), ('\u{1EAE}', '\u{1EAE}', UppercaseLetter), ('\u{1EAF}', '\u{1EAF}', LowercaseLetter),
+	('\u{1EB0}', '\u{1EB0}', UppercaseLetter), ('\u{1EB1}', '\u{1EB1}', LowercaseLetter), ('\u{1EB2}', '\u{1EB2}', UppercaseLetter),
+	('\u{1EB3}', '\u{1EB3}', LowercaseLetter), ('\u{1EB4}', '\u{1EB4}', UppercaseLetter), ('\u{1EB5}', '\u{1EB5}', LowercaseLetter),
+	('\u{1EB6}', '\u{1EB6}', UppercaseLetter), ('\u{1EB7}', '\u{1EB7}', LowercaseLetter), ('\u{1EB8}', '\u{1EB8}', UppercaseLetter),
+	('\u{1EB9}', '\u{1EB9}', LowercaseLetter), ('\u{1EBA}', '\u{1EBA}', UppercaseLetter), ('\u{1EBB}', '\u{1EBB}', LowercaseLetter),
+	('\u{1EBC}', '\u{1EBC}', UppercaseLetter), ('\u{1EBD}', '\u{1EBD}', LowercaseLetter), ('\u{1EBE}', '\u{1EBE}', UppercaseLetter),
+	('\u{1EBF}', '\u{1EBF}', LowercaseLetter), ('\u{1EC0}', '\u{1EC0}', UppercaseLetter), ('\u{1EC1}', '\u{1EC1}', LowercaseLetter),
+	('\u{1EC2}', '\u{1EC2}', UppercaseLetter), ('\u{1EC3}', '\u{1EC3}', LowercaseLetter), ('\u{1EC4}', '\u{1EC4}', UppercaseLetter),
+	('\u{1EC5}', '\u{1EC5}', LowercaseLetter), ('\u{1EC6}', '\u{1EC6}', UppercaseLetter), ('\u{1EC7}', '\u{1EC7}', LowercaseLetter),
+	('\u{1EC8}', '\u{1EC8}', UppercaseLetter), ('\u{1EC9}', '\u{1EC9}', LowercaseLetter), ('\u{1ECA}', '\u{1ECA}', UppercaseLetter),
+	('\u{1ECB}', '\u{1ECB}', LowercaseLetter), ('\u{1ECC}', '\u{1ECC}', UppercaseLetter), ('\u{1ECD}', '\u{1ECD}', LowercaseLetter),
+	('\u{1ECE}', '\u{1ECE}', UppercaseLetter), ('\u{1ECF}', '\u{1ECF}', LowercaseLetter), ('\u{1ED0}', '\u{1ED0}', UppercaseLetter),
+	('\u{1ED1}', '\u{1ED1}', LowercaseLetter), ('\u{1ED2}', '\u{1ED2}', UppercaseLetter), ('\u{1ED3}', '\u{1ED3}', LowercaseLetter),
+	('\u{1ED4}', '\u{1ED4}', UppercaseLetter), ('\u{1ED5}', '\u{1ED5}', LowercaseLetter), ('\u{1ED6}', '\u{1ED6}', UppercaseLetter),
+	('\u{1ED7}', '\u{1ED7}', LowercaseLetter), ('\u{1ED8}', '\u{1ED8}', UppercaseLetter), ('\u{1ED9}', '\u{1ED9}', LowercaseLetter),
+	('\u{1EDA}', '\u{1EDA}', UppercaseLetter), ('\u{1EDB}', '\u{1EDB}', LowercaseLetter), ('\u{1EDC}', '\u{1EDC}', UppercaseLetter),
+	('\u{1EDD}', '\u{1EDD}', LowercaseLetter), ('\u{1EDE}', '\u{1EDE}', UppercaseLetter), ('\u{1EDF}', '\u{1EDF}', LowercaseLetter),
+	('\u{1EE0}', '\u{1EE0}', UppercaseLetter), ('\u{1EE1}', '\u{1EE1}', LowercaseLetter), ('\u{1EE2}', '\u{1EE2}', UppercaseLetter),
+	('\u{1EE3}', '\u{1EE3}', LowercaseLetter), ('\u{1EE4}', '\u{1EE4}', UppercaseLetter), ('\u{1EE5}', '\u{1EE5}', LowercaseLetter),
+	('\u{1EE6}', '\u{1EE6}', UppercaseLetter), ('\u{1EE7}', '\u{1EE7}', LowercaseLetter), ('\u{1EE8}', '\u{1EE8}', UppercaseLetter),
+	('\u{1EE9}', '\u{1EE9}', LowercaseLetter), ('\u{1EEA}', '\u{1EEA}', UppercaseLetter), ('\u{1EEB}', '\u{1EEB}', LowercaseLetter),
+	('\u{1EEC}', '\u{1EEC}', UppercaseLetter), ('\u{1EED}', '\u{1EED}', LowercaseLetter), ('\u{1EEE}', '\u{1EEE}', UppercaseLetter),
+	('\u{1EEF}', '\u{1EEF}', LowercaseLetter), ('\u{1EF0}', '\u{1EF0}', UppercaseLetter), ('\u{1EF1}', '\u{1EF1}', LowercaseLetter),
+	('\u{1EF2}', '\u{1EF2}', UppercaseLetter), ('\u{1EF3}', '\u{1EF3}', LowercaseLetter), ('\u{1EF4}', '\u{1EF4}', UppercaseLetter),
+	('\u{1EF5}', '\u{1EF5}', LowercaseLetter), ('\u{1EF6}', '\u{1EF6}', UppercaseLetter), ('\u{1EF7}', '\u{1EF7}', LowercaseLetter),
+	('\u{1EF8}', '\u{1EF8}', UppercaseLetter), ('\u{1EF9}', '\u{1EF9}', LowercaseLetter), ('\u{1EFA}', '\u{1EFA}', UppercaseLetter),
+	('\u{1EFB}', '\u{1EFB}', LowercaseLetter), ('\u{1EFC}', '\u{1EFC}', UppercaseLetter), ('\u{1EFD}', '\u{1EFD}', LowercaseLetter),
+	('\u{1EFE}', '\u{1EFE}', UppercaseLetter), ('\u{1EFF}', '\u{1F07}', LowercaseLetter), ('\u{1F08}', '\u{1F0F}', UppercaseLetter),
+	('\u{1F10}', '\u{1F15}', LowercaseLetter), ('\u{1F18}', '\u{1F1D}', UppercaseLetter), ('\u{1F20}', '\u{1F27}', LowercaseLetter),
+	('\u{1F28}', '\u{1F2F}', UppercaseLetter), ('\u{1F30}', '\u{1F37}', LowercaseLetter), ('\u{1F38}', '\u{1F3F}', UppercaseLetter),
+	('\u{1F40}', '\u{1F45}', LowercaseLetter), ('\u{1F48}', '\u{1F4D}', UppercaseLetter), ('\u{1F50}', '\u{1F57}', LowercaseLetter),
+	('\u{1F59}', '\u{1F59}', UppercaseLetter), ('\u{1F5B}', '\u{1F5B}', UppercaseLetter), ('\u{1F5D}', '\u{1F5D}', UppercaseLetter),
+	('\u{1F5F}', '\u{1F5F}', UppercaseLetter), ('\u{1F60}', '\u{1F67}', LowercaseLetter), ('\u{1F68}', '\u{1F6F}', UppercaseLetter),
+	('\u{1F70}', '\u{1F7D}', LowercaseLetter), ('\u{1F80}', '\u{1F87}', LowercaseLetter), ('\u{1F88}', '\u{1F8F}', TitlecaseLetter),
+	('\u{1F90}', '\u{1F97}', LowercaseLetter), ('\u{1F98}', '\u{1F9F}', TitlecaseLetter), ('\u{1FA0}', '\u{1FA7}', LowercaseLetter),
+	('\u{1FA8}', '\u{1FAF}', TitlecaseLetter), ('\u{1FB0}', '\u{1FB4}', LowercaseLetter), ('\u{1FB6}', '\u{1FB7}', LowercaseLetter),
+	('\u{1FB8}', '\u{1FBB}', UppercaseLetter), ('\u{1FBC}', '\u{1FBC}', TitlecaseLetter), ('\u{1FBD}', '\u{1FBD}', ModifierSymbol),
+	('\u{1FBE}', '\u{1FBE}', LowercaseLetter), ('\u{1FBF}', '\u{1FC1}', ModifierSymbol), ('\u{1FC2}', '\u{1FC4}', LowercaseLetter),
+	('\u{1FC6}', '\u{1FC7}', LowercaseLetter), ('\u{1FC8}', '\u{1FCB}', UppercaseLetter), ('\u{1FCC}', '\u{1FCC}', TitlecaseLetter),
+	('\u{1FCD}', '\u{1FCF}', ModifierSymbol), ('\u{1FD0}', '\u{1FD3}', LowercaseLetter), ('\u{1FD6}', '\u{1FD7}', LowercaseLetter),
+	('\u{1FD8}', '\u{1FDB}', UppercaseLetter), ('\u{1FDD}', '\u{1FDF}', ModifierSymbol), ('\u{1FE0}', '\u{1FE7}', LowercaseLetter),
+	('\u{1FE8}', '\u{1FEC}', UppercaseLetter), ('\u{1FED}', '\u{1FEF}', ModifierSymbol), ('\u{1FF2}', '\u{1FF4}', LowercaseLetter),
+	('\u{1FF6}', '\u{1FF7}', LowercaseLetter), ('\u{1FF8}', '\u{1FFB}', UppercaseLetter), ('\u{1FFC}', '\u{1FFC}', TitlecaseLetter),
+	('\u{1FFD}', '\u{1FFE}', ModifierSymbol), ('\u{2000}', '\u{200A}', SpaceSeparator), ('\u{200B}', '\u{200F}', Format),
+	('\u{2010}', '\u{2015}', DashPunctuation), ('\u{2016}', '\u{2017}', OtherPunctuation), ('\u{2018}', '\u{2018}', InitialPunctuation),
+	('\u{2019}', '\u{2019}', FinalPunctuation), ('\u{201A}', '\u{201A}', OpenPunctuation), ('\u{201B}', '\u{201C}', InitialPunctuation),
+	('\u{201D}', '\u{201D}', FinalPunctuation), ('\u{201E}', '\u{201E}', OpenPunctuation), ('\u{201F}', '\u{201F}', InitialPunctuation),
+	('\u{2020}', '\u{2027}', OtherPunctuation), ('\u{2028}', '\u{2028}', LineSeparator), ('\u{2029}', '\u{2029}', ParagraphSeparator),
+	('\u{202A}', '\u{202E}', Format), ('\u{202F}', '\u{202F}', SpaceSeparator), ('\u{2030}', '\u{2038}', OtherPunctuation),
+	('\u{2039}', '\u{2039}', InitialPunctuation), ('\u{203A}', '\u{203A}', FinalPunctuation), ('\u{203B}', '\u{203E}', OtherPunctuation),
+	('\u{203F}', '\u{2040}', ConnectorPunctuation), ('\u{2041}', '\u{2043}', OtherPunctuation), ('\u{2044}', '\u{2044}', MathSymbol),
+	('\u{2045}', '\u{2045}', OpenPunctuation), ('\u{2046}', '\u{2046}', ClosePunctuation), ('\u{2047}', '\u{2051}', OtherPunctuation),
+	('\u{2052}', '\u{2052}', MathSymbol), ('\u{2053}', '\u{2053}', OtherPunctuation), ('\u{2054}', '\u{2054}', ConnectorPunctuation),
+	('\u{2055}', '\u{205E}', OtherPunctuation), ('\u{205F}', '\u{205F}', SpaceSeparator), ('\u{2060}', '\u{2064}', Format),
+	('\u{2066}', '\u{206F}', Format), ('\u{2070}', '\u{2070}', OtherNumber), ('\u{2071}', '\u{2071}', ModifierLetter),
+	('\u{2074}', '\u{2079}', OtherNumber), ('\u{207A}', '\u{207C}', MathSymbol), ('\u{207D}', '\u{207D}', OpenPunctuation),
+	('\u{207E}', '\u{207E}', ClosePunctuation), ('\u{207F}', '\u{207F}', ModifierLetter), ('\u{2080}', '\u{2089}', OtherNumber),
+	('\u{208A}', '\u{208C}', MathSymbol), ('\u{208D}', '\u{208D}', OpenPunctuation), ('\u{208E}', '\u{208E}', ClosePunctuation),
+	('\u{2090}', '\u{209C}', ModifierLetter), ('\u{20A0}', '\u{20C0}', CurrencySymbol), ('\u{20D0}', '\u{20DC}', NonspacingMark),
+	('\u{20DD}', '\u{20E0}', EnclosingMark), ('\u{20E1}', '\u{20E1}', NonspacingMark), ('\u{20E2}', '\u{20E4}', EnclosingMark),
+	('\u{20E5}', '\u{20F0}', NonspacingMark), ('\u{2100}', '\u{2101}', OtherSymbol), ('\u{2102}', '\u{2102}', UppercaseLetter),
+	('\u{2103}', '\u{2106}', OtherSymbol), ('\u{2107}', '\u{2107}', UppercaseLetter), ('\u{2108}', '\u{2109}', OtherSymbol),
+	('\u{210A}', '\u{210A}', LowercaseLetter), ('\u{210B}', '\u{210D}', UppercaseLetter), ('\u{210E}', '\u{210F}', LowercaseLetter),
+	('\u{2110}', '\u{2112}', UppercaseLetter), ('\u{2113}', '\u{2113}', LowercaseLetter), ('\u{2114}', '\u{2114}', OtherSymbol),
+	('\u{2115}', '\u{2115}', UppercaseLetter), ('\u{2116}', '\u{2117}', OtherSymbol), ('\u{2118}', '\u{2118}', MathSymbol),
+	('\u{2119}', '\u{211D}', UppercaseLetter), ('\u{211E}', '\u{2123}', OtherSymbol), ('\u{2124}', '\u{2124}', UppercaseLetter),
+	('\u{2125}', '\u{2125}', OtherSymbol), ('\u{2126}', '\u{2126}', UppercaseLetter), ('\u{2127}', '\u{2127}', OtherSymbol),
+	('\u{2128}', '\u{2128}', UppercaseLetter), ('\u{2129}', '\u{2129}', OtherSymbol), ('\u{212A}', '\u{212D}', UppercaseLetter),
+	('\u{212E}', '\u{212E}', OtherSymbol), ('\u{212F}', '\u{212F}', LowercaseLetter), ('\u{2130}', '\u{2133}', UppercaseLetter),
+	('\u{2134}', '\u{2134}', LowercaseLetter), ('\u{2135}', '\u{2138}', OtherLetter), ('\u{2139}', '\u{2139}', LowercaseLetter),
+	('\u{213A}', '\u{213B}', OtherSymbol), ('\u{213C}', '\u{213D}', LowercaseLetter), ('\u{213E}', '\u{213F}', UppercaseLetter),
+	('\u{2140}', '\u{2144}', MathSymbol), ('\u{2145}', '\u{2145}', UppercaseLetter), ('\u{2146}', '\u{2149}', LowercaseLetter),
+	('\u{214A}', '\u{214A}', OtherSymbol), ('\u{214B}', '\u{214B}', MathSymbol), ('\u{214C}', '\u{214D}', OtherSymbol),
+	('\u{214E}', '\u{214E}', LowercaseLetter), ('\u{214F}', '\u{214F}', OtherSymbol), ('\u{2150}', '\u{215F}', OtherNumber),
+	('\u{2160}', '\u{2182}', LetterNumber), ('\u{2183}', '\u{2183}', UppercaseLetter), ('\u{2184}', '\u{2184}', LowercaseLetter),
+	('\u{2185}', '\u{2188}', LetterNumber), ('\u{2189}', '\u{2189}', OtherNumber), ('\u{218A}', '\u{218B}', OtherSymbol),
+	('\u{2190}', '\u{2194}', MathSymbol), ('\u{2195}', '\u{2199}', OtherSymbol), ('\u{219A}', '\u{219B}', MathSymbol),
+	('\u{219C}', '\u{219F}', OtherSymbol), ('\u{21A0}', '\u{21A0}', MathSymbol), ('\u{21A1}', '\u{21A2}', OtherSymbol),
+	('\u{21A3}', '\u{21A3}', MathSymbol), ('\u{21A4}', '\u{21A5}', OtherSymbol), ('\u{21A6}', '\u{21A6}', MathSymbol),
+	('\u{21A7}', '\u{21AD}', OtherSymbol), ('\u{21AE}', '\u{21AE}', MathSymbol), ('\u{21AF}', '\u{21CD}', OtherSymbol),
+	('\u{21CE}', '\u{21CF}', MathSymbol), ('\u{21D0}', '\u{21D1}', OtherSymbol), ('\u{21D2}', '\u{21D2}', MathSymbol),
+	('\u{21D3}', '\u{21D3}', OtherSymbol), ('\u{21D4}', '\u{21D4}', MathSymbol), ('\u{21D5}', '\u{21F3}', OtherSymbol),
+	('\u{21F4}', '\u{22FF}', MathSymbol), ('\u{2300}', '\u{2307}', OtherSymbol), ('\u{2308}', '\u{2308}', OpenPunctuation),
+	('\u{2309}', '\u{2309}', ClosePunctuation), ('\u{230A}', '\u{230A}', OpenPunctuation), ('\u{230B}', '\u{230B}', ClosePunctuation),
+	('\u{230C}', '\u{231F}', OtherSymbol), ('\u{2320}', '\u{2321}', MathSymbol), ('\u{2322}', '\u{2328}', OtherSymbol),
+	('\u{2329}', '\u{2329}', OpenPunctuation), ('\u{232A}', '\u{232A}', ClosePunctuation), ('\u{232B}', '\u{237B}', OtherSymbol),
+	('\u{237C}', '\u{237C}', MathSymbol), ('\u{237D}', '\u{239A}', OtherSymbol), ('\u{239B}', '\u{23B3}', MathSymbol),
+	('\u{23B4}', '\u{23DB}', OtherSymbol), ('\u{23DC}', '\u{23E1}', MathSymbol), ('\u{23E2}', '\u{2426}', OtherSymbol),
+	('\u{2440}', '\u{244A}', OtherSymbol), ('\u{2460}', '\u{249B}', OtherNumber), ('\u{249C}', '\u{24E9}', OtherSymbol),
+	('\u{24EA}', '\u{24FF}', OtherNumber), ('\u{2500}', '\u{25B6}', OtherSymbol), ('\u{25B7}', '\u{25B7}', MathSymbol),
+	('\u{25B8}', '\u{25C0}', OtherSymbol), ('\u{25C1}', '\u{25C1}', MathSymbol), ('\u{25C2}', '\u{25F7}', OtherSymbol),
+	('\u{25F8}', '\u{25FF}', MathSymbol), ('\u{2600}', '\u{266E}', OtherSymbol), ('\u{266F}', '\u{266F}', MathSymbol),
+	('\u{2670}', '\u{2767}', OtherSymbol), ('\u{2768}', '\u{2768}', OpenPunctuation), ('\u{2769}', '\u{2769}', ClosePunctuation),
+	('\u{276A}', '\u{276A}', OpenPunctuation), ('\u{276B}', '\u{276B}', ClosePunctuation), ('\u{276C}', '\u{276C}', OpenPunctuation),
+	('\u{276D}', '\u{276D}', ClosePunctuation), ('\u{276E}', '\u{276E}', OpenPunctuation), ('\u{276F}', '\u{276F}', ClosePunctuation),
+	('\u{2770}', '\u{2770}', OpenPunctuation), ('\u{2771}', '\u{2771}', ClosePunctuation), ('\u{2772}', '\u{2772}', OpenPunctuation),
+	('\u{2773}', '\u{2773}', ClosePunctuation), ('\u{2774}', '\u{2774}', OpenPunctuation), ('\u{2775}', '\u{2775}', ClosePunctuation),
+	('\u{2776}', '\u{2793}', OtherNumber), ('\u{2794}', '\u{27BF}', OtherSymbol), ('\u{27C0}', '\u{27C4}', MathSymbol),
+	('\u{27C5}', '\u{27C5}', OpenPunctuation), ('\u{27C6}', '\u{27C6}', ClosePunctuation), ('\u{27C7}', '\u{27E5}', MathSymbol),
+	('\u{27E6}', '\u{27E6}', OpenPunctuation), ('\u{27E7}', '\u{27E7}', ClosePunctuation), ('\u{27E8}', '\u{27E8}', OpenPunctuation),
+	('\u{27E9}', '\u{27E9}', ClosePunctuation), ('\u{27EA}', '\u{27EA}', OpenPunctuation), ('\u{27EB}', '\u{27EB}', ClosePunctuation),
+	('\u{27EC}', '\u{27EC}', OpenPunctuation), ('\u{27ED}', '\u{27ED}', ClosePunctuation), ('\u{27EE}', '\u{27EE}', OpenPunctuation),
+	('\u{27EF}', '\u{27EF}', ClosePunctuation), ('\u{27F0}', '\u{27FF}', MathSymbol), ('\u{2800}', '\u{28FF}', OtherSymbol),
+	('\u{2900}', '\u{2982}', MathSymbol), ('\u{2983}', '\u{2983}', OpenPunctuation), ('\u{2984}', '\u{2984}', ClosePunctuation),
+	('\u{2985}', '\u{2985}', OpenPunctuation), ('\u{2986}', '\u{2986}', ClosePunctuation), ('\u{2987}', '\u{2987}', OpenPunctuation),
+	('\u{2988}', '\u{2988}', ClosePunctuation), ('\u{2989}', '\u{2989}', OpenPunctuation), ('\u{298A}', '\u{298A}', ClosePunctuation),
+	('\u{298B}', '\u{298B}', OpenPunctuation), ('\u{298C}', '\u{298C}', ClosePunctuation), ('\u{298D}', '\u{298D}', OpenPunctuation),
+	('\u{298E}', '\u{298E}', ClosePunctuation), ('\u{298F}', '\u{298F}', OpenPunctuation), ('\u{2990}', '\u{2990}', ClosePunctuation),
+	('\u{2991}', '\u{2991}', OpenPunctuation), ('\u{2992}', '\u{2992}', ClosePunctuation), ('\u{2993}', '\u{2993}', OpenPunctuation),
+	('\u{2994}', '\u{2994}', ClosePunctuation), ('\u{2995}', '\u{2995}', OpenPunctuation), ('\u{2996}', '\u{2996}', ClosePunctuation),
+	('\u{2997}', '\u{2997}', OpenPunctuation), ('\u{2998}', '\u{2998}', ClosePunctuation), ('\u{2999}', '\u{29D7}', MathSymbol),
+	('\u{29D8}', '\u{29D8}', OpenPunctuation), ('\u{29D9}', '\u{29D9}', ClosePunctuation), ('\u{29DA}', '\u{29DA}', OpenPunctuation),
+	('\u{29DB}', '\u{29DB}', ClosePunctuation), ('\u{29DC}', '\u{29FB}', MathSymbol), ('\u{29FC}', '\u{29FC}', OpenPunctuation),
+	('\u{29FD}', '\u{29FD}', ClosePunctuation), ('\u{29FE}', '\u{2AFF}', MathSymbol), ('\u{2B00}', '\u{2B2F}', OtherSymbol),
+	('\u{2B30}', '\u{2B44}', MathSymbol), ('\u{2B45}', '\u{2B46}', OtherSymbol), ('\u{2B47}', '\u{2B4C}', MathSymbol),
+	('\u{2B4D}', '\u{2B73}', OtherSymbol), ('\u{2B76}', '\u{2B95}', OtherSymbol), ('\u{2B97}', '\u{2BFF}', OtherSymbol),
+	('\u{2C00}', '\u{2C2F}', UppercaseLetter), ('\u{2C30}', '\u{2C5F}', LowercaseLetter), ('\u{2C60}', '\u{2C60}', UppercaseLetter),
+	('\u{2C61}', '\u{2C61}', LowercaseLetter), ('\u{2C62}', '\u{2C64}', UppercaseLetter), ('\u{2C65}', '\u{2C66}', LowercaseLetter),
+	('\u{2C67}', '\u{2C67}', UppercaseLetter), ('\u{2C68}', '\u{2C68}', LowercaseLetter), ('\u{2C69}', '\u{2C69}', UppercaseLetter),
+	('\u{2C6A}', '\u{2C6A}', LowercaseLetter), ('\u{2C6B}', '\u{2C6B}', UppercaseLetter), ('\u{2C6C}', '\u{2C6C}', LowercaseLetter),
+	('\u{2C6D}', '\u{2C70}', UppercaseLetter), ('\u{2C71}', '\u{2C71}', LowercaseLetter), ('\u{2C72}', '\u{2C72}', UppercaseLetter),
+	('\u{2C73}', '\u{2C74}', LowercaseLetter), ('\u{2C75}', '\u{2C75}', UppercaseLetter), ('\u{2C76}', '\u{2C7B}', LowercaseLetter),
+	('\u{2C7C}', '\u{2C7D}', ModifierLetter), ('\u{2C7E}', '\u{2C80}', UppercaseLetter), ('\u{2C81}', '\u{2C81}', LowercaseLetter),
+	('\u{2C82}', '\u{2C82}', UppercaseLetter), ('\u{2C83}', '\u{2C83}', LowercaseLetter), ('\u{2C84}', '\u{2C84}', UppercaseLetter),
+	('\u{2C85}', '\u{2C85}', LowercaseLetter), ('\u{2C86}', '\u{2C86}', UppercaseLetter), ('\u{2C87}', '\u{2C87}', LowercaseLetter),
+	('\u{2C88}', '\u{2C88}', UppercaseLetter), ('\u{2C89}', '\u{2C89}', LowercaseLetter), ('\u{2C8A}', '\u{2C8A}', UppercaseLetter),
+	('\u{2C8B}', '\u{2C8B}', LowercaseLetter), ('\u{2C8C}', '\u{2C8C}', UppercaseLetter), ('\u{2C8D}', '\u{2C8D}', LowercaseLetter),
+	('\u{2C8E}', '\u{2C8E}', UppercaseLetter), ('\u{2C8F}', '\u{2C8F}', LowercaseLetter), ('\u{2C90}', '\u{2C90}', UppercaseLetter),
+	('\u{2C91}', '\u{2C91}', LowercaseLetter), ('\u{2C92}', '\u{2C92}', UppercaseLetter), ('\u{2C93}', '\u{2C93}', LowercaseLetter),
+	('\u{2C94}', '\u{2C94}', UppercaseLetter), ('\u{2C95}', '\u{2C95}', LowercaseLetter), ('\u{2C96}', '\u{2C96}', UppercaseLetter),
+	('\u{2C97}', '\u{2C97}', LowercaseLetter), ('\u{2C98}', '\u{2C98}', UppercaseLetter), ('\u{2C99}', '\u{2C99}', LowercaseLetter),
+	('\u{2C9A}', '\u{2C9A}', UppercaseLetter), ('\u{2C9B}', '\u{2C9B}', LowercaseLetter), ('\u{2C9C}', '\u{2C9C}', UppercaseLetter),
+	('\u{2C9D}', '\u{2C9D}', LowercaseLetter), ('\u{2C9E}', '\u{2C9E}', UppercaseLetter), ('\u{2C9F}', '\u{2C9F}', LowercaseLetter),
+	('\u{2CA0}', '\u{2CA0}', UppercaseLetter), ('\u{2CA1}', '\u{2CA1}', LowercaseLetter), ('\u{2CA2}', '\u{2CA2}', UppercaseLetter),
+	('\u{2CA3}', '\u{2CA3}', LowercaseLetter), ('\u{2CA4}', '\u{2CA4}', UppercaseLetter), ('\u{2CA5}', '\u{2CA5}', LowercaseLetter),
+	('\u{2CA6}', '\u{2CA6}', UppercaseLetter), ('\u{2CA7}', '\u{2CA7}', LowercaseLetter), ('\u{2CA8}', '\u{2CA8}', UppercaseLetter),
+	('\u{2CA9}', '\u{2CA9}', LowercaseLetter), ('\u{2CAA}', '\u{2CAA}', UppercaseLetter), ('\u{2CAB}', '\u{2CAB}', LowercaseLetter),
+	('\u{2CAC}', '\u{2CAC}', UppercaseLetter), ('\u{2CAD}', '\u{2CAD}', LowercaseLetter), ('\u{2CAE}', '\u{2CAE}', UppercaseLetter),
+	('\u{2CAF}', '\u{2CAF}', LowercaseLetter), ('\u{2CB0}', '\u{2CB0}', UppercaseLetter), ('\u{2CB1}', '\u{2CB1}', LowercaseLetter),
+	('\u{2CB2}', '\u{2CB2}', UppercaseLetter), ('\u{2CB3}', '\u{2CB3}', LowercaseLetter), ('\u{2CB4}', '\u{2CB4}', UppercaseLetter),
+	('\u{2CB5}', '\u{2CB5}', LowercaseLetter), ('\u{2CB6}', '\u{2CB6}', UppercaseLetter), ('\u{2CB7}', '\u{2CB7}', LowercaseLetter),
+	('\u{2CB8}', '\u{2CB8}', UppercaseLetter), ('\u{2CB9}', '\u{2CB9}', LowercaseLetter), ('\u{2CBA}', '\u{2CBA}', UppercaseLetter),
+	('\u{2CBB}', '\u{2CBB}', LowercaseLetter), ('\u{2CBC}', '\u{2CBC}', UppercaseLetter), ('\u{2CBD}', '\u{2CBD}', LowercaseLetter),
+	('\u{2CBE}', '\u{2CBE}', UppercaseLetter), ('\u{2CBF}', '\u{2CBF}', LowercaseLetter), ('\u{2CC0}', '\u{2CC0}', UppercaseLetter),
+	('\u{2CC1}', '\u{2CC1}', LowercaseLetter), ('\u{2CC2}', '\u{2CC2}', UppercaseLetter), ('\u{2CC3}', '\u{2CC3}', LowercaseLetter),
+	('\u{2CC4}', '\u{2CC4}', UppercaseLetter), ('\u{2CC5}', '\u{2CC5}', LowercaseLetter), ('\u{2CC6}', '\u{2CC6}', UppercaseLetter),
+	('\u{2CC7}', '\u{2CC7}', LowercaseLetter), ('\u{2CC8}', '\u{2CC8}', UppercaseLetter), ('\u{2CC9}', '\u{2CC9}', LowercaseLetter),
+	('\u{2CCA}', '\u{2CCA}', UppercaseLetter), ('\u{2CCB}', '\u{2CCB}', LowercaseLetter), ('\u{2CCC}', '\u{2CCC}', UppercaseLetter),
+	('\u{2CCD}', '\u{2CCD}', LowercaseLetter), ('\u{2CCE}', '\u{2CCE}', UppercaseLetter), ('\u{2CCF}', '\u{2CCF}', LowercaseLetter),
+	('\u{2CD0}', '\u{2CD0}', UppercaseLetter), ('\u{2CD1}', '\u{2CD1}', LowercaseLetter), ('\u{2CD2}', '\u{2CD2}', UppercaseLetter),
+	('\u{2CD3}', '\u{2CD3}', LowercaseLetter), ('\u{2CD4}', '\u{2CD4}', UppercaseLetter), ('\u{2CD5}', '\u{2CD5}', LowercaseLetter),
+	('\u{2CD6}', '\u{2CD6}', UppercaseLetter), ('\u{2CD7}', '\u{2CD7}', LowercaseLetter), ('\u{2CD8}', '\u{2CD8}', UppercaseLetter),
+	('\u{2CD9}', '\u{2CD9}', LowercaseLetter), ('\u{2CDA}', '\u{2CDA}', UppercaseLetter), ('\u{2CDB}', '\u{2CDB}', LowercaseLetter),
+	('\u{2CDC}', '\u{2CDC}', UppercaseLetter), ('\u{2CDD}', '\u{2CDD}', LowercaseLetter), ('\u{2CDE}', '\u{2CDE}', UppercaseLetter),
+	('\u{2CDF}', '\u{2CDF}', LowercaseLetter), ('\u{2CE0}', '\u{2CE0}', UppercaseLetter), ('\u{2CE1}', '\u{2CE1}', LowercaseLetter),
+	('\u{2CE2}', '\u{2CE2}', UppercaseLetter), ('\u{2CE3}', '\u{2CE4}', LowercaseLetter), ('\u{2CE5}', '\u{2CEA}', OtherSymbol),
+	('\u{2CEB}', '\u{2CEB}', UppercaseLetter), ('\u{2CEC}', '\u{2CEC}', LowercaseLetter), ('\u{2CED}', '\u{2CED}', UppercaseLetter),
+	('\u{2CEE}', '\u{2CEE}', LowercaseLetter), ('\u{2CEF}', '\u{2CF1}', NonspacingMark), ('\u{2CF2}', '\u{2CF2}', UppercaseLetter),
+	('\u{2CF3}', '\u{2CF3}', LowercaseLetter), ('\u{2CF9}', '\u{2CFC}', OtherPunctuation), ('\u{2CFD}', '\u{2CFD}', OtherNumber),
+	('\u{2CFE}', '\u{2CFF}', OtherPunctuation), ('\u{2D00}', '\u{2D25}', LowercaseLetter), ('\u{2D27}', '\u{2D27}', LowercaseLetter),
+	('\u{2D2D}', '\u{2D2D}', LowercaseLetter), ('\u{2D30}', '\u{2D67}', OtherLetter), ('\u{2D6F}', '\u{2D6F}', ModifierLetter),
+	('\u{2D70}', '\u{2D70}', OtherPunctuation), ('\u{2D7F}', '\u{2D7F}', NonspacingMark), ('\u{2D80}', '\u{2D96}', OtherLetter),
+	('\u{2DA0}', '\u{2DA6}', OtherLetter), ('\u{2DA8}', '\u{2DAE}', OtherLetter), ('\u{2DB0}', '\u{2DB6}', OtherLetter),
+	('\u{2DB8}', '\u{2DBE}', OtherLetter), ('\u{2DC0}', '\u{2DC6}', OtherLetter), ('\u{2DC8}', '\u{2DCE}', OtherLetter),
+	('\u{2DD0}', '\u{2DD6}', OtherLetter), ('\u{2DD8}', '\u{2DDE}', OtherLetter), ('\u{2DE0}', '\u{2DFF}', NonspacingMark),
+	('\u{2E00}', '\u{2E01}', OtherPunctuation), ('\u{2E02}', '\u{2E02}', InitialPunctuation), ('\u{2E03}', '\u{2E03}', FinalPunctuation),
+	('\u{2E04}', '\u{2E04}', InitialPunctuation), ('\u{2E05}', '\u{2E05}', FinalPunctuation), ('\u{2E06}', '\u{2E08}', OtherPunctuation),
+	('\u{2E09}', '\u{2E09}', InitialPunctuation), ('\u{2E0A}', '\u{2E0A}', FinalPunctuation), ('\u{2E0B}', '\u{2E0B}', OtherPunctuation),
+	('\u{2E0C}', '\u{2E0C}', InitialPunctuation), ('\u{2E0D}', '\u{2E0D}', FinalPunctuation), ('\u{2E0E}', '\u{2E16}', OtherPunctuation),
+	('\u{2E17}', '\u{2E17}', DashPunctuation), ('\u{2E18}', '\u{2E19}', OtherPunctuation), ('\u{2E1A}', '\u{2E1A}', DashPunctuation),
+	('\u{2E1B}', '\u{2E1B}', OtherPunctuation), ('\u{2E1C}', '\u{2E1C}', InitialPunctuation), ('\u{2E1D}', '\u{2E1D}', FinalPunctuation),
+	('\u{2E1E}', '\u{2E1F}', OtherPunctuation), ('\u{2E20}', '\u{2E20}', InitialPunctuation), ('\u{2E21}', '\u{2E21}', FinalPunctuation),
+	('\u{2E22}', '\u{2E22}', OpenPunctuation), ('\u{2E23}', '\u{2E23}', ClosePunctuation), ('\u{2E24}', '\u{2E24}', OpenPunctuation),
+	('\u{2E25}', '\u{2E25}', ClosePunctuation), ('\u{2E26}', '\u{2E26}', OpenPunctuation), ('\u{2E27}', '\u{2E27}', ClosePunctuation),
+	('\u{2E28}', '\u{2E28}', OpenPunctuation), ('\u{2E29}', '\u{2E29}', ClosePunctuation), ('\u{2E2A}', '\u{2E2E}', OtherPunctuation),
+	('\u{2E2F}', '\u{2E2F}', ModifierLetter), ('\u{2E30}', '\u{2E39}', OtherPunctuation), ('\u{2E3A}', '\u{2E3B}', DashPunctuation),
+	('\u{2E3C}', '\u{2E3F}', OtherPunctuation), ('\u{2E40}', '\u{2E40}', DashPunctuation), ('\u{2E41}', '\u{2E41}', OtherPunctuation),
+	('\u{2E42}', '\u{2E42}', OpenPunctuation), ('\u{2E43}', '\u{2E4F}', OtherPunctuation), ('\u{2E50}', '\u{2E51}', OtherSymbol),
+	('\u{2E52}', '\u{2E54}', OtherPunctuation), ('\u{2E55}', '\u{2E55}', OpenPunctuation), ('\u{2E56}', '\u{2E56}', ClosePunctuation),
+	('\u{2E57}', '\u{2E57}', OpenPunctuation), ('\u{2E58}', '\u{2E58}', ClosePunctuation), ('\u{2E59}', '\u{2E59}', OpenPunctuation),
+	('\u{2E5A}', '\u{2E5A}', ClosePunctuation), ('\u{2E5B}', '\u{2E5B}', OpenPunctuation), ('\u{2E5C}', '\u{2E5C}', ClosePunctuation),
+	('\u{2E5D}', '\u{2E5D}', DashPunctuation), ('\u{2E80}', '\u{2E99}', OtherSymbol), ('\u{2E9B}', '\u{2EF3}', OtherSymbol),
+	('\u{2F00}', '\u{2FD5}', OtherSymbol), ('\u{2FF0}', '\u{2FFB}', OtherSymbol), ('\u{3000}', '\u{3000}', SpaceSeparator),
+	('\u{3001}', '\u{3003}', OtherPunctuation), ('\u{3004}', '\u{3004}', OtherSymbol), ('\u{3005}', '\u{3005}', ModifierLetter),
+	('\u{3006}', '\u{3006}', OtherLetter), ('\u{3007}', '\u{3007}', LetterNumber), ('\u{3008}', '\u{3008}', OpenPunctuation),
+	('\u{3009}', '\u{3009}', ClosePunctuation), ('\u{300A}', '\u{300A}', OpenPunctuation), ('\u{300B}', '\u{300B}', ClosePunctuation),
+	('\u{300C}', '\u{300C}', OpenPunctuation), ('\u{300D}', '\u{300D}', ClosePunctuation), ('\u{300E}', '\u{300E}', OpenPunctuation),
+	('\u{300F}', '\u{300F}', ClosePunctuation), ('\u{3010}', '\u{3010}', OpenPunctuation), ('\u{3011}', '\u{3011}', ClosePunctuation),
+	('\u{3012}', '\u{3013}', OtherSymbol), ('\u{3014}', '\u{3014}', OpenPunctuation), ('\u{3015}', '\u{3015}', ClosePunctuation),
+	('\u{3016}', '\u{3016}', OpenPunctuation), ('\u{3017}', '\u{3017}', ClosePunctuation), ('\u{3018}', '\u{3018}', OpenPunctuation),
+	('\u{3019}', '\u{3019}', ClosePunctuation), ('\u{301A}', '\u{301A}', OpenPunctuation), ('\u{301B}', '\u{301B}', ClosePunctuation),
+	('\u{301C}', '\u{301C}', DashPunctuation), ('\u{301D}', '\u{301D}', OpenPunctuation), ('\u{301E}', '\u{301F}', ClosePunctuation),
+	('\u{3020}', '\u{3020}', OtherSymbol), ('\u{3021}', '\u{3029}', LetterNumber), ('\u{302A}', '\u{302D}', NonspacingMark),
+	('\u{302E}', '\u{302F}', SpacingMark), ('\u{3030}', '\u{3030}', DashPunctuation), ('\u{3031}', '\u{3035}', ModifierLetter),
+	('\u{3036}', '\u{3037}', OtherSymbol), ('\u{3038}', '\u{303A}', LetterNumber), ('\u{303B}', '\u{303B}', ModifierLetter),
+	('\u{303C}', '\u{303C}', OtherLetter), ('\u{303D}', '\u{303D}', OtherPunctuation), ('\u{303E}', '\u{303F}', OtherSymbol),
+	('\u{3041}', '\u{3096}', OtherLetter), ('\u{3099}', '\u{309A}', NonspacingMark), ('\u{309B}', '\u{309C}', ModifierSymbol),
+	('\u{309D}', '\u{309E}', ModifierLetter), ('\u{309F}', '\u{309F}', OtherLetter), ('\u{30A0}', '\u{30A0}', DashPunctuation),
+	('\u{30A1}', '\u{30FA}', OtherLetter), ('\u{30FB}', '\u{30FB}', OtherPunctuation), ('\u{30FC}', '\u{30FE}', ModifierLetter),
+	('\u{30FF}', '\u{30FF}', OtherLetter), ('\u{3105}', '\u{312F}', OtherLetter), ('\u{3131}', '\u{318E}', OtherLetter),
+	('\u{3190}', '\u{3191}', OtherSymbol), ('\u{3192}', '\u{3195}', OtherNumber), ('\u{3196}', '\u{319F}', OtherSymbol),
+	('\u{31A0}', '\u{31BF}', OtherLetter), ('\u{31C0}', '\u{31E3}', OtherSymbol), ('\u{31F0}', '\u{31FF}', OtherLetter),
+	('\u{3200}', '\u{321E}', OtherSymbol), ('\u{3220}', '\u{3229}', OtherNumber), ('\u{322A}', '\u{3247}', OtherSymbol),
+	('\u{3248}', '\u{324F}', OtherNumber), ('\u{3250}', '\u{3250}', OtherSymbol), ('\u{3251}', '\u{325F}', OtherNumber),
+	('\u{3260}', '\u{327F}', OtherSymbol), ('\u{3280}', '\u{3289}', OtherNumber), ('\u{328A}', '\u{32B0}', OtherSymbol),
+	('\u{32B1}', '\u{32BF}', OtherNumber), ('\u{32C0}', '\u{33FF}', OtherSymbol), ('\u{3400}', '\u{4DBF}', OtherLetter),
+	('\u{4DC0}', '\u{4DFF}', OtherSymbol), ('\u{4E00}', '\u{A014}', OtherLetter), ('\u{A015}', '\u{A015}', ModifierLetter),
+	('\u{A016}', '\u{A48C}', OtherLetter), ('\u{A490}', '\u{A4C6}', OtherSymbol), ('\u{A4D0}', '\u{A4F7}', OtherLetter),
+	('\u{A4F8}', '\u{A4FD}', ModifierLetter), ('\u{A4FE}', '\u{A4FF}', OtherPunctuation), ('\u{A500}', '\u{A60B}', OtherLetter),
+	('\u{A60C}', '\u{A60C}', ModifierLetter), ('\u{A60D}', '\u{A60F}', OtherPunctuation), ('\u{A610}', '\u{A61F}', OtherLetter),
+	('\u{A620}', '\u{A629}', DecimalNumber), ('\u{A62A}', '\u{A62B}', OtherLetter), ('\u{A640}', '\u{A640}', UppercaseLetter),
+	('\u{A641}', '\u{A641}', LowercaseLetter), ('\u{A642}', '\u{A642}', UppercaseLetter), ('\u{A643}', '\u{A643}', LowercaseLetter),
+	('\u{A644}', '\u{A644}', UppercaseLetter), ('\u{A645}', '\u{A645}', LowercaseLetter), ('\u{A646}', '\u{A646}', UppercaseLetter),
+	('\u{A647}', '\u{A647}', LowercaseLetter), ('\u{A648}', '\u{A648}', UppercaseLetter), ('\u{A649}', '\u{A649}', LowercaseLetter),
+	('\u{A64A}', '\u{A64A}', UppercaseLetter), ('\u{A64B}', '\u{A64B}', LowercaseLetter), ('\u{A64C}', '\u{A64C}', UppercaseLetter),
+	('\u{A64D}', '\u{A64D}', LowercaseLetter), ('\u{A64E}', '\u{A64E}', UppercaseLetter), ('\u{A64F}', '\u{A64F}', LowercaseLetter),
+	('\u{A650}', '\u{A650}', UppercaseLetter), ('\u{A651}', '\u{A651}', LowercaseLetter), ('\u{A652}', '\u{A652}', UppercaseLetter),
+	('\u{A653}', '\u{A653}', LowercaseLetter), ('\u{A654}', '\u{A654}', UppercaseLetter), ('\u{A655}', '\u{A655}', LowercaseLetter),
+	('\u{A656}', '\u{A656}', UppercaseLetter), ('\u{A657}', '\u{A657}', LowercaseLetter), ('\u{A658}', '\u{A658}', UppercaseLetter),
+	('\u{A659}', '\u{A659}', LowercaseLetter), ('\u{A65A}', '\u{A65A}', UppercaseLetter), ('\u{A65B}', '\u{A65B}', LowercaseLetter),
+	('\u{A65C}', '\u{A65C}', UppercaseLetter), ('\u{A65D}', '\u{A65D}', LowercaseLetter), ('\u{A65E}', '\u{A65E}', UppercaseLetter),
+	('\u{A65F}', '\u{A65F}', LowercaseLetter), ('\u{A660}', '\u{A660}', UppercaseLetter), ('\u{A661}', '\u{A661}', LowercaseLetter),
+	('\u{A662}', '\u{A662}', UppercaseLetter), ('\u{A663}', '\u{A663}', LowercaseLetter), ('\u{A664}', '\u{A664}', UppercaseLetter),
+	('\u{A665}', '\u{A665}', LowercaseLetter), ('\u{A666}', '\u{A666}', UppercaseLetter), ('\u{A667}', '\u{A667}', LowercaseLetter),
+	('\u{A668}', '\u{A668}', UppercaseLetter), ('\u{A669}', '\u{A669}', LowercaseLetter), ('\u{A66A}', '\u{A66A}', UppercaseLetter),
+	('\u{A66B}', '\u{A66B}', LowercaseLetter), ('\u{A66C}', '\u{A66C}', UppercaseLetter), ('\u{A66D}', '\u{A66D}', LowercaseLetter),
+	('\u{A66E}', '\u{A66E}', OtherLetter), ('\u{A66F}', '\u{A66F}', NonspacingMark), ('\u{A670}', '\u{A672}', EnclosingMark),
+	('\u{A673}', '\u{A673}', OtherPunctuation), ('\u{A674}', '\u{A67D}', NonspacingMark), ('\u{A67E}', '\u{A67E}', OtherPunctuation),
+	('\u{A67F}', '\u{A67F}', ModifierLetter), ('\u{A680}', '\u{A680}', UppercaseLetter), ('\u{A681}', '\u{A681}', LowercaseLetter),
+	('\u{A682}', '\u{A682}', UppercaseLetter), ('\u{A683}', '\u{A683}', LowercaseLetter), ('\u{A684}', '\u{A684}', UppercaseLetter),
+	('\u{A685}', '\u{A685}', LowercaseLetter), ('\u{A686}', '\u{A686}', UppercaseLetter), ('\u{A687}', '\u{A687}', LowercaseLetter),
+	('\u{A688}', '\u{A688}', UppercaseLetter), ('\u{A689}', '\u{A689}', LowercaseLetter), ('\u{A68A}', '\u{A68A}', UppercaseLetter),
+	('\u{A68B}', '\u{A68B}', LowercaseLetter), ('\u{A68C}', '\u{A68C}', UppercaseLetter), ('\u{A68D}', '\u{A68D}', LowercaseLetter),
+	('\u{A68E}', '\u{A68E}', UppercaseLetter), ('\u{A68F}', '\u{A68F}', LowercaseLetter), ('\u{A690}', '\u{A690}', UppercaseLetter),
+	('\u{A691}', '\u{A691}', LowercaseLetter), ('\u{A692}', '\u{A692}', UppercaseLetter), ('\u{A693}', '\u{A693}', LowercaseLetter),
+	('\u{A694}', '\u{A694}', UppercaseLetter), ('\u{A695}', '\u{A695}', LowercaseLetter), ('\u{A696}', '\u{A696}', UppercaseLetter),
+	('\u{A697}', '\u{A697}', LowercaseLetter), ('\u{A698}', '\u{A698}', UppercaseLetter), ('\u{A699}', '\u{A699}', LowercaseLetter),
+	('\u{A69A}', '\u{A69A}', UppercaseLetter), ('\u{A69B}', '\u{A69B}', LowercaseLetter), ('\u{A69C}', '\u{A69D}', ModifierLetter),
+	('\u{A69E}', '\u{A69F}', NonspacingMark), ('\u{A6A0}', '\u{A6E5}', OtherLetter), ('\u{A6E6}', '\u{A6EF}', LetterNumber),
+	('\u{A6F0}', '\u{A6F1}', NonspacingMark), ('\u{A6F2}', '\u{A6F7}', OtherPunctuation), ('\u{A700}', '\u{A716}', ModifierSymbol),
+	('\u{A717}', '\u{A71F}', ModifierLetter), ('\u{A720}', '\u{A721}', ModifierSymbol), ('\u{A722}', '\u{A722}', UppercaseLetter),
+	('\u{A723}', '\u{A723}', LowercaseLetter), ('\u{A724}', '\u{A724}', UppercaseLetter), ('\u{A725}', '\u{A725}', LowercaseLetter),
+	('\u{A726}', '\u{A726}', UppercaseLetter), ('\u{A727}', '\u{A727}', LowercaseLetter), ('\u{A728}', '\u{A728}', UppercaseLetter),
+	('\u{A729}', '\u{A729}', LowercaseLetter), ('\u{A72A}', '\u{A72A}', UppercaseLetter), ('\u{A72B}', '\u{A72B}', LowercaseLetter),
+	('\u{A72C}', '\u{A72C}', UppercaseLetter), ('\u{A72D}', '\u{A72D}', LowercaseLetter), ('\u{A72E}', '\u{A72E}', UppercaseLetter),
+	('\u{A72F}', '\u{A731}', LowercaseLetter), ('\u{A732}', '\u{A732}', UppercaseLetter), ('\u{A733}', '\u{A733}', LowercaseLetter),
+	('\u{A734}', '\u{A734}', UppercaseLetter), ('\u{A735}', '\u{A735}', LowercaseLetter), ('\u{A736}', '\u{A736}', UppercaseLetter),
+	('\u{A737}', '\u{A737}', LowercaseLetter), ('\u{A738}', '\u{A738}', UppercaseLetter), ('\u{A739}', '\u{A739}', LowercaseLetter),
+	('\u{A73A}', '\u{A73A}', UppercaseLetter), ('\u{A73B}', '\u{A73B}', LowercaseLetter), ('\u{A73C}', '\u{A73C}', UppercaseLetter),
+	('\u{A73D}', '\u{A73D}', LowercaseLetter), ('\u{A73E}', '\u{A73E}', UppercaseLetter), ('\u{A73F}', '\u{A73F}', LowercaseLetter),
+	('\u{A740}', '\u{A740}', UppercaseLetter), ('\u{A741}', '\u{A741}', LowercaseLetter), ('\u{A742}', '\u{A742}', UppercaseLetter),
+	('\u{A743}', '\u{A743}', LowercaseLetter), ('\u{A744}', '\u{A744}', UppercaseLetter), ('\u{A745}', '\u{A745}', LowercaseLetter),
+	('\u{A746}', '\u{A746}', UppercaseLetter), ('\u{A747}', '\u{A747}', LowercaseLetter), ('\u{A748}', '\u{A748}', UppercaseLetter),
+	('\u{A749}', '\u{A749}', LowercaseLetter), ('\u{A74A}', '\u{A74A}', UppercaseLetter), ('\u{A74B}', '\u{A74B}', LowercaseLetter),
+	('\u{A74C}', '\u{A74C}', UppercaseLetter), ('\u{A74D}', '\u{A74D}', LowercaseLetter), ('\u{A74E}', '\u{A74E}', UppercaseLetter),
+	('\u{A74F}', '\u{A74F}', LowercaseLetter), ('\u{A750}', '\u{A750}', UppercaseLetter), ('\u{A751}', '\u{A751}', LowercaseLetter),
+	('\u{A752}', '\u{A752}', UppercaseLetter), ('\u{A753}', '\u{A753}', LowercaseLetter), ('\u{A754}', '\u{A754}', UppercaseLetter),
+	('\u{A755}', '\u{A755}', LowercaseLetter), ('\u{A756}', '\u{A756}', UppercaseLetter), ('\u{A757}', '\u{A757}', LowercaseLetter),
+	('\u{A758}', '\u{A758}', UppercaseLetter), ('\u{A759}', '\u{A759}', LowercaseLetter), ('\u{A75A}', '\u{A75A}', UppercaseLetter),
+	('\u{A75B}', '\u{A75B}', LowercaseLetter), ('\u{A75C}', '\u{A75C}', UppercaseLetter), ('\u{A75D}', '\u{A75D}', LowercaseLetter),
+	('\u{A75E}', '\u{A75E}', UppercaseLetter), ('\u{A75F}', '\u{A75F}', LowercaseLetter), ('\u{A760}', '\u{A760}', UppercaseLetter),
+	('\u{A761}', '\u{A761}', LowercaseLetter), ('\u{A762}', '\u{A762}', UppercaseLetter), ('\u{A763}', '\u{A763}', LowercaseLetter),
+	('\u{A764}', '\u{A764}', UppercaseLetter), ('\u{A765}', '\u{A765}', LowercaseLetter), ('\u{A766}', '\u{A766}', UppercaseLetter),
+	('\u{A767}', '\u{A767}', LowercaseLetter), ('\u{A768}', '\u{A768}', UppercaseLetter), ('\u{A769}', '\u{A769}', LowercaseLetter),
+	('\u{A76A}', '\u{A76A}', UppercaseLetter), ('\u{A76B}', '\u{A76B}', LowercaseLetter), ('\u{A76C}', '\u{A76C}', UppercaseLetter),
+	('\u{A76D}', '\u{A76D}', LowercaseLetter), ('\u{A76E}', '\u{A76E}', UppercaseLetter), ('\u{A76F}', '\u{A76F}', LowercaseLetter),
+	('\u{A770}', '\u{A770}', ModifierLetter), ('\u{A771}', '\u{A778}', LowercaseLetter), ('\u{A779}', '\u{A779}', UppercaseLetter),
+	('\u{A77A}', '\u{A77A}', LowercaseLetter), ('\u{A77B}', '\u{A77B}', UppercaseLetter), ('\u{A77C}', '\u{A77C}', LowercaseLetter),
+	('\u{A77D}', '\u{A77E}', UppercaseLetter), ('\u{A77F}', '\u{A77F}', LowercaseLetter), ('\u{A780}', '\u{A780}', UppercaseLetter),
+	('\u{A781}', '\u{A781}', LowercaseLetter), ('\u{A782}', '\u{A782}', UppercaseLetter), ('\u{A783}', '\u{A783}', LowercaseLetter),
+	('\u{A784}', '\u{A784}', UppercaseLetter), ('\u{A785}', '\u{A785}', LowercaseLetter), ('\u{A786}', '\u{A786}', UppercaseLetter),
+	('\u{A787}', '\u{A787}', LowercaseLetter), ('\u{A788}', '\u{A788}', ModifierLetter), ('\u{A789}', '\u{A78A}', ModifierSymbol),
+	('\u{A78B}', '\u{A78B}', UppercaseLetter), ('\u{A78C}', '\u{A78C}', LowercaseLetter), ('\u{A78D}', '\u{A78D}', UppercaseLetter),
+	('\u{A78E}', '\u{A78E}', LowercaseLetter), ('\u{A78F}', '\u{A78F}', OtherLetter), ('\u{A790}', '\u{A790}', UppercaseLetter),
+	('\u{A791}', '\u{A791}', LowercaseLetter), ('\u{A792}', '\u{A792}', UppercaseLetter), ('\u{A793}', '\u{A795}', LowercaseLetter),
+	('\u{A796}', '\u{A796}', UppercaseLetter), ('\u{A797}', '\u{A797}', LowercaseLetter), ('\u{A798}', '\u{A798}', UppercaseLetter),
+	('\u{A799}', '\u{A799}', LowercaseLetter), ('\u{A79A}', '\u{A79A}', UppercaseLetter), ('\u{A79B}', '\u{A79B}', LowercaseLetter),
+	('\u{A79C}', '\u{A79C}', UppercaseLetter), ('\u{A79D}', '\u{A79D}', LowercaseLetter), ('\u{A79E}', '\u{A79E}', UppercaseLetter),
+	('\u{A79F}', '\u{A79F}', LowercaseLetter), ('\u{A7A0}', '\u{A7A0}', UppercaseLetter), ('\u{A7A1}', '\u{A7A1}', LowercaseLetter),
+	('\u{A7A2}', '\u{A7A2}', UppercaseLetter), ('\u{A7A3}', '\u{A7A3}', LowercaseLetter), ('\u{A7A4}', '\u{A7A4}', UppercaseLetter),
+	('\u{A7A5}', '\u{A7A5}', LowercaseLetter), ('\u{A7A6}', '\u{A7A6}', UppercaseLetter), ('\u{A7A7}', '\u{A7A7}', LowercaseLetter),
+	('\u{A7A8}', '\u{A7A8}', UppercaseLetter), ('\u{A7A9}', '\u{A7A9}', LowercaseLetter), ('\u{A7AA}', '\u{A7AE}', UppercaseLetter),
+	('\u{A7AF}', '\u{A7AF}', LowercaseLetter), ('\u{A7B0}', '\u{A7B4}', UppercaseLetter), ('\u{A7B5}', '\u{A7B5}', LowercaseLetter),
+	('\u{A7B6}', '\u{A7B6}', UppercaseLetter), ('\u{A7B7}', '\u{A7B7}', LowercaseLetter), ('\u{A7B8}', '\u{A7B8}', UppercaseLetter),
+	('\u{A7B9}', '\u{A7B9}', LowercaseLetter), ('\u{A7BA}', '\u{A7BA}', UppercaseLetter), ('\u{A7BB}', '\u{A7BB}', LowercaseLetter),
+	('\u{A7BC}', '\u{A7BC}', UppercaseLetter), ('\u{A7BD}', '\u{A7BD}', LowercaseLetter), ('\u{A7BE}', '\u{A7BE}', UppercaseLetter),
+	('\u{A7BF}', '\u{A7BF}', LowercaseLetter), ('\u{A7C0}', '\u{A7C0}', UppercaseLetter), ('\u{A7C1}', '\u{A7C1}', LowercaseLetter),
+	('\u{A7C2}', '\u{A7C2}', UppercaseLetter), ('\u{A7C3}', '\u{A7C3}', LowercaseLetter), ('\u{A7C4}', '\u{A7C7}', UppercaseLetter),
+	('\u{A7C8}', '\u{A7C8}', LowercaseLetter), ('\u{A7C9}', '\u{A7C9}', UppercaseLetter), ('\u{A7CA}', '\u{A7CA}', LowercaseLetter),
+	('\u{A7D0}', '\u{A7D0}', UppercaseLetter), ('\u{A7D1}', '\u{A7D1}', LowercaseLetter), ('\u{A7D3}', '\u{A7D3}', LowercaseLetter),
+	('\u{A7D5}', '\u{A7D5}', LowercaseLetter), ('\u{A7D6}', '\u{A7D6}', UppercaseLetter), ('\u{A7D7}', '\u{A7D7}', LowercaseLetter),
+	('\u{A7D8}', '\u{A7D8}', UppercaseLetter), ('\u{A7D9}', '\u{A7D9}', LowercaseLetter), ('\u{A7F2}', '\u{A7F4}', ModifierLetter),
+	('\u{A7F5}', '\u{A7F5}', UppercaseLetter), ('\u{A7F6}', '\u{A7F6}', LowercaseLetter), ('\u{A7F7}', '\u{A7F7}', OtherLetter),
+	('\u{A7F8}', '\u{A7F9}', ModifierLetter), ('\u{A7FA}', '\u{A7FA}', LowercaseLetter), ('\u{A7FB}', '\u{A801}', OtherLetter),
+	('\u{A802}', '\u{A802}', NonspacingMark), ('\u{A803}', '\u{A805}', OtherLetter), ('\u{A806}', '\u{A806}', NonspacingMark),
+	('\u{A807}', '\u{A80A}', OtherLetter), ('\u{A80B}', '\u{A80B}', NonspacingMark), ('\u{A80C}', '\u{A822}', OtherLetter),
+	('\u{A823}', '\u{A824}', SpacingMark), ('\u{A825}', '\u{A826}', NonspacingMark), ('\u{A827}', '\u{A827}', SpacingMark),
+	('\u{A828}', '\u{A82B}', OtherSymbol), ('\u{A82C}', '\u{A82C}', NonspacingMark), ('\u{A830}', '\u{A835}', OtherNumber),
+	('\u{A836}', '\u{A837}', OtherSymbol), ('\u{A838}', '\u{A838}', CurrencySymbol), ('\u{A839}', '\u{A839}', OtherSymbol),
+	('\u{A840}', '\u{A873}', OtherLetter), ('\u{A874}', '\u{A877}', OtherPunctuation), ('\u{A880}', '\u{A881}', SpacingMark),
+	('\u{A882}', '\u{A8B3}', OtherLetter), ('\u{A8B4}', '\u{A8C3}', SpacingMark), ('\u{A8C4}', '\u{A8C5}', NonspacingMark),
+	('\u{A8CE}', '\u{A8CF}', OtherPunctuation), ('\u{A8D0}', '\u{A8D9}', DecimalNumber), ('\u{A8E0}', '\u{A8F1}', NonspacingMark),
+	('\u{A8F2}', '\u{A8F7}', OtherLetter), ('\u{A8F8}', '\u{A8FA}', OtherPunctuation), ('\u{A8FB}', '\u{A8FB}', OtherLetter),
+	('\u{A8FC}', '\u{A8FC}', OtherPunctuation), ('\u{A8FD}', '\u{A8FE}', OtherLetter), ('\u{A8FF}', '\u{A8FF}', NonspacingMark),
+	('\u{A900}', '\u{A909}', DecimalNumber), ('\u{A90A}', '\u{A925}', OtherLetter), ('\u{A926}', '\u{A92D}', NonspacingMark),
+	('\u{A92E}', '\u{A92F}', OtherPunctuation), ('\u{A930}', '\u{A946}', OtherLetter), ('\u{A947}', '\u{A951}', NonspacingMark),
+	('\u{A952}', '\u{A953}', SpacingMark), ('\u{A95F}', '\u{A95F}', OtherPunctuation), ('\u{A960}', '\u{A97C}', OtherLetter),
+	('\u{A980}', '\u{A982}', NonspacingMark), ('\u{A983}', '\u{A983}', SpacingMark), ('\u{A984}', '\u{A9B2}', OtherLetter),
+	('\u{A9B3}', '\u{A9B3}', NonspacingMark), ('\u{A9B4}', '\u{A9B5}', SpacingMark), ('\u{A9B6}', '\u{A9B9}', NonspacingMark),
+	('\u{A9BA}', '\u{A9BB}', SpacingMark), ('\u{A9BC}', '\u{A9BD}', NonspacingMark), ('\u{A9BE}', '\u{A9C0}', SpacingMark),
+	('\u{A9C1}', '\u{A9CD}', OtherPunctuation), ('\u{A9CF}', '\u{A9CF}', ModifierLetter), ('\u{A9D0}', '\u{A9D9}', DecimalNumber),
+	('\u{A9DE}', '\u{A9DF}', OtherPunctuation), ('\u{A9E0}', '\u{A9E4}', OtherLetter), ('\u{A9E5}', '\u{A9E5}', NonspacingMark),
+	('\u{A9E6}', '\u{A9E6}', ModifierLetter), ('\u{A9E7}', '\u{A9EF}', OtherLetter), ('\u{A9F0}', '\u{A9F9}', DecimalNumber),
+	('\u{A9FA}', '\u{A9FE}', OtherLetter), ('\u{AA00}', '\u{AA28}', OtherLetter), ('\u{AA29}', '\u{AA2E}', NonspacingMark),
+	('\u{AA2F}', '\u{AA30}', SpacingMark), ('\u{AA31}', '\u{AA32}', NonspacingMark), ('\u{AA33}', '\u{AA34}', SpacingMark),
+	('\u{AA35}', '\u{AA36}', NonspacingMark), ('\u{AA40}', '\u{AA42}', OtherLetter), ('\u{AA43}', '\u{AA43}', NonspacingMark),
+	('\u{AA44}', '\u{AA4B}', OtherLetter), ('\u{AA4C}', '\u{AA4C}', NonspacingMark), ('\u{AA4D}', '\u{AA4D}', SpacingMark),
+	('\u{AA50}', '\u{AA59}', DecimalNumber), ('\u{AA5C}', '\u{AA5F}', OtherPunctuation), ('\u{AA60}', '\u{AA6F}', OtherLetter),
+	('\u{AA70}', '\u{AA70}', ModifierLetter), ('\u{AA71}', '\u{AA76}', OtherLetter), ('\u{AA77}', '\u{AA79}', OtherSymbol),
+	('\u{AA7A}', '\u{AA7A}', OtherLetter), ('\u{AA7B}', '\u{AA7B}', SpacingMark), ('\u{AA7C}', '\u{AA7C}', NonspacingMark),
+	('\u{AA7D}', '\u{AA7D}', SpacingMark), ('\u{AA7E}', '\u{AAAF}', OtherLetter), ('\u{AAB0}', '\u{AAB0}', NonspacingMark),
+	('\u{AAB1}', '\u{AAB1}', OtherLetter), ('\u{AAB2}', '\u{AAB4}', NonspacingMark), ('\u{AAB5}', '\u{AAB6}', OtherLetter),
+	('\u{AAB7}', '\u{AAB8}', NonspacingMark), ('\u{AAB9}', '\u{AABD}', OtherLetter), ('\u{AABE}', '\u{AABF}', NonspacingMark),
+	('\u{AAC0}', '\u{AAC0}', OtherLetter), ('\u{AAC1}', '\u{AAC1}', NonspacingMark), ('\u{AAC2}', '\u{AAC2}', OtherLetter),
+	('\u{AADB}', '\u{AADC}', OtherLetter), ('\u{AADD}', '\u{AADD}', ModifierLetter), ('\u{AADE}', '\u{AADF}', OtherPunctuation),
+	('\u{AAE0}', '\u{AAEA}', OtherLetter), ('\u{AAEB}', '\u{AAEB}', SpacingMark), ('\u{AAEC}', '\u{AAED}', NonspacingMark),
+	('\u{AAEE}', '\u{AAEF}', SpacingMark), ('\u{AAF0}', '\u{AAF1}', OtherPunctuation), ('\u{AAF2}', '\u{AAF2}', OtherLetter),
+	('\u{AAF3}', '\u{AAF4}', ModifierLetter), ('\u{AAF5}', '\u{AAF5}', SpacingMark), ('\u{AAF6}', '\u{AAF6}', NonspacingMark),
+	('\u{AB01}', '\u{AB06}', OtherLetter), ('\u{AB09}', '\u{AB0E}', OtherLetter), ('\u{AB11}', '\u{AB16}', OtherLetter),
+	('\u{AB20}', '\u{AB26}', OtherLetter), ('\u{AB28}', '\u{AB2E}', OtherLetter), ('\u{AB30}', '\u{AB5A}', LowercaseLetter),
+	('\u{AB5B}', '\u{AB5B}', ModifierSymbol), ('\u{AB5C}', '\u{AB5F}', ModifierLetter), ('\u{AB60}', '\u{AB68}', LowercaseLetter),
+	('\u{AB69}', '\u{AB69}', ModifierLetter), ('\u{AB6A}', '\u{AB6B}', ModifierSymbol), ('\u{AB70}', '\u{ABBF}', LowercaseLetter),
+	('\u{ABC0}', '\u{ABE2}', OtherLetter), ('\u{ABE3}', '\u{ABE4}', SpacingMark), ('\u{ABE5}', '\u{ABE5}', NonspacingMark),
+	('\u{ABE6}', '\u{ABE7}', SpacingMark), ('\u{ABE8}', '\u{ABE8}', NonspacingMark), ('\u{ABE9}', '\u{ABEA}', SpacingMark),
+	('\u{ABEB}', '\u{ABEB}', OtherPunctuation), ('\u{ABEC}', '\u{ABEC}', SpacingMark), ('\u{ABED}', '\u{ABED}', NonspacingMark),
+	('\u{ABF0}', '\u{ABF9}', DecimalNumber), ('\u{AC00}', '\u{D7A3}', OtherLetter), ('\u{D7B0}', '\u{D7C6}', OtherLetter),
+	('\u{D7CB}', '\u{D7FB}', OtherLetter), ('\u{E000}', '\u{F8FF}', PrivateUse), ('\u{F900}', '\u{FA6D}', OtherLetter),
+	('\u{FA70}', '\u{FAD9}', OtherLetter), ('\u{FB00}', '\u{FB06}', LowercaseLetter), ('\u{FB13}', '\u{FB17}', LowercaseLetter),
+	('\u{FB1D}', '\u{FB1D}', OtherLetter), ('\u{FB1E}', '\u{FB1E}', NonspacingMark), ('\u{FB1F}', '\u{FB28}', OtherLetter),
+	('\u{FB29}', '\u{FB29}', MathSymbol), ('\u{FB2A}', '\u{FB36}', OtherLetter), ('\u{FB38}', '\u{FB3C}', OtherLetter),
+	('\u{FB3E}', '\u{FB3E}', OtherLetter), ('\u{FB40}', '\u{FB41}', OtherLetter), ('\u{FB43}', '\u{FB44}', OtherLetter),
+	('\u{FB46}', '\u{FBB1}', OtherLetter), ('\u{FBB2}', '\u{FBC2}', ModifierSymbol), ('\u{FBD3}', '\u{FD3D}', OtherLetter),
+	('\u{FD3E}', '\u{FD3E}', ClosePunctuation), ('\u{FD3F}', '\u{FD3F}', OpenPunctuation), ('\u{FD40}', '\u{FD4F}', OtherSymbol),
+	('\u{FD50}', '\u{FD8F}', OtherLetter), ('\u{FD92}', '\u{FDC7}', OtherLetter), ('\u{FDCF}', '\u{FDCF}', OtherSymbol),
+	('\u{FDF0}', '\u{FDFB}', OtherLetter), ('\u{FDFC}', '\u{FDFC}', CurrencySymbol), ('\u{FDFD}', '\u{FDFF}', OtherSymbol),
+	('\u{FE00}', '\u{FE0F}', NonspacingMark), ('\u{FE10}', '\u{FE16}', OtherPunctuation), ('\u{FE17}', '\u{FE17}', OpenPunctuation),
+	('\u{FE18}', '\u{FE18}', ClosePunctuation), ('\u{FE19}', '\u{FE19}', OtherPunctuation), ('\u{FE20}', '\u{FE2F}', NonspacingMark),
+	('\u{FE30}', '\u{FE30}', OtherPunctuation), ('\u{FE31}', '\u{FE32}', DashPunctuation), ('\u{FE33}', '\u{FE34}', ConnectorPunctuation),
+	('\u{FE35}', '\u{FE35}', OpenPunctuation), ('\u{FE36}', '\u{FE36}', ClosePunctuation), ('\u{FE37}', '\u{FE37}', OpenPunctuation),
+	('\u{FE38}', '\u{FE38}', ClosePunctuation), ('\u{FE39}', '\u{FE39}', OpenPunctuation), ('\u{FE3A}', '\u{FE3A}', ClosePunctuation),
+	('\u{FE3B}', '\u{FE3B}', OpenPunctuation), ('\u{FE3C}', '\u{FE3C}', ClosePunctuation), ('\u{FE3D}', '\u{FE3D}', OpenPunctuation),
+	('\u{FE3E}', '\u{FE3E}', ClosePunctuation), ('\u{FE3F}', '\u{FE3F}', OpenPunctuation), ('\u{FE40}', '\u{FE40}', ClosePunctuation),
+	('\u{FE41}', '\u{FE41}', OpenPunctuation), ('\u{FE42}', '\u{FE42}', ClosePunctuation), ('\u{FE43}', '\u{FE43}', OpenPunctuation),
+	('\u{FE44}', '\u{FE44}', ClosePunctuation), ('\u{FE45}', '\u{FE46}', OtherPunctuation), ('\u{FE47}', '\u{FE47}', OpenPunctuation),
+	('\u{FE48}', '\u{FE48}', ClosePunctuation), ('\u{FE49}', '\u{FE4C}', OtherPunctuation), ('\u{FE4D}', '\u{FE4F}', ConnectorPunctuation),
+	('\u{FE50}', '\u{FE52}', OtherPunctuation), ('\u{FE54}', '\u{FE57}', OtherPunctuation), ('\u{FE58}', '\u{FE58}', DashPunctuation),
+	('\u{FE59}', '\u{FE59}', OpenPunctuation), ('\u{FE5A}', '\u{FE5A}', ClosePunctuation), ('\u{FE5B}', '\u{FE5B}', OpenPunctuation),
+	('\u{FE5C}', '\u{FE5C}', ClosePunctuation), ('\u{FE5D}', '\u{FE5D}', OpenPunctuation), ('\u{FE5E}', '\u{FE5E}', ClosePunctuation),
+	('\u{FE5F}', '\u{FE61}', OtherPunctuation), ('\u{FE62}', '\u{FE62}', MathSymbol), ('\u{FE63}', '\u{FE63}', DashPunctuation),
+	('\u{FE64}', '\u{FE66}', MathSymbol), ('\u{FE68}', '\u{FE68}', OtherPunctuation), ('\u{FE69}', '\u{FE69}', CurrencySymbol),
+	('\u{FE6A}', '\u{FE6B}', OtherPunctuation), ('\u{FE70}', '\u{FE74}', OtherLetter), ('\u{FE76}', '\u{FEFC}', OtherLetter),
+	('\u{FEFF}', '\u{FEFF}', Format), ('\u{FF01}', '\u{FF03}', OtherPunctuation), ('\u{FF04}', '\u{FF04}', CurrencySymbol),
+	('\u{FF05}', '\u{FF07}', OtherPunctuation), ('\u{FF08}', '\u{FF08}', OpenPunctuation), ('\u{FF09}', '\u{FF09}', ClosePunctuation),
+	('\u{FF0A}', '\u{FF0A}', OtherPunctuation), ('\u{FF0B}', '\u{FF0B}', MathSymbol), ('\u{FF0C}', '\u{FF0C}', OtherPunctuation),
+	('\u{FF0D}', '\u{FF0D}', DashPunctuation), ('\u{FF0E}', '\u{FF0F}', OtherPunctuation), ('\u{FF10}', '\u{FF19}', DecimalNumber),
+	('\u{FF1A}', '\u{FF1B}', OtherPunctuation), ('\u{FF1C}', '\u{FF1E}', MathSymbol), ('\u{FF1F}', '\u{FF20}', OtherPunctuation),
+	('\u{FF21}', '\u{FF3A}', UppercaseLetter), ('\u{FF3B}', '\u{FF3B}', OpenPunctuation), ('\u{FF3C}', '\u{FF3C}', OtherPunctuation),
+	('\u{FF3D}', '\u{FF3D}', ClosePunctuation), ('\u{FF3E}', '\u{FF3E}', ModifierSymbol), ('\u{FF3F}', '\u{FF3F}', ConnectorPunctuation),
+	('\u{FF40}', '\u{FF40}', ModifierSymbol), ('\u{FF41}', '\u{FF5A}', LowercaseLetter), ('\u{FF5B}', '\u{FF5B}', OpenPunctuation),
+	('\u{FF5C}', '\u{FF5C}', MathSymbol), ('\u{FF5D}', '\u{FF5D}', ClosePunctuation), ('\u{FF5E}', '\u{FF5E}', MathSymbol),
+	('\u{FF5F}', '\u{FF5F}', OpenPunctuation), ('\u{FF60}', '\u{FF60}', ClosePunctuation), ('\u{FF61}', '\u{FF61}', OtherPunctuation),
+	('\u{FF62}', '\u{FF62}', OpenPunctuation), ('\u{FF63}', '\u{FF63}', ClosePunctuation), ('\u{FF64}', '\u{FF65}', OtherPunctuation),
+	('\u{FF66}', '\u{FF6F}', OtherLetter), ('\u{FF70}', '\u{FF70}', ModifierLetter), ('\u{FF71}', '\u{FF9D}', OtherLetter),
+	('\u{FF9E}', '\u{FF9F}', ModifierLetter), ('\u{FFA0}', '\u{FFBE}', OtherLetter), ('\u{FFC2}', '\u{FFC7}', OtherLetter),
+	('\u{FFCA}', '\u{FFCF}', OtherLetter), ('\u{FFD2}', '\u{FFD7}', OtherLetter), ('\u{FFDA}', '\u{FFDC}', OtherLetter),
+	('\u{FFE0}', '\u{FFE1}', CurrencySymbol), ('\u{FFE2}', '\u{FFE2}', MathSymbol), ('\u{FFE3}', '\u{FFE3}', ModifierSymbol),
+	('\u{FFE4}', '\u{FFE4}', OtherSymbol), ('\u{FFE5}', '\u{FFE6}', CurrencySymbol), ('\u{FFE8}', '\u{FFE8}', OtherSymbol),
+	('\u{FFE9}', '\u{FFEC}', MathSymbol), ('\u{FFED}', '\u{FFEE}', OtherSymbol), ('\u{FFF9}', '\u{FFFB}', Format),
+	('\u{FFFC}', '\u{FFFD}', OtherSymbol), ('\u{10000}', '\u{1000B}', OtherLetter), ('\u{1000D}', '\u{10026}', OtherLetter),
+	('\u{10028}', '\u{1003A}', OtherLetter), ('\u{1003C}', '\u{1003D}', OtherLetter), ('\u{1003F}', '\u{1004D}', OtherLetter),
+	('\u{10050}', '\u{1005D}', OtherLetter), ('\u{10080}', '\u{100FA}', OtherLetter), ('\u{10100}', '\u{10102}', OtherPunctuation),
+	('\u{10107}', '\u{10133}', OtherNumber), ('\u{10137}', '\u{1013F}', OtherSymbol), ('\u{10140}', '\u{10174}', LetterNumber),
+	('\u{10175}', '\u{10178}', OtherNumber), ('\u{10179}', '\u{10189}', OtherSymbol), ('\u{1018A}', '\u{1018B}', OtherNumber),
+	('\u{1018C}', '\u{1018E}', OtherSymbol), ('\u{10190}', '\u{1019C}', OtherSymbol), ('\u{101A0}', '\u{101A0}', OtherSymbol),
+	('\u{101D0}', '\u{101FC}', OtherSymbol), ('\u{101FD}', '\u{101FD}', NonspacingMark), ('\u{10280}', '\u{1029C}', OtherLetter),
+	('\u{102A0}', '\u{102D0}', OtherLetter), ('\u{102E0}', '\u{102E0}', NonspacingMark), ('\u{102E1}', '\u{102FB}', OtherNumber),
+	('\u{10300}', '\u{1031F}', OtherLetter), ('\u{10320}', '\u{10323}', OtherNumber), ('\u{1032D}', '\u{10340}', OtherLetter),
+	('\u{10341}', '\u{10341}', LetterNumber), ('\u{10342}', '\u{10349}', OtherLetter), ('\u{1034A}', '\u{1034A}', LetterNumber),
+	('\u{10350}', '\u{10375}', OtherLetter), ('\u{10376}', '\u{1037A}', NonspacingMark), ('\u{10380}', '\u{1039D}', OtherLetter),
+	('\u{1039F}', '\u{1039F}', OtherPunctuation), ('\u{103A0}', '\u{103C3}', OtherLetter), ('\u{103C8}', '\u{103CF}', OtherLetter),
+	('\u{103D0}', '\u{103D0}', OtherPunctuation), ('\u{103D1}', '\u{103D5}', LetterNumber), ('\u{10400}', '\u{10427}', UppercaseLetter),
+	('\u{10428}', '\u{1044F}', LowercaseLetter), ('\u{10450}', '\u{1049D}', OtherLetter), ('\u{104A0}', '\u{104A9}', DecimalNumber),
+	('\u{104B0}', '\u{104D3}', UppercaseLetter), ('\u{104D8}', '\u{104FB}', LowercaseLetter), ('\u{10500}', '\u{10527}', OtherLetter),
+	('\u{10530}', '\u{10563}', OtherLetter), ('\u{1056F}', '\u{1056F}', OtherPunctuation), ('\u{10570}', '\u{1057A}', UppercaseLetter),
+	('\u{1057C}', '\u{1058A}', UppercaseLetter), ('\u{1058C}', '\u{10592}', UppercaseLetter), ('\u{10594}', '\u{10595}', UppercaseLetter),
+	('\u{10597}', '\u{105A1}', LowercaseLetter), ('\u{105A3}', '\u{105B1}', LowercaseLetter), ('\u{105B3}', '\u{105B9}', LowercaseLetter),
+	('\u{105BB}', '\u{105BC}', LowercaseLetter), ('\u{10600}', '\u{10736}', OtherLetter), ('\u{10740}', '\u{10755}', OtherLetter),
+	('\u{10760}', '\u{10767}', OtherLetter), ('\u{10780}', '\u{10785}', ModifierLetter), ('\u{10787}', '\u{107B0}', ModifierLetter),
+	('\u{107B2}', '\u{107BA}', ModifierLetter), ('\u{10800}', '\u{10805}', OtherLetter), ('\u{10808}', '\u{10808}', OtherLetter),
+	('\u{1080A}', '\u{10835}', OtherLetter), ('\u{10837}', '\u{10838}', OtherLetter), ('\u{1083C}', '\u{1083C}', OtherLetter),
+	('\u{1083F}', '\u{10855}', OtherLetter), ('\u{10857}', '\u{10857}', OtherPunctuation), ('\u{10858}', '\u{1085F}', OtherNumber),
+	('\u{10860}', '\u{10876}', OtherLetter), ('\u{10877}', '\u{10878}', OtherSymbol), ('\u{10879}', '\u{1087F}', OtherNumber),
+	('\u{10880}', '\u{1089E}', OtherLetter), ('\u{108A7}', '\u{108AF}', OtherNumber), ('\u{108E0}', '\u{108F2}', OtherLetter),
+	('\u{108F4}', '\u{108F5}', OtherLetter), ('\u{108FB}', '\u{108FF}', OtherNumber), ('\u{10900}', '\u{10915}', OtherLetter),
+	('\u{10916}', '\u{1091B}', OtherNumber), ('\u{1091F}', '\u{1091F}', OtherPunctuation), ('\u{10920}', '\u{10939}', OtherLetter),
+	('\u{1093F}', '\u{1093F}', OtherPunctuation), ('\u{10980}', '\u{109B7}', OtherLetter), ('\u{109BC}', '\u{109BD}', OtherNumber),
+	('\u{109BE}', '\u{109BF}', OtherLetter), ('\u{109C0}', '\u{109CF}', OtherNumber), ('\u{109D2}', '\u{109FF}', OtherNumber),
+	('\u{10A00}', '\u{10A00}', OtherLetter), ('\u{10A01}', '\u{10A03}', NonspacingMark), ('\u{10A05}', '\u{10A06}', NonspacingMark),
+	('\u{10A0C}', '\u{10A0F}', NonspacingMark), ('\u{10A10}', '\u{10A13}', OtherLetter), ('\u{10A15}', '\u{10A17}', OtherLetter),
+	('\u{10A19}', '\u{10A35}', OtherLetter), ('\u{10A38}', '\u{10A3A}', NonspacingMark), ('\u{10A3F}', '\u{10A3F}', NonspacingMark),
+	('\u{10A40}', '\u{10A48}', OtherNumber), ('\u{10A50}', '\u{10A58}', OtherPunctuation), ('\u{10A60}', '\u{10A7C}', OtherLetter),
+	('\u{10A7D}', '\u{10A7E}', OtherNumber), ('\u{10A7F}', '\u{10A7F}', OtherPunctuation), ('\u{10A80}', '\u{10A9C}', OtherLetter),
+	('\u{10A9D}', '\u{10A9F}', OtherNumber), ('\u{10AC0}', '\u{10AC7}', OtherLetter), ('\u{10AC8}', '\u{10AC8}', OtherSymbol),
+	('\u{10AC9}', '\u{10AE4}', OtherLetter), ('\u{10AE5}', '\u{10AE6}', NonspacingMark), ('\u{10AEB}', '\u{10AEF}', OtherNumber),
+	('\u{10AF0}', '\u{10AF6}', OtherPunctuation), ('\u{10B00}', '\u{10B35}', OtherLetter), ('\u{10B39}', '\u{10B3F}', OtherPunctuation),
+	('\u{10B40}', '\u{10B55}', OtherLetter), ('\u{10B58}', '\u{10B5F}', OtherNumber), ('\u{10B60}', '\u{10B72}', OtherLetter),
+	('\u{10B78}', '\u{10B7F}', OtherNumber), ('\u{10B80}', '\u{10B91}', OtherLetter), ('\u{10B99}', '\u{10B9C}', OtherPunctuation),
+	('\u{10BA9}', '\u{10BAF}', OtherNumber), ('\u{10C00}', '\u{10C48}', OtherLetter), ('\u{10C80}', '\u{10CB2}', UppercaseLetter),
+	('\u{10CC0}', '\u{10CF2}', LowercaseLetter), ('\u{10CFA}', '\u{10CFF}', OtherNumber), ('\u{10D00}', '\u{10D23}', OtherLetter),
+	('\u{10D24}', '\u{10D27}', NonspacingMark), ('\u{10D30}', '\u{10D39}', DecimalNumber), ('\u{10E60}', '\u{10E7E}', OtherNumber),
+	('\u{10E80}', '\u{10EA9}', OtherLetter), ('\u{10EAB}', '\u{10EAC}', NonspacingMark), ('\u{10EAD}', '\u{10EAD}', DashPunctuation),
+	('\u{10EB0}', '\u{10EB1}', OtherLetter), ('\u{10F00}', '\u{10F1C}', OtherLetter), ('\u{10F1D}', '\u{10F26}', OtherNumber),
+	('\u{10F27}', '\u{10F27}', OtherLetter), ('\u{10F30}', '\u{10F45}', OtherLetter), ('\u{10F46}', '\u{10F50}', NonspacingMark),
+	('\u{10F51}', '\u{10F54}', OtherNumber), ('\u{10F55}', '\u{10F59}', OtherPunctuation), ('\u{10F70}', '\u{10F81}', OtherLetter),
+	('\u{10F82}', '\u{10F85}', NonspacingMark), ('\u{10F86}', '\u{10F89}', OtherPunctuation), ('\u{10FB0}', '\u{10FC4}', OtherLetter),
+	('\u{10FC5}', '\u{10FCB}', OtherNumber), ('\u{10FE0}', '\u{10FF6}', OtherLetter), ('\u{11000}', '\u{11000}', SpacingMark),
+	('\u{11001}', '\u{11001}', NonspacingMark), ('\u{11002}', '\u{11002}', SpacingMark), ('\u{11003}', '\u{11037}', OtherLetter),
+	('\u{11038}', '\u{11046}', NonspacingMark), ('\u{11047}', '\u{1104D}', OtherPunctuation), ('\u{11052}', '\u{11065}', OtherNumber),
+	('\u{11066}', '\u{1106F}', DecimalNumber), ('\u{11070}', '\u{11070}', NonspacingMark), ('\u{11071}', '\u{11072}', OtherLetter),
+	('\u{11073}', '\u{11074}', NonspacingMark), ('\u{11075}', '\u{11075}', OtherLetter), ('\u{1107F}', '\u{11081}', NonspacingMark),
+	('\u{11082}', '\u{11082}', SpacingMark), ('\u{11083}', '\u{110AF}', OtherLetter), ('\u{110B0}', '\u{110B2}', SpacingMark),
+	('\u{110B3}', '\u{110B6}', NonspacingMark), ('\u{110B7}', '\u{110B8}', SpacingMark), ('\u{110B9}', '\u{110BA}', NonspacingMark),
+	('\u{110BB}', '\u{110BC}', OtherPunctuation), ('\u{110BD}', '\u{110BD}', Format), ('\u{110BE}', '\u{110C1}', OtherPunctuation),
+	('\u{110C2}', '\u{110C2}', NonspacingMark), ('\u{110CD}', '\u{110CD}', Format), ('\u{110D0}', '\u{110E8}', OtherLetter),
+	('\u{110F0}', '\u{110F9}', DecimalNumber), ('\u{11100}', '\u{11102}', NonspacingMark), ('\u{11103}', '\u{11126}', OtherLetter),
+	('\u{11127}', '\u{1112B}', NonspacingMark), ('\u{1112C}', '\u{1112C}', SpacingMark), ('\u{1112D}', '\u{11134}', NonspacingMark),
+	('\u{11136}', '\u{1113F}', DecimalNumber), ('\u{11140}', '\u{11143}', OtherPunctuation), ('\u{11144}', '\u{11144}', OtherLetter),
+	('\u{11145}', '\u{11146}', SpacingMark), ('\u{11147}', '\u{11147}', OtherLetter), ('\u{11150}', '\u{11172}', OtherLetter),
+	('\u{11173}', '\u{11173}', NonspacingMark), ('\u{11174}', '\u{11175}', OtherPunctuation), ('\u{11176}', '\u{11176}', OtherLetter),
+	('\u{11180}', '\u{11181}', NonspacingMark), ('\u{11182}', '\u{11182}', SpacingMark), ('\u{11183}', '\u{111B2}', OtherLetter),
+	('\u{111B3}', '\u{111B5}', SpacingMark), ('\u{111B6}', '\u{111BE}', NonspacingMark), ('\u{111BF}', '\u{111C0}', SpacingMark),
+	('\u{111C1}', '\u{111C4}', OtherLetter), ('\u{111C5}', '\u{111C8}', OtherPunctuation), ('\u{111C9}', '\u{111CC}', NonspacingMark),
+	('\u{111CD}', '\u{111CD}', OtherPunctuation), ('\u{111CE}', '\u{111CE}', SpacingMark), ('\u{111CF}', '\u{111CF}', NonspacingMark),
+	('\u{111D0}', '\u{111D9}', DecimalNumber), ('\u{111DA}', '\u{111DA}', OtherLetter), ('\u{111DB}', '\u{111DB}', OtherPunctuation),
+	('\u{111DC}', '\u{111DC}', OtherLetter), ('\u{111DD}', '\u{111DF}', OtherPunctuation), ('\u{111E1}', '\u{111F4}', OtherNumber),
+	('\u{11200}', '\u{11211}', OtherLetter), ('\u{11213}', '\u{1122B}', OtherLetter), ('\u{1122C}', '\u{1122E}', SpacingMark),
+	('\u{1122F}', '\u{11231}', NonspacingMark), ('\u{11232}', '\u{11233}', SpacingMark), ('\u{11234}', '\u{11234}', NonspacingMark),
+	('\u{11235}', '\u{11235}', SpacingMark), ('\u{11236}', '\u{11237}', NonspacingMark), ('\u{11238}', '\u{1123D}', OtherPunctuation),
+	('\u{1123E}', '\u{1123E}', NonspacingMark), ('\u{11280}', '\u{11286}', OtherLetter), ('\u{11288}', '\u{11288}', OtherLetter),
+	('\u{1128A}', '\u{1128D}', OtherLetter), ('\u{1128F}', '\u{1129D}', OtherLetter), ('\u{1129F}', '\u{112A8}', OtherLetter),
+	('\u{112A9}', '\u{112A9}', OtherPunctuation), ('\u{112B0}', '\u{112DE}', OtherLetter), ('\u{112DF}', '\u{112DF}', NonspacingMark),
+	('\u{112E0}', '\u{112E2}', SpacingMark), ('\u{112E3}', '\u{112EA}', NonspacingMark), ('\u{112F0}', '\u{112F9}', DecimalNumber),
+	('\u{11300}', '\u{11301}', NonspacingMark), ('\u{11302}', '\u{11303}', SpacingMark), ('\u{11305}', '\u{1130C}', OtherLetter),
+	('\u{1130F}', '\u{11310}', OtherLetter), ('\u{11313}', '\u{11328}', OtherLetter), ('\u{1132A}', '\u{11330}', OtherLetter),
+	('\u{11332}', '\u{11333}', OtherLetter), ('\u{11335}', '\u{11339}', OtherLetter), ('\u{1133B}', '\u{1133C}', NonspacingMark),
+	('\u{1133D}', '\u{1133D}', OtherLetter), ('\u{1133E}', '\u{1133F}', SpacingMark), ('\u{11340}', '\u{11340}', NonspacingMark),
+	('\u{11341}', '\u{11344}', SpacingMark), ('\u{11347}', '\u{11348}', SpacingMark), ('\u{1134B}', '\u{1134D}', SpacingMark),
+	('\u{11350}', '\u{11350}', OtherLetter), ('\u{11357}', '\u{11357}', SpacingMark), ('\u{1135D}', '\u{11361}', OtherLetter),
+	('\u{11362}', '\u{11363}', SpacingMark), ('\u{11366}', '\u{1136C}', NonspacingMark), ('\u{11370}', '\u{11374}', NonspacingMark),
+	('\u{11400}', '\u{11434}', OtherLetter), ('\u{11435}', '\u{11437}', SpacingMark), ('\u{11438}', '\u{1143F}', NonspacingMark),
+	('\u{11440}', '\u{11441}', SpacingMark), ('\u{11442}', '\u{11444}', NonspacingMark), ('\u{11445}', '\u{11445}', SpacingMark),
+	('\u{11446}', '\u{11446}', NonspacingMark), ('\u{11447}', '\u{1144A}', OtherLetter), ('\u{1144B}', '\u{1144F}', OtherPunctuation),
+	('\u{11450}', '\u{11459}', DecimalNumber), ('\u{1145A}', '\u{1145B}', OtherPunctuation), ('\u{1145D}', '\u{1145D}', OtherPunctuation),
+	('\u{1145E}', '\u{1145E}', NonspacingMark), ('\u{1145F}', '\u{11461}', OtherLetter), ('\u{11480}', '\u{114AF}', OtherLetter),
+	('\u{114B0}', '\u{114B2}', SpacingMark), ('\u{114B3}', '\u{114B8}', NonspacingMark), ('\u{114B9}', '\u{114B9}', SpacingMark),
+	('\u{114BA}', '\u{114BA}', NonspacingMark), ('\u{114BB}', '\u{114BE}', SpacingMark), ('\u{114BF}', '\u{114C0}', NonspacingMark),
+	('\u{114C1}', '\u{114C1}', SpacingMark), ('\u{114C2}', '\u{114C3}', NonspacingMark), ('\u{114C4}', '\u{114C5}', OtherLetter),
+	('\u{114C6}', '\u{114C6}', OtherPunctuation), ('\u{114C7}', '\u{114C7}', OtherLetter), ('\u{114D0}', '\u{114D9}', DecimalNumber),
+	('\u{11580}', '\u{115AE}', OtherLetter), ('\u{115AF}', '\u{115B1}', SpacingMark), ('\u{115B2}', '\u{115B5}', NonspacingMark),
+	('\u{115B8}', '\u{115BB}', SpacingMark), ('\u{115BC}', '\u{115BD}', NonspacingMark), ('\u{115BE}', '\u{115BE}', SpacingMark),
+	('\u{115BF}', '\u{115C0}', NonspacingMark), ('\u{115C1}', '\u{115D7}', OtherPunctuation), ('\u{115D8}', '\u{115DB}', OtherLetter),
+	('\u{115DC}', '\u{115DD}', NonspacingMark), ('\u{11600}', '\u{1162F}', OtherLetter), ('\u{11630}', '\u{11632}', SpacingMark),
+	('\u{11633}', '\u{1163A}', NonspacingMark), ('\u{1163B}', '\u{1163C}', SpacingMark), ('\u{1163D}', '\u{1163D}', NonspacingMark),
+	('\u{1163E}', '\u{1163E}', SpacingMark), ('\u{1163F}', '\u{11640}', NonspacingMark), ('\u{11641}', '\u{11643}', OtherPunctuation),
+	('\u{11644}', '\u{11644}', OtherLetter), ('\u{11650}', '\u{11659}', DecimalNumber), ('\u{11660}', '\u{1166C}', OtherPunctuation),
+	('\u{11680}', '\u{116AA}', OtherLetter), ('\u{116AB}', '\u{116AB}', NonspacingMark), ('\u{116AC}', '\u{116AC}', SpacingMark),
+	('\u{116AD}', '\u{116AD}', NonspacingMark), ('\u{116AE}', '\u{116AF}', SpacingMark), ('\u{116B0}', '\u{116B5}', NonspacingMark),
+	('\u{116B6}', '\u{116B6}', SpacingMark), ('\u{116B7}', '\u{116B7}', NonspacingMark), ('\u{116B8}', '\u{116B8}', OtherLetter),
+	('\u{116B9}', '\u{116B9}', OtherPunctuation), ('\u{116C0}', '\u{116C9}', DecimalNumber), ('\u{11700}', '\u{1171A}', OtherLetter),
+	('\u{1171D}', '\u{1171F}', NonspacingMark), ('\u{11720}', '\u{11721}', SpacingMark), ('\u{11722}', '\u{11725}', NonspacingMark),
+	('\u{11726}', '\u{11726}', SpacingMark), ('\u{11727}', '\u{1172B}', NonspacingMark), ('\u{11730}', '\u{11739}', DecimalNumber),
+	('\u{1173A}', '\u{1173B}', OtherNumber), ('\u{1173C}', '\u{1173E}', OtherPunctuation), ('\u{1173F}', '\u{1173F}', OtherSymbol),
+	('\u{11740}', '\u{11746}', OtherLetter), ('\u{11800}', '\u{1182B}', OtherLetter), ('\u{1182C}', '\u{1182E}', SpacingMark),
+	('\u{1182F}', '\u{11837}', NonspacingMark), ('\u{11838}', '\u{11838}', SpacingMark), ('\u{11839}', '\u{1183A}', NonspacingMark),
+	('\u{1183B}', '\u{1183B}', OtherPunctuation), ('\u{118A0}', '\u{118BF}', UppercaseLetter), ('\u{118C0}', '\u{118DF}', LowercaseLetter),
+	('\u{118E0}', '\u{118E9}', DecimalNumber), ('\u{118EA}', '\u{118F2}', OtherNumber), ('\u{118FF}', '\u{11906}', OtherLetter),
+	('\u{11909}', '\u{11909}', OtherLetter), ('\u{1190C}', '\u{11913}', OtherLetter), ('\u{11915}', '\u{11916}', OtherLetter),
+	('\u{11918}', '\u{1192F}', OtherLetter), ('\u{11930}', '\u{11935}', SpacingMark), ('\u{11937}', '\u{11938}', SpacingMark),
+	('\u{1193B}', '\u{1193C}', NonspacingMark), ('\u{1193D}', '\u{1193D}', SpacingMark), ('\u{1193E}', '\u{1193E}', NonspacingMark),
+	('\u{1193F}', '\u{1193F}', OtherLetter), ('\u{11940}', '\u{11940}', SpacingMark), ('\u{11941}', '\u{11941}', OtherLetter),
+	('\u{11942}', '\u{11942}', SpacingMark), ('\u{11943}', '\u{11943}', NonspacingMark), ('\u{11944}', '\u{11946}', OtherPunctuation),
+	('\u{11950}', '\u{11959}', DecimalNumber), ('\u{119A0}', '\u{119A7}', OtherLetter), ('\u{119AA}', '\u{119D0}', OtherLetter),
+	('\u{119D1}', '\u{119D3}', SpacingMark), ('\u{119D4}', '\u{119D7}', NonspacingMark), ('\u{119DA}', '\u{119DB}', NonspacingMark),
+	('\u{119DC}', '\u{119DF}', SpacingMark), ('\u{119E0}', '\u{119E0}', NonspacingMark), ('\u{119E1}', '\u{119E1}', OtherLetter),
+	('\u{119E2}', '\u{119E2}', OtherPunctuation), ('\u{119E3}', '\u{119E3}', OtherLetter), ('\u{119E4}', '\u{119E4}', SpacingMark),
+	('\u{11A00}', '\u{11A00}', OtherLetter), ('\u{11A01}', '\u{11A0A}', NonspacingMark), ('\u{11A0B}', '\u{11A32}', OtherLetter),
+	('\u{11A33}', '\u{11A38}', NonspacingMark), ('\u{11A39}', '\u{11A39}', SpacingMark), ('\u{11A3A}', '\u{11A3A}', OtherLetter),
+	('\u{11A3B}', '\u{11A3E}', NonspacingMark), ('\u{11A3F}', '\u{11A46}', OtherPunctuation), ('\u{11A47}', '\u{11A47}', NonspacingMark),
+	('\u{11A50}', '\u{11A50}', OtherLetter), ('\u{11A51}', '\u{11A56}', NonspacingMark), ('\u{11A57}', '\u{11A58}', SpacingMark),
+	('\u{11A59}', '\u{11A5B}', NonspacingMark), ('\u{11A5C}', '\u{11A89}', OtherLetter), ('\u{11A8A}', '\u{11A96}', NonspacingMark),
+	('\u{11A97}', '\u{11A97}', SpacingMark), ('\u{11A98}', '\u{11A99}', NonspacingMark), ('\u{11A9A}', '\u{11A9C}', OtherPunctuation),
+	('\u{11A9D}', '\u{11A9D}', OtherLetter), ('\u{11A9E}', '\u{11AA2}', OtherPunctuation), ('\u{11AB0}', '\u{11AF8}', OtherLetter),
+	('\u{11C00}', '\u{11C08}', OtherLetter), ('\u{11C0A}', '\u{11C2E}', OtherLetter), ('\u{11C2F}', '\u{11C2F}', SpacingMark),
+	('\u{11C30}', '\u{11C36}', NonspacingMark), ('\u{11C38}', '\u{11C3D}', NonspacingMark), ('\u{11C3E}', '\u{11C3E}', SpacingMark),
+	('\u{11C3F}', '\u{11C3F}', NonspacingMark), ('\u{11C40}', '\u{11C40}', OtherLetter), ('\u{11C41}', '\u{11C45}', OtherPunctuation),
+	('\u{11C50}', '\u{11C59}', DecimalNumber), ('\u{11C5A}', '\u{11C6C}', OtherNumber), ('\u{11C70}', '\u{11C71}', OtherPunctuation),
+	('\u{11C72}', '\u{11C8F}', OtherLetter), ('\u{11C92}', '\u{11CA7}', NonspacingMark), ('\u{11CA9}', '\u{11CA9}', SpacingMark),
+	('\u{11CAA}', '\u{11CB0}', NonspacingMark), ('\u{11CB1}', '\u{11CB1}', SpacingMark), ('\u{11CB2}', '\u{11CB3}', NonspacingMark),
+	('\u{11CB4}', '\u{11CB4}', SpacingMark), ('\u{11CB5}', '\u{11CB6}', NonspacingMark), ('\u{11D00}', '\u{11D06}', OtherLetter),
+	('\u{11D08}', '\u{11D09}', OtherLetter), ('\u{11D0B}', '\u{11D30}', OtherLetter), ('\u{11D31}', '\u{11D36}', NonspacingMark),
+	('\u{11D3A}', '\u{11D3A}', NonspacingMark), ('\u{11D3C}', '\u{11D3D}', NonspacingMark), ('\u{11D3F}', '\u{11D45}', NonspacingMark),
+	('\u{11D46}', '\u{11D46}', OtherLetter), ('\u{11D47}', '\u{11D47}', NonspacingMark), ('\u{11D50}', '\u{11D59}', DecimalNumber),
+	('\u{11D60}', '\u{11D65}', OtherLetter), ('\u{11D67}', '\u{11D68}', OtherLetter), ('\u{11D6A}', '\u{11D89}', OtherLetter),
+	('\u{11D8A}', '\u{11D8E}', SpacingMark), ('\u{11D90}', '\u{11D91}', NonspacingMark), ('\u{11D93}', '\u{11D94}', SpacingMark),
+	('\u{11D95}', '\u{11D95}', NonspacingMark), ('\u{11D96}', '\u{11D96}', SpacingMark), ('\u{11D97}', '\u{11D97}', NonspacingMark),
+	('\u{11D98}', '\u{11D98}', OtherLetter), ('\u{11DA0}', '\u{11DA9}', DecimalNumber), ('\u{11EE0}', '\u{11EF2}', OtherLetter),
+	('\u{11EF3}', '\u{11EF4}', NonspacingMark), ('\u{11EF5}', '\u{11EF6}', SpacingMark), ('\u{11EF7}', '\u{11EF8}', OtherPunctuation),
+	('\u{11FB0}', '\u{11FB0}', OtherLetter), ('\u{11FC0}', '\u{11FD4}', OtherNumber), ('\u{11FD5}', '\u{11FDC}', OtherSymbol),
+	('\u{11FDD}', '\u{11FE0}', CurrencySymbol), ('\u{11FE1}', '\u{11FF1}', OtherSymbol), ('\u{11FFF}', '\u{11FFF}', OtherPunctuation),
+	('\u{12000}', '\u{12399}', OtherLetter), ('\u{12400}', '\u{1246E}', LetterNumber), ('\u{12470}', '\u{12474}', OtherPunctuation),
+	('\u{12480}', '\u{12543}', OtherLetter), ('\u{12F90}', '\u{12FF0}', OtherLetter), ('\u{12FF1}', '\u{12FF2}', OtherPunctuation),
+	('\u{13000}', '\u{1342E}', OtherLetter), ('\u{13430}', '\u{13438}', Format), ('\u{14400}', '\u{14646}', OtherLetter),
+	('\u{16800}', '\u{16A38}', OtherLetter), ('\u{16A40}', '\u{16A5E}', OtherLetter), ('\u{16A60}', '\u{16A69}', DecimalNumber),
+	('\u{16A6E}', '\u{16A6F}', OtherPunctuation), ('\u{16A70}', '\u{16ABE}', OtherLetter), ('\u{16AC0}', '\u{16AC9}', DecimalNumber),
+	('\u{16AD0}', '\u{16AED}', OtherLetter), ('\u{16AF0}', '\u{16AF4}', NonspacingMark), ('\u{16AF5}', '\u{16AF5}', OtherPunctuation),
+	('\u{16B00}', '\u{16B2F}', OtherLetter), ('\u{16B30}', '\u{16B36}', NonspacingMark), ('\u{16B37}', '\u{16B3B}', OtherPunctuation),
+	('\u{16B3C}', '\u{16B3F}', OtherSymbol), ('\u{16B40}', '\u{16B43}', ModifierLetter), ('\u{16B44}', '\u{16B44}', OtherPunctuation),
+	('\u{16B45}', '\u{16B45}', OtherSymbol), ('\u{16B50}', '\u{16B59}', DecimalNumber), ('\u{16B5B}', '\u{16B61}', OtherNumber),
+	('\u{16B63}', '\u{16B77}', OtherLetter), ('\u{16B7D}', '\u{16B8F}', OtherLetter), ('\u{16E40}', '\u{16E5F}', UppercaseLetter),
+	('\u{16E60}', '\u{16E7F}', LowercaseLetter), ('\u{16E80}', '\u{16E96}', OtherNumber), ('\u{16E97}', '\u{16E9A}', OtherPunctuation),
+	('\u{16F00}', '\u{16F4A}', OtherLetter), ('\u{16F4F}', '\u{16F4F}', NonspacingMark), ('\u{16F50}', '\u{16F50}', OtherLetter),
+	('\u{16F51}', '\u{16F87}', SpacingMark), ('\u{16F8F}', '\u{16F92}', NonspacingMark), ('\u{16F93}', '\u{16F9F}', ModifierLetter),
+	('\u{16FE0}', '\u{16FE1}', ModifierLetter), ('\u{16FE2}', '\u{16FE2}', OtherPunctuation), ('\u{16FE3}', '\u{16FE3}', ModifierLetter),
+	('\u{16FE4}', '\u{16FE4}', NonspacingMark), ('\u{16FF0}', '\u{16FF1}', SpacingMark), ('\u{17000}', '\u{187F7}', OtherLetter),
+	('\u{18800}', '\u{18CD5}', OtherLetter), ('\u{18D00}', '\u{18D08}', OtherLetter), ('\u{1AFF0}', '\u{1AFF3}', ModifierLetter),
+	('\u{1AFF5}', '\u{1AFFB}', ModifierLetter), ('\u{1AFFD}', '\u{1AFFE}', ModifierLetter), ('\u{1B000}', '\u{1B122}', OtherLetter),
+	('\u{1B150}', '\u{1B152}', OtherLetter), ('\u{1B164}', '\u{1B167}', OtherLetter), ('\u{1B170}', '\u{1B2FB}', OtherLetter),
+	('\u{1BC00}', '\u{1BC6A}', OtherLetter), ('\u{1BC70}', '\u{1BC7C}', OtherLetter), ('\u{1BC80}', '\u{1BC88}', OtherLetter),
+	('\u{1BC90}', '\u{1BC99}', OtherLetter), ('\u{1BC9C}', '\u{1BC9C}', OtherSymbol), ('\u{1BC9D}', '\u{1BC9E}', NonspacingMark),
+	('\u{1BC9F}', '\u{1BC9F}', OtherPunctuation), ('\u{1BCA0}', '\u{1BCA3}', Format), ('\u{1CF00}', '\u{1CF2D}', NonspacingMark),
+	('\u{1CF30}', '\u{1CF46}', NonspacingMark), ('\u{1CF50}', '\u{1CFC3}', OtherSymbol), ('\u{1D000}', '\u{1D0F5}', OtherSymbol),
+	('\u{1D100}', '\u{1D126}', OtherSymbol), ('\u{1D129}', '\u{1D164}', OtherSymbol), ('\u{1D165}', '\u{1D166}', SpacingMark),
+	('\u{1D167}', '\u{1D169}', NonspacingMark), ('\u{1D16A}', '\u{1D16C}', OtherSymbol), ('\u{1D16D}', '\u{1D172}', SpacingMark),
+	('\u{1D173}', '\u{1D17A}', Format), ('\u{1D17B}', '\u{1D182}', NonspacingMark), ('\u{1D183}', '\u{1D184}', OtherSymbol),
+	('\u{1D185}', '\u{1D18B}', NonspacingMark), ('\u{1D18C}', '\u{1D1A9}', OtherSymbol), ('\u{1D1AA}', '\u{1D1AD}', NonspacingMark),
+	('\u{1D1AE}', '\u{1D1EA}', OtherSymbol), ('\u{1D200}', '\u{1D241}', OtherSymbol), ('\u{1D242}', '\u{1D244}', NonspacingMark),
+	('\u{1D245}', '\u{1D245}', OtherSymbol), ('\u{1D2E0}', '\u{1D2F3}', OtherNumber), ('\u{1D300}', '\u{1D356}', OtherSymbol),
+	('\u{1D360}', '\u{1D378}', OtherNumber), ('\u{1D400}', '\u{1D419}', UppercaseLetter), ('\u{1D41A}', '\u{1D433}', LowercaseLetter),
+	('\u{1D434}', '\u{1D44D}', UppercaseLetter), ('\u{1D44E}', '\u{1D454}', LowercaseLetter), ('\u{1D456}', '\u{1D467}', LowercaseLetter),
+	('\u{1D468}', '\u{1D481}', UppercaseLetter), ('\u{1D482}', '\u{1D49B}', LowercaseLetter), ('\u{1D49C}', '\u{1D49C}', UppercaseLetter),
+	('\u{1D49E}', '\u{1D49F}', UppercaseLetter), ('\u{1D4A2}', '\u{1D4A2}', UppercaseLetter), ('\u{1D4A5}', '\u{1D4A6}', UppercaseLetter),
+	('\u{1D4A9}', '\u{1D4AC}', UppercaseLetter), ('\u{1D4AE}', '\u{1D4B5}', UppercaseLetter), ('\u{1D4B6}', '\u{1D4B9}', LowercaseLetter),
+	('\u{1D4BB}', '\u{1D4BB}', LowercaseLetter), ('\u{1D4BD}', '\u{1D4C3}', LowercaseLetter), ('\u{1D4C5}', '\u{1D4CF}', LowercaseLetter),
+	('\u{1D4D0}', '\u{1D4E9}', UppercaseLetter), ('\u{1D4EA}', '\u{1D503}', LowercaseLetter), ('\u{1D504}', '\u{1D505}', UppercaseLetter),
+	('\u{1D507}', '\u{1D50A}', UppercaseLetter), ('\u{1D50D}', '\u{1D514}', UppercaseLetter), ('\u{1D516}', '\u{1D51C}', UppercaseLetter),
+	('\u{1D51E}', '\u{1D537}', LowercaseLetter), ('\u{1D538}', '\u{1D539}', UppercaseLetter), ('\u{1D53B}', '\u{1D53E}', UppercaseLetter),
+	('\u{1D540}', '\u{1D544}', UppercaseLetter), ('\u{1D546}', '\u{1D546}', UppercaseLetter), ('\u{1D54A}', '\u{1D550}', UppercaseLetter),
+	('\u{1D552}', '\u{1D56B}', LowercaseLetter), ('\u{1D56C}', '\u{1D585}', UppercaseLetter), ('\u{1D586}', '\u{1D59F}', LowercaseLetter),
+	('\u{1D5A0}', '\u{1D5B9}', UppercaseLetter), ('\u{1D5BA}', '\u{1D5D3}', LowercaseLetter), ('\u{1D5D4}', '\u{1D5ED}', UppercaseLetter),
+	('\u{1D5EE}', '\u{1D607}', LowercaseLetter), ('\u{1D608}', '\u{1D621}', UppercaseLetter), ('\u{1D622}', '\u{1D63B}', LowercaseLetter),
+	('\u{1D63C}', '\u{1D655}', UppercaseLetter), ('\u{1D656}', '\u{1D66F}', LowercaseLetter), ('\u{1D670}', '\u{1D689}', UppercaseLetter),
+	('\u{1D68A}', '\u{1D6A5}', LowercaseLetter), ('\u{1D6A8}', '\u{1D6C0}', UppercaseLetter), ('\u{1D6C1}', '\u{1D6C1}', MathSymbol),
+	('\u{1D6C2}', '\u{1D6DA}', LowercaseLetter), ('\u{1D6DB}', '\u{1D6DB}', MathSymbol), ('\u{1D6DC}', '\u{1D6E1}', LowercaseLetter),
+	('\u{1D6E2}', '\u{1D6FA}', UppercaseLetter), ('\u{1D6FB}', '\u{1D6FB}', MathSymbol), ('\u{1D6FC}', '\u{1D714}', LowercaseLetter),
+	('\u{1D715}', '\u{1D715}', MathSymbol), ('\u{1D716}', '\u{1D71B}', LowercaseLetter), ('\u{1D71C}', '\u{1D734}', UppercaseLetter),
+	('\u{1D735}', '\u{1D735}', MathSymbol), ('\u{1D736}', '\u{1D74E}', LowercaseLetter), ('\u{1D74F}', '\u{1D74F}', MathSymbol),
+	('\u{1D750}', '\u{1D755}', LowercaseLetter), ('\u{1D756}', '\u{1D76E}', UppercaseLetter), ('\u{1D76F}', '\u{1D76F}', MathSymbol),
+	('\u{1D770}', '\u{1D788}', LowercaseLetter), ('\u{1D789}', '\u{1D789}', MathSymbol), ('\u{1D78A}', '\u{1D78F}', LowercaseLetter),
+	('\u{1D790}', '\u{1D7A8}', UppercaseLetter), ('\u{1D7A9}', '\u{1D7A9}', MathSymbol), ('\u{1D7AA}', '\u{1D7C2}', LowercaseLetter),
+	('\u{1D7C3}', '\u{1D7C3}', MathSymbol), ('\u{1D7C4}', '\u{1D7C9}', LowercaseLetter), ('\u{1D7CA}', '\u{1D7CA}', UppercaseLetter),
+	('\u{1D7CB}', '\u{1D7CB}', LowercaseLetter), ('\u{1D7CE}', '\u{1D7FF}', DecimalNumber), ('\u{1D800}', '\u{1D9FF}', OtherSymbol),
+	('\u{1DA00}', '\u{1DA36}', NonspacingMark), ('\u{1DA37}', '\u{1DA3A}', OtherSymbol), ('\u{1DA3B}', '\u{1DA6C}', NonspacingMark),
+	('\u{1DA6D}', '\u{1DA74}', OtherSymbol), ('\u{1DA75}', '\u{1DA75}', NonspacingMark), ('\u{1DA76}', '\u{1DA83}', OtherSymbol),
+	('\u{1DA84}', '\u{1DA84}', NonspacingMark), ('\u{1DA85}', '\u{1DA86}', OtherSymbol), ('\u{1DA87}', '\u{1DA8B}', OtherPunctuation),
+	('\u{1DA9B}', '\u{1DA9F}', NonspacingMark), ('\u{1DAA1}', '\u{1DAAF}', NonspacingMark), ('\u{1DF00}', '\u{1DF09}', LowercaseLetter),
+	('\u{1DF0A}', '\u{1DF0A}', OtherLetter), ('\u{1DF0B}', '\u{1DF1E}', LowercaseLetter), ('\u{1E000}', '\u{1E006}', NonspacingMark),
+	('\u{1E008}', '\u{1E018}', NonspacingMark), ('\u{1E01B}', '\u{1E021}', NonspacingMark), ('\u{1E023}', '\u{1E024}', NonspacingMark),
+	('\u{1E026}', '\u{1E02A}', NonspacingMark), ('\u{1E100}', '\u{1E12C}', OtherLetter), ('\u{1E130}', '\u{1E136}', NonspacingMark),
+	('\u{1E137}', '\u{1E13D}', ModifierLetter), ('\u{1E140}', '\u{1E149}', DecimalNumber), ('\u{1E14E}', '\u{1E14E}', OtherLetter),
+	('\u{1E14F}', '\u{1E14F}', OtherSymbol), ('\u{1E290}', '\u{1E2AD}', OtherLetter), ('\u{1E2AE}', '\u{1E2AE}', NonspacingMark),
+	('\u{1E2C0}', '\u{1E2EB}', OtherLetter), ('\u{1E2EC}', '\u{1E2EF}', NonspacingMark), ('\u{1E2F0}', '\u{1E2F9}', DecimalNumber),
+	('\u{1E2FF}', '\u{1E2FF}', CurrencySymbol), ('\u{1E7E0}', '\u{1E7E6}', OtherLetter), ('\u{1E7E8}', '\u{1E7EB}', OtherLetter),
+	('\u{1E7ED}', '\u{1E7EE}', OtherLetter), ('\u{1E7F0}', '\u{1E7FE}', OtherLetter), ('\u{1E800}', '\u{1E8C4}', OtherLetter),
+	('\u{1E8C7}', '\u{1E8CF}', OtherNumber), ('\u{1E8D0}', '\u{1E8D6}', NonspacingMark), ('\u{1E900}', '\u{1E921}', UppercaseLetter),
+	('\u{1E922}', '\u{1E943}', LowercaseLetter), ('\u{1E944}', '\u{1E94A}', NonspacingMark), ('\u{1E94B}', '\u{1E94B}', ModifierLetter),
+	('\u{1E950}', '\u{1E959}', DecimalNumber), ('\u{1E95E}', '\u{1E95F}', OtherPunctuation), ('\u{1EC71}', '\u{1ECAB}', OtherNumber),
+	('\u{1ECAC}', '\u{1ECAC}', OtherSymbol), ('\u{1ECAD}', '\u{1ECAF}', OtherNumber), ('\u{1ECB0}', '\u{1ECB0}', CurrencySymbol),
+	('\u{1ECB1}', '\u{1ECB4}', OtherNumber), ('\u{1ED01}', '\u{1ED2D}', OtherNumber), ('\u{1ED2E}', '\u{1ED2E}', OtherSymbol),
+	('\u{1ED2F}', '\u{1ED3D}', OtherNumber), ('\u{1EE00}', '\u{1EE03}', OtherLetter), ('\u{1EE05}', '\u{1EE1F}', OtherLetter),
+	('\u{1EE21}', '\u{1EE22}', OtherLetter), ('\u{1EE24}', '\u{1EE24}', OtherLetter), ('\u{1EE27}', '\u{1EE27}', OtherLetter),
+	('\u{1EE29}', '\u{1EE32}', OtherLetter), ('\u{1EE34}', '\u{1EE37}', OtherLetter), ('\u{1EE39}', '\u{1EE39}', OtherLetter),
+	('\u{1EE3B}', '\u{1EE3B}', OtherLetter), ('\u{1EE42}', '\u{1EE42}', OtherLetter), ('\u{1EE47}', '\u{1EE47}', OtherLetter),
+	('\u{1EE49}', '\u{1EE49}', OtherLetter), ('\u{1EE4B}', '\u{1EE4B}', OtherLetter), ('\u{1EE4D}', '\u{1EE4F}', OtherLetter),
+	('\u{1EE51}', '\u{1EE52}', OtherLetter), ('\u{1EE54}', '\u{1EE54}', OtherLetter), ('\u{1EE57}', '\u{1EE57}', OtherLetter),
+	('\u{1EE59}', '\u{1EE59}', OtherLetter), ('\u{1EE5B}', '\u{1EE5B}', OtherLetter), ('\u{1EE5D}', '\u{1EE5D}', OtherLetter),
+	('\u{1EE5F}', '\u{1EE5F}', OtherLetter), ('\u{1EE61}', '\u{1EE62}', OtherLetter), ('\u{1EE64}', '\u{1EE64}', OtherLetter),
+	('\u{1EE67}', '\u{1EE6A}', OtherLetter), ('\u{1EE6C}', '\u{1EE72}', OtherLetter), ('\u{1EE74}', '\u{1EE77}', OtherLetter),
+	('\u{1EE79}', '\u{1EE7C}', OtherLetter), ('\u{1EE7E}', '\u{1EE7E}', OtherLetter), ('\u{1EE80}', '\u{1EE89}', OtherLetter),
+	('\u{1EE8B}', '\u{1EE9B}', OtherLetter), ('\u{1EEA1}', '\u{1EEA3}', OtherLetter), ('\u{1EEA5}', '\u{1EEA9}', OtherLetter),
+	('\u{1EEAB}', '\u{1EEBB}', OtherLetter), ('\u{1EEF0}', '\u{1EEF1}', MathSymbol), ('\u{1F000}', '\u{1F02B}', OtherSymbol),
+	('\u{1F030}', '\u{1F093}', OtherSymbol), ('\u{1F0A0}', '\u{1F0AE}', OtherSymbol), ('\u{1F0B1}', '\u{1F0BF}', OtherSymbol),
+	('\u{1F0C1}', '\u{1F0CF}', OtherSymbol), ('\u{1F0D1}', '\u{1F0F5}', OtherSymbol), ('\u{1F100}', '\u{1F10C}', OtherNumber),
+	('\u{1F10D}', '\u{1F1AD}', OtherSymbol), ('\u{1F1E6}', '\u{1F202}', OtherSymbol), ('\u{1F210}', '\u{1F23B}', OtherSymbol),
+	('\u{1F240}', '\u{1F248}', OtherSymbol), ('\u{1F250}', '\u{1F251}', OtherSymbol), ('\u{1F260}', '\u{1F265}', OtherSymbol),
+	('\u{1F300}', '\u{1F3FA}', OtherSymbol), ('\u{1F3FB}', '\u{1F3FF}', ModifierSymbol), ('\u{1F400}', '\u{1F6D7}', OtherSymbol),
+	('\u{1F6DD}', '\u{1F6EC}', OtherSymbol), ('\u{1F6F0}', '\u{1F6FC}', OtherSymbol), ('\u{1F700}', '\u{1F773}', OtherSymbol),
+	('\u{1F780}', '\u{1F7D8}', OtherSymbol), ('\u{1F7E0}', '\u{1F7EB}', OtherSymbol), ('\u{1F7F0}', '\u{1F7F0}', OtherSymbol),
+	('\u{1F800}', '\u{1F80B}', OtherSymbol), ('\u{1F810}', '\u{1F847}', OtherSymbol), ('\u{1F850}', '\u{1F859}', OtherSymbol),
+	('\u{1F860}', '\u{1F887}', OtherSymbol), ('\u{1F890}', '\u{1F8AD}', OtherSymbol), ('\u{1F8B0}', '\u{1F8B1}', OtherSymbol),
+	('\u{1F900}', '\u{1FA53}', OtherSymbol), ('\u{1FA60}', '\u{1FA6D}', OtherSymbol), ('\u{1FA70}', '\u{1FA74}', OtherSymbol),
+	('\u{1FA78}', '\u{1FA7C}', OtherSymbol), ('\u{1FA80}', '\u{1FA86}', OtherSymbol), ('\u{1FA90}', '\u{1FAAC}', OtherSymbol),
+	('\u{1FAB0}', '\u{1FABA}', OtherSymbol), ('\u{1FAC0}', '\u{1FAC5}', OtherSymbol), ('\u{1FAD0}', '\u{1FAD9}', OtherSymbol),
+	('\u{1FAE0}', '\u{1FAE7}', OtherSymbol), ('\u{1FAF0}', '\u{1FAF6}', OtherSymbol), ('\u{1FB00}', '\u{1FB92}', OtherSymbol),
+	('\u{1FB94}', '\u{1FBCA}', OtherSymbol), ('\u{1FBF0}', '\u{1FBF9}', DecimalNumber), ('\u{20000}', '\u{2A6DF}', OtherLetter),
+	('\u{2A700}', '\u{2B738}', OtherLetter), ('\u{2B740}', '\u{2B81D}', OtherLetter), ('\u{2B820}', '\u{2CEA1}', OtherLetter),
+	('\u{2CEB0}', '\u{2EBE0}', OtherLetter), ('\u{2F800}', '\u{2FA1D}', OtherLetter), ('\u{30000}', '\u{3134A}', OtherLetter),
+	('\u{E0001}', '\u{E0001}', Format), ('\u{E0020}', '\u{E007F}', Format), ('\u{E0100}', '\u{E01EF}', NonspacingMark),
+	('\u{F0000}', '\u{FFFFD}', PrivateUse), ('\u{100000}', '\u{10FFFD}', PrivateUse)
+];
\ No newline at end of file